@@ -2,7 +2,6 @@
 
 use bevy::prelude::*;
 
-use bevy::app::AppExit;
 use bevy_tui::prelude::*;
 
 use ratatui::layout::{Alignment, Constraint, Layout};
@@ -21,7 +20,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // and input handling.
     App::new()
         .add_plugins(MinimalTuiPlugins)
-        .add_systems(Update, (run_basic_ui, quit_system))
+        .insert_resource(TuiQuitSettings {
+            quit_keys: vec!["q".parse()?, "ctrl+c".parse()?],
+            ..default()
+        })
+        .add_systems(Update, run_basic_ui)
         .run();
 
     // The changes to the terminal need to be undone before returning the terminal for interactive
@@ -32,16 +35,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-// This lint doesn't like values passed in but not consumed which is fair. Bevy requires the
-// `Res<_>` type to be passed by value so we unfortunately have to disable this lint wherever a
-// `Res<_>` is used but not consumed.
-#[allow(clippy::needless_pass_by_value)]
-fn quit_system(key_code: Res<ButtonInput<KeyCode>>, mut event_writer: EventWriter<AppExit>) {
-    if key_code.just_pressed(KeyCode::KeyQ) {
-        event_writer.send(AppExit);
-    }
-}
-
 fn render_ui(
     f: &mut Frame,
     keyboard: &ButtonInput<KeyCode>,
@@ -0,0 +1,144 @@
+//! Spawning a dashboard out of declarative widget bundles rather than drawing it by hand. Tab
+//! moves focus between the list and table which can then be scrolled with the arrow keys.
+
+use bevy::prelude::*;
+
+use bevy_tui::prelude::*;
+
+use ratatui::layout::Constraint;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Gauge, List, Paragraph, Row};
+
+#[derive(Component)]
+struct FrameCounter;
+
+#[derive(Component)]
+struct FrameHistory;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    initialize_terminal()?;
+
+    App::new()
+        .add_plugins(MinimalTuiPlugins)
+        .insert_resource(TuiQuitSettings {
+            quit_keys: vec!["q".parse()?, "ctrl+c".parse()?],
+            ..default()
+        })
+        .add_systems(Startup, spawn_dashboard)
+        .add_systems(Update, (update_widgets, toggle_theme))
+        .run();
+
+    teardown_terminal()?;
+
+    Ok(())
+}
+
+fn panel(title: &'static str) -> TuiBlock {
+    TuiBlock(Block::bordered().title(title))
+}
+
+fn spawn_dashboard(mut commands: Commands) {
+    commands
+        .spawn((
+            TuiNode::default(),
+            TuiLayout::vertical([
+                Constraint::Length(1),
+                Constraint::Length(3),
+                Constraint::Length(5),
+                Constraint::Min(0),
+            ]),
+        ))
+        .with_children(|root| {
+            root.spawn(ParagraphBundle {
+                paragraph: TuiParagraph(Paragraph::new(
+                    "Hello Bevy! Press 't' to toggle the theme or 'q' to quit.",
+                )),
+                ..default()
+            });
+
+            root.spawn((
+                GaugeBundle {
+                    gauge: TuiGauge(
+                        Gauge::default().gauge_style(Style::default().fg(Color::Green)),
+                    ),
+                    themed: Themed {
+                        primary: false,
+                        ..default()
+                    },
+                    ..default()
+                },
+                panel(" Progress "),
+                FrameCounter,
+            ));
+
+            root.spawn((SparklineBundle::default(), panel(" History "), FrameHistory));
+
+            root.spawn((
+                TuiNode::default(),
+                TuiLayout::horizontal([Constraint::Percentage(50); 2]),
+            ))
+            .with_children(|body| {
+                let servers = List::new(["alpha", "beta", "gamma"])
+                    .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+                body.spawn((
+                    ListBundle {
+                        list: TuiList::new(servers),
+                        themed: Themed {
+                            selection: false,
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    panel(" Servers "),
+                ));
+
+                let rows = [Row::new(["players", "12"]), Row::new(["uptime", "3d"])];
+                let mut stats = TuiTable::new(rows, [Constraint::Min(10); 2]);
+                stats.table = stats
+                    .table
+                    .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+                body.spawn((
+                    TableBundle {
+                        table: stats,
+                        themed: Themed {
+                            selection: false,
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    panel(" Stats "),
+                ));
+            });
+        });
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn toggle_theme(key_code: Res<ButtonInput<KeyCode>>, mut theme: ResMut<TuiTheme>) {
+    if key_code.just_pressed(KeyCode::KeyT) {
+        *theme = if *theme == TuiTheme::dark() {
+            TuiTheme::light()
+        } else {
+            TuiTheme::dark()
+        };
+    }
+}
+
+fn update_widgets(
+    mut frame: Local<u64>,
+    mut gauges: Query<&mut TuiGauge, With<FrameCounter>>,
+    mut sparklines: Query<&mut TuiSparkline, With<FrameHistory>>,
+) {
+    *frame += 1;
+
+    for mut gauge in &mut gauges {
+        let percent = u16::try_from(*frame % 101).unwrap_or_default();
+        gauge.0 = gauge.0.clone().percent(percent);
+    }
+
+    for mut sparkline in &mut sparklines {
+        sparkline.data.push(*frame % 7);
+        if sparkline.data.len() > 200 {
+            sparkline.data.remove(0);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! A small login form using focusable text inputs. Tab and Shift+Tab move between the fields and
+//! Escape quits.
+
+use bevy::prelude::*;
+
+use bevy::app::AppExit;
+use bevy_tui::prelude::*;
+
+use ratatui::layout::Constraint;
+use ratatui::widgets::{Block, Paragraph};
+
+#[derive(Component)]
+struct StatusLine;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    initialize_terminal()?;
+
+    App::new()
+        .add_plugins(MinimalTuiPlugins)
+        .add_systems(Startup, spawn_form)
+        .add_systems(Update, (show_submissions, quit_system))
+        .run();
+
+    teardown_terminal()?;
+
+    Ok(())
+}
+
+fn spawn_form(mut commands: Commands, mut focused: ResMut<FocusedEntity>) {
+    let mut username = None;
+
+    commands
+        .spawn((
+            TuiNode::default(),
+            TuiLayout::vertical([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Min(0),
+            ])
+            .margin(1),
+        ))
+        .with_children(|form| {
+            let field = form.spawn((
+                TextInputBundle {
+                    text_input: TextInput::new().with_placeholder("admin"),
+                    ..default()
+                },
+                TuiBlock(Block::bordered().title(" Username ")),
+            ));
+            username = Some(field.id());
+
+            form.spawn((
+                TextInputBundle {
+                    text_input: TextInput::new().with_placeholder("hunter2"),
+                    ..default()
+                },
+                TuiBlock(Block::bordered().title(" Password ")),
+            ));
+
+            form.spawn((
+                ParagraphBundle {
+                    paragraph: TuiParagraph(Paragraph::new("Press Enter to submit a field")),
+                    ..default()
+                },
+                StatusLine,
+            ));
+        });
+
+    focused.set(username);
+}
+
+fn show_submissions(
+    mut submissions: EventReader<TextInputSubmitted>,
+    mut status: Query<&mut TuiParagraph, With<StatusLine>>,
+) {
+    for submitted in submissions.read() {
+        for mut paragraph in &mut status {
+            paragraph.0 = Paragraph::new(format!("Submitted: {}", submitted.value));
+        }
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn quit_system(key_code: Res<ButtonInput<KeyCode>>, mut event_writer: EventWriter<AppExit>) {
+    if key_code.just_pressed(KeyCode::Escape) {
+        event_writer.send(AppExit);
+    }
+}
@@ -0,0 +1,80 @@
+//! Running inside of the shell rather than taking over the screen, as a progress display for a
+//! command line tool would. Finished steps are printed into the scrollback above the progress bar.
+
+use bevy::prelude::*;
+
+use bevy::app::AppExit;
+use bevy_tui::prelude::*;
+
+use ratatui::text::Line;
+use ratatui::widgets::{Gauge, Widget};
+use ratatui::Viewport;
+
+const STEPS: [&str; 5] = [
+    "Resolving dependencies",
+    "Downloading crates",
+    "Compiling sources",
+    "Linking",
+    "Packaging",
+];
+
+#[derive(Component)]
+struct Progress;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    initialize_inline_terminal()?;
+
+    App::new()
+        .add_plugins(MinimalTuiPlugins)
+        .insert_resource(TuiQuitSettings {
+            quit_keys: vec!["q".parse()?, "ctrl+c".parse()?],
+            ..default()
+        })
+        .insert_resource(TuiTerminalSettings {
+            viewport: Viewport::Inline(1),
+            ..default()
+        })
+        .add_systems(Startup, spawn_progress)
+        .add_systems(Update, advance)
+        .run();
+
+    teardown_inline_terminal()?;
+
+    println!("All steps completed");
+
+    Ok(())
+}
+
+fn spawn_progress(mut commands: Commands) {
+    commands.spawn((GaugeBundle::default(), Progress));
+}
+
+fn advance(
+    mut terminal: ResMut<bevy_tui::BevyTerminal>,
+    mut gauges: Query<&mut TuiGauge, With<Progress>>,
+    mut event_writer: EventWriter<AppExit>,
+    mut frame: Local<usize>,
+) {
+    *frame += 1;
+
+    let step = *frame / 10;
+    if *frame % 10 == 0 && step <= STEPS.len() {
+        let finished = Line::from(format!("[done] {}", STEPS[step - 1]));
+        terminal
+            .0
+            .insert_before(1, |buf| finished.render(buf.area, buf))
+            .expect("failed to write to terminal");
+    }
+
+    if step >= STEPS.len() {
+        event_writer.send(AppExit);
+    }
+
+    let percent = (*frame * 100 / (STEPS.len() * 10)).min(100);
+    let label = STEPS.get(step).copied().unwrap_or("Done");
+    for mut gauge in &mut gauges {
+        gauge.0 = Gauge::default()
+            .percent(u16::try_from(percent).unwrap_or(100))
+            .label(format!("{label} {percent}%"));
+    }
+}
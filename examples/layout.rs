@@ -0,0 +1,70 @@
+//! Composing a screen layout out of the Bevy entity hierarchy instead of by hand.
+
+use bevy::prelude::*;
+
+use bevy_tui::prelude::*;
+
+use ratatui::layout::Constraint;
+use ratatui::widgets::Paragraph;
+
+#[derive(Component)]
+struct Panel(&'static str);
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    initialize_terminal()?;
+
+    App::new()
+        .add_plugins(MinimalTuiPlugins)
+        .insert_resource(TuiQuitSettings {
+            quit_keys: vec!["q".parse()?, "ctrl+c".parse()?],
+            ..default()
+        })
+        .add_systems(Startup, spawn_layout)
+        // The areas are computed during PostUpdate, drawing afterwards shows those of this frame
+        .add_systems(PostUpdate, render_panels.after(TuiSystem::Layout))
+        .run();
+
+    teardown_terminal()?;
+
+    Ok(())
+}
+
+fn spawn_layout(mut commands: Commands) {
+    // The root node is given the entire terminal, it then splits itself into a header and a body
+    commands
+        .spawn((
+            TuiNode::default(),
+            TuiLayout::vertical([Constraint::Length(3), Constraint::Min(0)]),
+        ))
+        .with_children(|root| {
+            root.spawn((TuiNode::default(), Panel(" Header ")));
+
+            // The body is further split into a sidebar and the main content area
+            root.spawn((
+                TuiNode::default(),
+                TuiLayout::horizontal([Constraint::Length(24), Constraint::Min(0)]).spacing(1),
+            ))
+            .with_children(|body| {
+                body.spawn((TuiNode::default(), Panel(" Sidebar ")));
+                body.spawn((TuiNode::default(), Panel(" Content ")));
+            });
+        });
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn render_panels(
+    mut terminal: ResMut<bevy_tui::BevyTerminal>,
+    theme: Res<TuiTheme>,
+    panels: Query<(&TuiNode, &Panel)>,
+) {
+    terminal
+        .0
+        .draw(|f| {
+            for (node, panel) in &panels {
+                let block = theme.block().title(panel.0);
+                let content = Paragraph::new(format!("{:?}", node.area())).block(block);
+                f.render_widget(content, node.area());
+            }
+        })
+        .expect("failed to draw to terminal");
+}
@@ -2,7 +2,6 @@
 
 use bevy::prelude::*;
 
-use bevy::app::AppExit;
 use bevy_tui::prelude::*;
 
 use ratatui::layout::{Alignment, Rect};
@@ -27,6 +26,9 @@ impl Default for BoundedCamera {
 struct CanvasData([u8; 256 * 256]);
 
 impl Default for CanvasData {
+    // The canvas is intentionally a fixed size array, it only lives on the stack briefly before
+    // being moved into the Bevy world.
+    #[allow(clippy::large_stack_arrays)]
     fn default() -> Self {
         Self([0; 256 * 256])
     }
@@ -37,9 +39,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     App::new()
         .add_plugins(MinimalTuiPlugins)
+        .insert_resource(TuiQuitSettings {
+            quit_keys: vec!["q".parse()?, "ctrl+c".parse()?],
+            ..default()
+        })
         .init_resource::<BoundedCamera>()
         .init_resource::<CanvasData>()
-        .add_systems(Update, (camera_controller, run_canvas_ui, quit_system))
+        .add_systems(Update, (camera_controller, run_canvas_ui))
         .run();
 
     teardown_terminal()?;
@@ -72,14 +78,7 @@ fn camera_controller(key_code: Res<ButtonInput<KeyCode>>, mut camera: ResMut<Bou
     }
 }
 
-#[allow(clippy::needless_pass_by_value)]
-fn quit_system(key_code: Res<ButtonInput<KeyCode>>, mut event_writer: EventWriter<AppExit>) {
-    if key_code.just_pressed(KeyCode::KeyQ) {
-        event_writer.send(AppExit);
-    }
-}
-
-fn render_ui(f: &mut Frame, camera: &BoundedCamera, _canvas_data: &CanvasData) {
+fn render_ui(f: &mut Frame, theme: &TuiTheme, camera: &BoundedCamera, _canvas_data: &CanvasData) {
     // Render canvas to the entirety of the screen
     let _canvas = f.size();
 
@@ -90,11 +89,7 @@ fn render_ui(f: &mut Frame, camera: &BoundedCamera, _canvas_data: &CanvasData) {
         height: 3,
     };
 
-    let camera_position_block = ratatui::widgets::Block::default()
-        .title(" Pos ")
-        .borders(ratatui::widgets::Borders::ALL)
-        .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::White))
-        .border_type(ratatui::widgets::BorderType::Rounded);
+    let camera_position_block = theme.block().title(" Pos ");
 
     let camera_position_text_rect = camera_position_block.inner(camera_position_rect);
     f.render_widget(camera_position_block, camera_position_rect);
@@ -117,11 +112,7 @@ fn render_ui(f: &mut Frame, camera: &BoundedCamera, _canvas_data: &CanvasData) {
         height: 11,
     };
 
-    let palette_block = ratatui::widgets::Block::default()
-        .title(" Palette ")
-        .borders(ratatui::widgets::Borders::ALL)
-        .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::White))
-        .border_type(ratatui::widgets::BorderType::Rounded);
+    let palette_block = theme.block().title(" Palette ");
 
     f.render_widget(palette_block, palette_rect);
 }
@@ -129,11 +120,12 @@ fn render_ui(f: &mut Frame, camera: &BoundedCamera, _canvas_data: &CanvasData) {
 #[allow(clippy::needless_pass_by_value)]
 fn run_canvas_ui(
     mut terminal: ResMut<bevy_tui::BevyTerminal>,
+    theme: Res<TuiTheme>,
     camera: Res<BoundedCamera>,
     canvas_data: Res<CanvasData>,
 ) {
     terminal
         .0
-        .draw(|f| render_ui(f, &camera, &canvas_data))
+        .draw(|f| render_ui(f, &theme, &camera, &canvas_data))
         .expect("failed to draw to terminal");
 }
@@ -0,0 +1,94 @@
+//! A picker drawn on the controlling terminal rather than STDOUT, printing the chosen item to
+//! STDOUT once it exits so it can be used in a pipeline such as `cargo run --example picker | cat`.
+
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use bevy::app::AppExit;
+use bevy_tui::prelude::*;
+
+use crossterm::event::{Event, KeyCode as CrossKeyCode, KeyEventKind};
+use ratatui::widgets::{Block, List};
+
+const ITEMS: [&str; 4] = ["apple", "banana", "cherry", "damson"];
+
+/// Shared with `main` so the choice survives the app being dropped.
+#[derive(Clone, Default, Resource)]
+struct Choice(Arc<Mutex<Option<&'static str>>>);
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut tty = TerminalOutput::open(TuiOutput::Tty)?;
+    initialize_terminal_with(&mut tty)?;
+
+    let choice = Choice::default();
+
+    App::new()
+        .add_plugins(MinimalTuiPlugins)
+        .insert_resource(TuiTerminalSettings {
+            output: TuiOutput::Tty,
+            ..default()
+        })
+        .insert_resource(choice.clone())
+        .add_systems(Startup, spawn_picker)
+        .add_systems(Update, (pick, quit_system))
+        .run();
+
+    teardown_terminal_with(&mut tty)?;
+
+    let picked = *choice.0.lock().map_err(|_| "choice lock poisoned")?;
+    if let Some(item) = picked {
+        println!("{item}");
+    }
+
+    Ok(())
+}
+
+fn spawn_picker(mut commands: Commands, mut focused: ResMut<FocusedEntity>) {
+    let mut list = TuiList::new(List::new(ITEMS));
+    list.state.select(Some(0));
+
+    let picker = commands
+        .spawn((
+            ListBundle { list, ..default() },
+            TuiBlock(Block::bordered().title(" Pick a fruit, Enter to choose ")),
+        ))
+        .id();
+
+    focused.set(Some(picker));
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn pick(
+    mut focused_input: EventReader<FocusedInput>,
+    lists: Query<&TuiList>,
+    choice: Res<Choice>,
+    mut event_writer: EventWriter<AppExit>,
+) {
+    for FocusedInput { entity, event } in focused_input.read() {
+        let Event::Key(key) = event else {
+            continue;
+        };
+
+        if key.code != CrossKeyCode::Enter || key.kind == KeyEventKind::Release {
+            continue;
+        }
+
+        let Ok(list) = lists.get(*entity) else {
+            continue;
+        };
+
+        if let (Some(idx), Ok(mut picked)) = (list.state.selected(), choice.0.lock()) {
+            *picked = ITEMS.get(idx).copied();
+        }
+
+        event_writer.send(AppExit);
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn quit_system(key_code: Res<ButtonInput<KeyCode>>, mut event_writer: EventWriter<AppExit>) {
+    if key_code.just_pressed(KeyCode::Escape) {
+        event_writer.send(AppExit);
+    }
+}
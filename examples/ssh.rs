@@ -0,0 +1,115 @@
+//! Serving a status screen over SSH instead of drawing to the local terminal. The server needs a
+//! host key and a list of the keys operators may log in with in the working directory:
+//!
+//! ```sh
+//! ssh-keygen -t ed25519 -N '' -f ssh_host_ed25519_key
+//! cp ~/.ssh/id_ed25519.pub authorized_keys
+//! cargo run --example ssh --features ssh
+//! ```
+//!
+//! Then connect with `ssh -p 2222 localhost` from up to four terminals at once. Each operator is
+//! shown their own window size, resizing the window updates it, and Escape disconnects. The server
+//! keeps running until it is interrupted.
+
+use std::time::Duration;
+
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+
+use bevy_tui::prelude::*;
+
+use ratatui::widgets::{Block, Paragraph};
+
+/// The screen shown to a session.
+#[derive(Component)]
+struct Status(Entity);
+
+fn main() {
+    App::new()
+        .add_plugins((MinimalTuiPlugins, LogPlugin::default(), SshPlugin))
+        .insert_resource(SshSettings {
+            max_clients: 4,
+            ..default()
+        })
+        .add_systems(PostStartup, print_address)
+        .add_systems(
+            Update,
+            (open_status, close_status, update_status, disconnect_system),
+        )
+        .run();
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn print_address(server: Res<SshServer>) {
+    if let Some(address) = server.local_addr() {
+        println!(
+            "Listening on {address}, connect with: ssh -p {} {}",
+            address.port(),
+            address.ip()
+        );
+    }
+}
+
+fn open_status(mut commands: Commands, mut started: EventReader<SessionStarted>) {
+    for SessionStarted { session } in started.read() {
+        let status = commands
+            .spawn((
+                ParagraphBundle::default(),
+                TuiBlock(Block::bordered().title(" Server ")),
+                Status(*session),
+            ))
+            .id();
+
+        commands
+            .entity(*session)
+            .insert(TuiSession { root: Some(status) });
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn close_status(
+    mut commands: Commands,
+    mut ended: EventReader<SessionEnded>,
+    screens: Query<(Entity, &Status)>,
+) {
+    for SessionEnded { session } in ended.read() {
+        for (entity, status) in &screens {
+            if status.0 == *session {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn update_status(
+    time: Res<Time>,
+    server: Res<SshServer>,
+    mut sessions: Query<&mut bevy_tui::BevyTerminal, With<TuiSession>>,
+    mut screens: Query<(&mut TuiParagraph, &Status)>,
+) {
+    let uptime = Duration::from_secs(time.elapsed().as_secs());
+    let operators = server.sessions().count();
+
+    for (mut paragraph, Status(session)) in &mut screens {
+        let Ok(mut terminal) = sessions.get_mut(*session) else {
+            continue;
+        };
+
+        let size = terminal.0.get_frame().size();
+        paragraph.0 = Paragraph::new(format!(
+            "Session: {session:?}\nUptime: {uptime:?}\nOperators: {operators}\n\
+             Your window: {}x{}\n\nPress Escape to disconnect.",
+            size.width, size.height
+        ));
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn disconnect_system(sessions: Query<(Entity, &SessionInput)>, server: Res<SshServer>) {
+    for (session, input) in &sessions {
+        if input.keys().just_pressed(KeyCode::Escape) {
+            server.disconnect(session);
+        }
+    }
+}
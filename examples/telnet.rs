@@ -0,0 +1,191 @@
+//! Serving an operator console over telnet instead of drawing to the local terminal, as a
+//! headless game server might. Connect with `telnet localhost 2323` from up to four terminals at
+//! once. Each operator gets their own console sharing the same command history, type commands
+//! into the prompt and press Escape to disconnect. The server keeps running until it is
+//! interrupted.
+
+use std::time::Duration;
+
+use bevy::log::LogPlugin;
+use bevy::prelude::*;
+
+use bevy_tui::prelude::*;
+
+use ratatui::layout::Constraint;
+use ratatui::widgets::{Block, List, Paragraph};
+
+/// The root of the console shown to a session.
+#[derive(Component)]
+struct Console(Entity);
+
+#[derive(Component)]
+struct Status(Entity);
+
+#[derive(Component)]
+struct Prompt(Entity);
+
+#[derive(Component)]
+struct History;
+
+#[derive(Default, Resource)]
+struct CommandLog(Vec<String>);
+
+fn main() {
+    App::new()
+        .add_plugins((MinimalTuiPlugins, LogPlugin::default(), TelnetPlugin))
+        .insert_resource(TelnetSettings {
+            max_clients: 4,
+            ..default()
+        })
+        .init_resource::<CommandLog>()
+        .add_systems(PostStartup, print_address)
+        .add_systems(
+            Update,
+            (
+                open_consoles,
+                close_consoles,
+                update_status,
+                run_commands,
+                update_history,
+                disconnect_system,
+            ),
+        )
+        .run();
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn print_address(server: Res<TelnetServer>) {
+    if let Some(address) = server.local_addr() {
+        println!(
+            "Listening on {address}, connect with: telnet {} {}",
+            address.ip(),
+            address.port()
+        );
+    }
+}
+
+fn open_consoles(mut commands: Commands, mut started: EventReader<SessionStarted>) {
+    for SessionStarted { session } in started.read() {
+        let mut prompt = None;
+
+        let console = commands
+            .spawn((
+                TuiNode::default(),
+                TuiLayout::vertical([
+                    Constraint::Length(3),
+                    Constraint::Min(0),
+                    Constraint::Length(3),
+                ]),
+                Console(*session),
+            ))
+            .with_children(|root| {
+                root.spawn((
+                    ParagraphBundle::default(),
+                    TuiBlock(Block::bordered().title(" Server ")),
+                    Status(*session),
+                ));
+
+                root.spawn((
+                    ListBundle::default(),
+                    TuiBlock(Block::bordered().title(" History ")),
+                    History,
+                ));
+
+                let mut input = TextInput::default();
+                input.placeholder = "Type a command and press Enter".into();
+                let entity = root
+                    .spawn((
+                        TextInputBundle {
+                            text_input: input,
+                            ..default()
+                        },
+                        TuiBlock(Block::bordered().title(" Command ")),
+                        Prompt(*session),
+                    ))
+                    .id();
+                prompt = Some(entity);
+            })
+            .id();
+
+        let mut focused = FocusedEntity::default();
+        focused.set(prompt);
+
+        commands.entity(*session).insert((
+            TuiSession {
+                root: Some(console),
+            },
+            focused,
+        ));
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn close_consoles(
+    mut commands: Commands,
+    mut ended: EventReader<SessionEnded>,
+    consoles: Query<(Entity, &Console)>,
+) {
+    for SessionEnded { session } in ended.read() {
+        for (entity, console) in &consoles {
+            if console.0 == *session {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn update_status(
+    time: Res<Time>,
+    server: Res<TelnetServer>,
+    mut sessions: Query<&mut bevy_tui::BevyTerminal, With<TuiSession>>,
+    mut status: Query<(&mut TuiParagraph, &Status)>,
+) {
+    let uptime = Duration::from_secs(time.elapsed().as_secs());
+    let operators = server.sessions().count();
+
+    for (mut paragraph, Status(session)) in &mut status {
+        let Ok(mut terminal) = sessions.get_mut(*session) else {
+            continue;
+        };
+
+        let size = terminal.0.get_frame().size();
+        paragraph.0 = Paragraph::new(format!(
+            "Uptime: {uptime:?}    Operators: {operators}    Your window: {}x{}",
+            size.width, size.height
+        ));
+    }
+}
+
+fn run_commands(
+    mut submitted: EventReader<TextInputSubmitted>,
+    mut inputs: Query<(&mut TextInput, &Prompt)>,
+    mut log: ResMut<CommandLog>,
+) {
+    for event in submitted.read() {
+        let Ok((mut input, Prompt(session))) = inputs.get_mut(event.entity) else {
+            continue;
+        };
+
+        log.0.push(format!("{session:?}> {}", event.value));
+        input.set_value("");
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn update_history(log: Res<CommandLog>, mut history: Query<&mut TuiList, With<History>>) {
+    for mut list in &mut history {
+        if log.is_changed() || list.is_added() {
+            list.list = List::new(log.0.clone());
+        }
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn disconnect_system(sessions: Query<(Entity, &SessionInput)>, server: Res<TelnetServer>) {
+    for (session, input) in &sessions {
+        if input.keys().just_pressed(KeyCode::Escape) {
+            server.disconnect(session);
+        }
+    }
+}
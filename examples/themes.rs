@@ -0,0 +1,71 @@
+//! Loading themes from asset files. Run with `--features file_watcher` and edit the files in
+//! `assets/themes` while the example is running to see the changes applied immediately.
+
+use bevy::prelude::*;
+
+use bevy_tui::prelude::*;
+
+use ratatui::layout::Constraint;
+use ratatui::widgets::{Block, List, Paragraph};
+
+const THEMES: [&str; 2] = ["themes/solarized.theme.ron", "themes/gruvbox.theme.toml"];
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    initialize_terminal()?;
+
+    App::new()
+        .add_plugins(MinimalTuiPlugins)
+        .insert_resource(TuiQuitSettings {
+            quit_keys: vec!["q".parse()?, "ctrl+c".parse()?],
+            ..default()
+        })
+        .add_systems(Startup, (load_theme, spawn_ui))
+        .add_systems(Update, switch_theme)
+        .run();
+
+    teardown_terminal()?;
+
+    Ok(())
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn load_theme(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(TuiThemeHandle(asset_server.load(THEMES[0])));
+}
+
+fn spawn_ui(mut commands: Commands) {
+    commands
+        .spawn((
+            TuiNode::default(),
+            TuiLayout::vertical([Constraint::Length(1), Constraint::Min(0)]),
+        ))
+        .with_children(|root| {
+            root.spawn(ParagraphBundle {
+                paragraph: TuiParagraph(Paragraph::new(
+                    "Press 't' to switch theme files or 'q' to quit.",
+                )),
+                ..default()
+            });
+
+            root.spawn((
+                ListBundle {
+                    list: TuiList::new(List::new(THEMES)),
+                    ..default()
+                },
+                TuiBlock(Block::bordered().title(" Theme Files ")),
+            ));
+        });
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn switch_theme(
+    key_code: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    mut handle: ResMut<TuiThemeHandle>,
+    mut current: Local<usize>,
+) {
+    if key_code.just_pressed(KeyCode::KeyT) {
+        *current = (*current + 1) % THEMES.len();
+        handle.0 = asset_server.load(THEMES[*current]);
+    }
+}
@@ -0,0 +1,547 @@
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use bevy::app::{App, Plugin, PreUpdate};
+use bevy::ecs::event::{EventReader, EventWriter};
+use bevy::ecs::schedule::IntoSystemConfigs;
+use bevy::ecs::system::{Res, ResMut, Resource};
+use bevy::input::mouse::MouseButton;
+use bevy::input::{ButtonInput, InputSystem};
+use bevy::time::{Real, Time};
+use crossterm::event::{Event as CrossEvent, KeyEventKind};
+
+use crate::events::KeySequenceMatched;
+use crate::focus::KeyboardCapture;
+use crate::input::RawConsoleEvent;
+
+mod binding;
+#[cfg(feature = "config")]
+mod config;
+mod sequence;
+
+pub use binding::{InputBinding, InvalidBinding, KeyChord};
+#[cfg(feature = "config")]
+pub use config::ActionMapError;
+pub use sequence::{KeySequenceSettings, PendingKeys};
+
+/// The requirements for a type to be used as an action, typically a fieldless enum listing
+/// everything the player can do. This is implemented automatically for any type that meets them.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+///
+/// #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+/// enum Action {
+///     Save,
+///     Quit,
+/// }
+///
+/// fn requires_action<A: TuiAction>() {}
+/// requires_action::<Action>();
+/// ```
+pub trait TuiAction: Copy + Eq + Hash + Send + Sync + 'static {}
+
+impl<T: Copy + Eq + Hash + Send + Sync + 'static> TuiAction for T {}
+
+/// The inputs bound to each action. An action can have any number of bindings, and the same
+/// binding can trigger more than one action.
+///
+/// The bindings are kept in the order they were added, so a help screen listing them shows them
+/// the way they were configured. With the `serialize` feature the map is written as each action
+/// followed by a list of its [`InputBinding`]s, and with the `config` feature it can be loaded
+/// from a RON or TOML file with `ActionMap::load`.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+/// use crossterm::event::KeyCode;
+///
+/// #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+/// enum Action {
+///     Save,
+///     Top,
+///     Quit,
+/// }
+///
+/// let map = ActionMap::new()
+///     .with(Action::Save, "ctrl+s".parse::<KeyChord>()?)
+///     .with(Action::Top, "g g".parse::<InputBinding>()?)
+///     .with(Action::Quit, KeyCode::Char('q'));
+///
+/// let quit: Vec<String> = map.bindings(Action::Quit).map(ToString::to_string).collect();
+/// assert_eq!(quit, ["q"]);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Resource)]
+pub struct ActionMap<A: TuiAction> {
+    bindings: Vec<(A, InputBinding)>,
+}
+
+impl<A: TuiAction> ActionMap<A> {
+    /// An empty map without any bindings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let map = ActionMap::<u8>::new();
+    /// assert_eq!(map.iter().count(), 0);
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Add a binding for an action, returning the map for further bindings to be added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::input::mouse::MouseButton;
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let map = ActionMap::new().with("select", MouseButton::Left);
+    /// assert_eq!(map.bindings("select").count(), 1);
+    /// ```
+    #[must_use]
+    pub fn with(mut self, action: A, binding: impl Into<InputBinding>) -> Self {
+        self.bind(action, binding);
+        self
+    }
+
+    /// Add a binding for an action. Adding a binding the action already has does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use crossterm::event::KeyCode;
+    ///
+    /// let mut map = ActionMap::new();
+    /// map.bind("up", KeyCode::Up);
+    /// map.bind("up", KeyCode::Char('k'));
+    /// map.bind("up", KeyCode::Up);
+    /// assert_eq!(map.bindings("up").count(), 2);
+    /// ```
+    pub fn bind(&mut self, action: A, binding: impl Into<InputBinding>) {
+        let binding = binding.into();
+
+        if !self
+            .bindings
+            .iter()
+            .any(|(a, b)| *a == action && *b == binding)
+        {
+            self.bindings.push((action, binding));
+        }
+    }
+
+    /// Remove every binding of an action, such as before replacing them with new ones chosen by
+    /// the player.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use crossterm::event::KeyCode;
+    ///
+    /// let mut map = ActionMap::new().with("up", KeyCode::Up);
+    /// map.unbind("up");
+    /// map.bind("up", KeyCode::Char('k'));
+    /// assert_eq!(map.bindings("up").next(), Some(&InputBinding::from(KeyCode::Char('k'))));
+    /// ```
+    pub fn unbind(&mut self, action: A) {
+        self.bindings.retain(|(a, _)| *a != action);
+    }
+
+    /// The bindings of an action, in the order they were added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use crossterm::event::KeyCode;
+    ///
+    /// let map = ActionMap::new().with("up", KeyCode::Up);
+    /// assert!(map.bindings("down").next().is_none());
+    /// ```
+    pub fn bindings(&self, action: A) -> impl Iterator<Item = &InputBinding> {
+        self.bindings
+            .iter()
+            .filter(move |(a, _)| *a == action)
+            .map(|(_, binding)| binding)
+    }
+
+    /// Every action along with one of its bindings, in the order they were added. Actions with
+    /// several bindings appear once for each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use crossterm::event::KeyCode;
+    ///
+    /// let map = ActionMap::new()
+    ///     .with("save", "ctrl+s".parse::<KeyChord>()?)
+    ///     .with("quit", KeyCode::Char('q'));
+    ///
+    /// for (action, binding) in map.iter() {
+    ///     println!("{binding:>10}  {action}");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (A, &InputBinding)> {
+        self.bindings
+            .iter()
+            .map(|(action, binding)| (*action, binding))
+    }
+
+    /// Whether the chords typed so far are the start of a longer sequence bound to an action.
+    fn continues(&self, typed: &[KeyChord]) -> bool {
+        self.bindings.iter().any(|(_, binding)| match binding {
+            InputBinding::Keys(chords) => chords.len() > typed.len() && chords.starts_with(typed),
+            InputBinding::Mouse(_) => false,
+        })
+    }
+
+    /// The actions bound to exactly the chords typed.
+    fn matching(&self, typed: &[KeyChord]) -> Vec<(A, &InputBinding)> {
+        self.bindings
+            .iter()
+            .filter(|(_, binding)| matches!(binding, InputBinding::Keys(chords) if chords == typed))
+            .map(|(action, binding)| (*action, binding))
+            .collect()
+    }
+}
+
+impl<A: TuiAction> Default for ActionMap<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Which actions are pressed this frame, updated from the [`ActionMap`] by the [`ActionPlugin`].
+/// This mirrors the Bevy `ButtonInput` resources so actions are checked the same way keys are.
+///
+/// An action bound to a key is pressed on the frame the key is, and stays pressed for as long as
+/// the terminal keeps repeating it. One bound to a sequence of keys is pressed when its last key
+/// is, and the keys typed towards it in the meantime are available from [`ActionState::pending`].
+/// Keys that are bound by themselves as well as being the start of a longer sequence wait until
+/// either the next key or the [`KeySequenceSettings::timeout`] to tell which was meant. One bound
+/// to a mouse button stays pressed until the button is released.
+///
+/// Only the terminal the process is attached to is followed, not remote sessions, and no actions
+/// are triggered by keys captured by a focused widget such as a [`TextInput`].
+///
+/// [`TextInput`]: crate::prelude::TextInput
+///
+/// # Examples
+///
+/// ```
+/// use bevy::app::AppExit;
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+/// enum Action {
+///     Quit,
+/// }
+///
+/// fn quit(actions: Res<ActionState<Action>>, mut exit: EventWriter<AppExit>) {
+///     if actions.just_pressed(Action::Quit) {
+///         exit.send(AppExit);
+///     }
+/// }
+/// ```
+#[derive(Debug, Resource)]
+pub struct ActionState<A: TuiAction> {
+    input: ButtonInput<A>,
+    pending: PendingKeys,
+}
+
+impl<A: TuiAction> ActionState<A> {
+    /// Whether the action is currently pressed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let state = ActionState::<&str>::default();
+    /// assert!(!state.pressed("jump"));
+    /// ```
+    pub fn pressed(&self, action: A) -> bool {
+        self.input.pressed(action)
+    }
+
+    /// Whether the action started being pressed this frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let state = ActionState::<&str>::default();
+    /// assert!(!state.just_pressed("jump"));
+    /// ```
+    pub fn just_pressed(&self, action: A) -> bool {
+        self.input.just_pressed(action)
+    }
+
+    /// Whether the action stopped being pressed this frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let state = ActionState::<&str>::default();
+    /// assert!(!state.just_released("jump"));
+    /// ```
+    pub fn just_released(&self, action: A) -> bool {
+        self.input.just_released(action)
+    }
+
+    /// Every action that is currently pressed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let state = ActionState::<&str>::default();
+    /// assert_eq!(state.get_pressed().count(), 0);
+    /// ```
+    pub fn get_pressed(&self) -> impl Iterator<Item = A> + '_ {
+        self.input.get_pressed().copied()
+    }
+
+    /// Every action that started being pressed this frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let state = ActionState::<&str>::default();
+    /// assert_eq!(state.get_just_pressed().count(), 0);
+    /// ```
+    pub fn get_just_pressed(&self) -> impl Iterator<Item = A> + '_ {
+        self.input.get_just_pressed().copied()
+    }
+
+    /// Every action that stopped being pressed this frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let state = ActionState::<&str>::default();
+    /// assert_eq!(state.get_just_released().count(), 0);
+    /// ```
+    pub fn get_just_released(&self) -> impl Iterator<Item = A> + '_ {
+        self.input.get_just_released().copied()
+    }
+
+    /// The count and the start of a sequence typed so far, for showing in a status line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let state = ActionState::<&str>::default();
+    /// assert_eq!(state.pending().to_string(), "");
+    /// ```
+    #[must_use]
+    pub fn pending(&self) -> &PendingKeys {
+        &self.pending
+    }
+}
+
+impl<A: TuiAction> Default for ActionState<A> {
+    fn default() -> Self {
+        Self {
+            input: ButtonInput::default(),
+            pending: PendingKeys::default(),
+        }
+    }
+}
+
+/// Adds the [`ActionState`] for a type of action, updating it from the [`ActionMap`] at the start
+/// of every frame and sending a [`KeySequenceMatched`] event for every key binding typed. An empty
+/// map is added unless one has already been inserted, and it can be changed at any time to rebind
+/// the actions.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use bevy_tui::RawConsoleEvent;
+/// use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+///
+/// #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+/// enum Action {
+///     Top,
+/// }
+///
+/// let mut app = App::new();
+/// app.insert_resource(ActionMap::new().with(Action::Top, "g g".parse::<InputBinding>()?))
+///     .add_plugins(ActionPlugin::<Action>::default());
+///
+/// let g = Event::Key(KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE));
+///
+/// app.world.send_event(RawConsoleEvent(g.clone()));
+/// app.update();
+/// assert!(!app.world.resource::<ActionState<Action>>().pressed(Action::Top));
+///
+/// app.world.send_event(RawConsoleEvent(g));
+/// app.update();
+/// assert!(app.world.resource::<ActionState<Action>>().just_pressed(Action::Top));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct ActionPlugin<A: TuiAction>(PhantomData<A>);
+
+impl<A: TuiAction> Default for ActionPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: TuiAction> Plugin for ActionPlugin<A> {
+    fn build(&self, app: &mut App) {
+        // These are normally added by the `TuiPlugin`, but allow the actions to be tested alone
+        app.add_event::<RawConsoleEvent>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<Time<Real>>()
+            .init_resource::<KeySequenceSettings>()
+            .add_event::<KeySequenceMatched<A>>()
+            .init_resource::<ActionMap<A>>()
+            .init_resource::<ActionState<A>>()
+            .add_systems(PreUpdate, action_system::<A>.after(InputSystem));
+    }
+}
+
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+pub(crate) fn action_system<A: TuiAction>(
+    map: Res<ActionMap<A>>,
+    mut state: ResMut<ActionState<A>>,
+    settings: Res<KeySequenceSettings>,
+    time: Res<Time<Real>>,
+    mut events: EventReader<RawConsoleEvent>,
+    mut matched_events: EventWriter<KeySequenceMatched<A>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    capture: Option<Res<KeyboardCapture>>,
+) {
+    let captured = capture.map_or(false, |c| c.0);
+    let now = time.elapsed();
+
+    let mut matched = state.pending.expire(&map, &settings, now);
+
+    for RawConsoleEvent(event) in events.read() {
+        let CrossEvent::Key(key) = event else {
+            continue;
+        };
+
+        if captured || key.kind == KeyEventKind::Release {
+            continue;
+        }
+
+        let chord = KeyChord::from(*key);
+        matched.extend(state.pending.push(&map, &settings, chord, now));
+    }
+
+    let mut active: Vec<A> = matched.iter().map(|m| m.action).collect();
+    matched_events.send_batch(matched);
+
+    for (action, binding) in &map.bindings {
+        if matches!(binding, InputBinding::Mouse(button) if mouse.pressed(*button)) {
+            active.push(*action);
+        }
+    }
+
+    // The same as the keys themselves, actions are released once nothing holds them down
+    state.input.clear();
+
+    #[allow(clippy::needless_collect)]
+    let released: Vec<A> = state
+        .input
+        .get_pressed()
+        .filter(|action| !active.contains(action))
+        .copied()
+        .collect();
+
+    for action in released {
+        state.input.release(action);
+    }
+
+    for action in active {
+        state.input.press(action);
+    }
+}
+
+#[cfg(feature = "serialize")]
+mod serialize {
+    use std::fmt::{self, Formatter};
+    use std::marker::PhantomData;
+
+    use serde::de::{MapAccess, Visitor};
+    use serde::ser::SerializeMap;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{ActionMap, InputBinding, TuiAction};
+
+    impl<A: TuiAction + Serialize> Serialize for ActionMap<A> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            // Each action is written once with all of its bindings, in the order it first appears
+            let mut actions: Vec<A> = Vec::new();
+            for (action, _) in &self.bindings {
+                if !actions.contains(action) {
+                    actions.push(*action);
+                }
+            }
+
+            let mut map = serializer.serialize_map(Some(actions.len()))?;
+            for action in actions {
+                let bindings: Vec<&InputBinding> = self.bindings(action).collect();
+                map.serialize_entry(&action, &bindings)?;
+            }
+            map.end()
+        }
+    }
+
+    impl<'de, A: TuiAction + Deserialize<'de>> Deserialize<'de> for ActionMap<A> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_map(ActionMapVisitor(PhantomData))
+        }
+    }
+
+    struct ActionMapVisitor<A>(PhantomData<A>);
+
+    impl<'de, A: TuiAction + Deserialize<'de>> Visitor<'de> for ActionMapVisitor<A> {
+        type Value = ActionMap<A>;
+
+        fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            f.write_str("a map of actions to lists of key bindings")
+        }
+
+        fn visit_map<M: MapAccess<'de>>(self, mut access: M) -> Result<Self::Value, M::Error> {
+            let mut map = ActionMap::new();
+
+            while let Some((action, bindings)) = access.next_entry::<A, Vec<InputBinding>>()? {
+                for binding in bindings {
+                    map.bind(action, binding);
+                }
+            }
+
+            Ok(map)
+        }
+    }
+}
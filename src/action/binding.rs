@@ -0,0 +1,390 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use bevy::input::mouse::MouseButton;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// The modifiers that can be part of a chord, in the order they're written.
+const MODIFIER_NAMES: [(KeyModifiers, &str); 4] = [
+    (KeyModifiers::CONTROL, "ctrl"),
+    (KeyModifiers::ALT, "alt"),
+    (KeyModifiers::SHIFT, "shift"),
+    (KeyModifiers::SUPER, "super"),
+];
+
+/// The keys that are written with a name rather than the character they type.
+const KEY_NAMES: [(KeyCode, &str); 15] = [
+    (KeyCode::Enter, "enter"),
+    (KeyCode::Esc, "esc"),
+    (KeyCode::Tab, "tab"),
+    (KeyCode::BackTab, "backtab"),
+    (KeyCode::Backspace, "backspace"),
+    (KeyCode::Delete, "delete"),
+    (KeyCode::Insert, "insert"),
+    (KeyCode::Home, "home"),
+    (KeyCode::End, "end"),
+    (KeyCode::PageUp, "pageup"),
+    (KeyCode::PageDown, "pagedown"),
+    (KeyCode::Up, "up"),
+    (KeyCode::Down, "down"),
+    (KeyCode::Left, "left"),
+    (KeyCode::Right, "right"),
+];
+
+/// The mouse buttons that can be bound, with the names they're written with.
+const MOUSE_NAMES: [(MouseButton, &str); 3] = [
+    (MouseButton::Left, "mouse:left"),
+    (MouseButton::Middle, "mouse:middle"),
+    (MouseButton::Right, "mouse:right"),
+];
+
+/// A key pressed along with the modifiers held down at the time, such as Ctrl+S.
+///
+/// Unlike the Bevy `KeyCode`s, chords are described by the character typed so they don't depend
+/// on the keyboard layout. Shift is folded into the character for that reason, Shift+A is the
+/// same chord as `A` and Shift+Tab the same as `BackTab`.
+///
+/// Chords are written as the modifiers followed by the key, separated by `+`. Keys are either the
+/// character they type, `space`, `f1` through `f24`, or one of `enter`, `esc`, `tab`, `backtab`,
+/// `backspace`, `delete`, `insert`, `home`, `end`, `pageup`, `pagedown`, `up`, `down`, `left` or
+/// `right`. The modifiers are `ctrl`, `alt`, `shift` and `super`.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+/// use crossterm::event::{KeyCode, KeyModifiers};
+///
+/// let save: KeyChord = "ctrl+s".parse()?;
+/// assert_eq!(save, KeyChord::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+/// assert_eq!(save.to_string(), "ctrl+s");
+///
+/// let shouted: KeyChord = "shift+a".parse()?;
+/// assert_eq!(shouted.to_string(), "A");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct KeyChord {
+    /// The key that was pressed.
+    pub code: KeyCode,
+
+    /// The modifiers held while it was pressed.
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    /// A chord of a key and modifiers, folding Shift into the key where it changes the character
+    /// typed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use crossterm::event::{KeyCode, KeyModifiers};
+    ///
+    /// let chord = KeyChord::new(KeyCode::Tab, KeyModifiers::SHIFT);
+    /// assert_eq!(chord, KeyChord::new(KeyCode::BackTab, KeyModifiers::NONE));
+    /// ```
+    #[must_use]
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let (code, modifiers) = match code {
+            KeyCode::Char(ch) if modifiers.contains(KeyModifiers::SHIFT) => (
+                KeyCode::Char(ch.to_ascii_uppercase()),
+                modifiers - KeyModifiers::SHIFT,
+            ),
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => {
+                (KeyCode::BackTab, modifiers - KeyModifiers::SHIFT)
+            }
+            KeyCode::BackTab => (code, modifiers - KeyModifiers::SHIFT),
+            _ => (code, modifiers),
+        };
+
+        // Only the modifiers that can be written are kept so chords can always be compared
+        let writable = MODIFIER_NAMES
+            .iter()
+            .fold(KeyModifiers::NONE, |all, (modifier, _)| all | *modifier);
+
+        Self {
+            code,
+            modifiers: modifiers & writable,
+        }
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(event: KeyEvent) -> Self {
+        Self::new(event.code, event.modifiers)
+    }
+}
+
+impl From<KeyCode> for KeyChord {
+    fn from(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+}
+
+impl Display for KeyChord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in MODIFIER_NAMES {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+
+        if let Some((_, name)) = KEY_NAMES.iter().find(|(code, _)| *code == self.code) {
+            return f.write_str(name);
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(ch) => write!(f, "{ch}"),
+            KeyCode::F(number) => write!(f, "f{number}"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = InvalidBinding;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidBinding(text.to_string());
+
+        // A trailing `+` is the key itself rather than a separator, as in `ctrl++`
+        let (modifiers, key) = match text.strip_suffix("++") {
+            Some(modifiers) => (Some(modifiers), "+"),
+            None if text == "+" => (None, "+"),
+            None => match text.rsplit_once('+') {
+                Some((modifiers, key)) => (Some(modifiers), key),
+                None => (None, text),
+            },
+        };
+
+        let mut held = KeyModifiers::NONE;
+        for name in modifiers
+            .into_iter()
+            .flat_map(|modifiers| modifiers.split('+'))
+        {
+            let (modifier, _) = MODIFIER_NAMES
+                .iter()
+                .find(|(_, modifier)| modifier.eq_ignore_ascii_case(name))
+                .ok_or_else(invalid)?;
+            held |= *modifier;
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(ch), None) => KeyCode::Char(ch),
+            _ => key_code(key).ok_or_else(invalid)?,
+        };
+
+        Ok(Self::new(code, held))
+    }
+}
+
+/// The key written with a name, such as `enter` or `f5`.
+fn key_code(name: &str) -> Option<KeyCode> {
+    let name = name.to_ascii_lowercase();
+
+    if let Some((code, _)) = KEY_NAMES.iter().find(|(_, key)| *key == name) {
+        return Some(*code);
+    }
+
+    match name.as_str() {
+        "space" => Some(KeyCode::Char(' ')),
+        "escape" => Some(KeyCode::Esc),
+        _ => {
+            let number = name.strip_prefix('f')?.parse().ok()?;
+            (1..=24).contains(&number).then_some(KeyCode::F(number))
+        }
+    }
+}
+
+/// Something the player can do to trigger an action.
+///
+/// Bindings are written as either a mouse button (`mouse:left`, `mouse:middle` or `mouse:right`)
+/// or one or more [`KeyChord`]s separated by spaces. A binding of several chords is a sequence
+/// that needs each of them pressed in turn, such as `g g` or `ctrl+x ctrl+s`.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::input::mouse::MouseButton;
+/// use bevy_tui::prelude::*;
+///
+/// let top: InputBinding = "g g".parse()?;
+/// assert_eq!(top, InputBinding::Keys(vec!["g".parse()?, "g".parse()?]));
+///
+/// let click: InputBinding = "mouse:left".parse()?;
+/// assert_eq!(click, InputBinding::Mouse(MouseButton::Left));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum InputBinding {
+    /// Key chords pressed one after the other. A single chord triggers the action as soon as it
+    /// is pressed.
+    Keys(Vec<KeyChord>),
+
+    /// A mouse button, which keeps the action pressed until it is released.
+    Mouse(MouseButton),
+}
+
+impl From<KeyChord> for InputBinding {
+    fn from(chord: KeyChord) -> Self {
+        Self::Keys(vec![chord])
+    }
+}
+
+impl From<KeyCode> for InputBinding {
+    fn from(code: KeyCode) -> Self {
+        Self::Keys(vec![KeyChord::from(code)])
+    }
+}
+
+impl From<MouseButton> for InputBinding {
+    fn from(button: MouseButton) -> Self {
+        Self::Mouse(button)
+    }
+}
+
+impl Display for InputBinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Keys(chords) => {
+                for (index, chord) in chords.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(" ")?;
+                    }
+                    write!(f, "{chord}")?;
+                }
+
+                Ok(())
+            }
+            Self::Mouse(button) => match MOUSE_NAMES.iter().find(|(b, _)| b == button) {
+                Some((_, name)) => f.write_str(name),
+                None => write!(f, "mouse:{button:?}"),
+            },
+        }
+    }
+}
+
+impl FromStr for InputBinding {
+    type Err = InvalidBinding;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+
+        if let Some((button, _)) = MOUSE_NAMES
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(text))
+        {
+            return Ok(Self::Mouse(*button));
+        }
+
+        let chords = text
+            .split_whitespace()
+            .map(KeyChord::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if chords.is_empty() {
+            return Err(InvalidBinding(text.to_string()));
+        }
+
+        Ok(Self::Keys(chords))
+    }
+}
+
+/// A [`KeyChord`] or [`InputBinding`] that couldn't be parsed, holding the text that was given.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+///
+/// let err = "ctrl+nope".parse::<KeyChord>().unwrap_err();
+/// assert_eq!(err.to_string(), "invalid key binding: ctrl+nope");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidBinding(pub String);
+
+impl Display for InvalidBinding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid key binding: {}", self.0)
+    }
+}
+
+impl Error for InvalidBinding {}
+
+// The reflection is implemented here rather than derived so the `Option#unwrap` the generated code
+// makes use of can be allowed without extending the exception to the rest of the module.
+mod reflect {
+    #![allow(clippy::disallowed_methods)]
+
+    use bevy::reflect::impl_reflect_value;
+    #[cfg(feature = "serialize")]
+    use bevy::reflect::{ReflectDeserialize, ReflectSerialize};
+
+    use super::InputBinding;
+
+    #[cfg(not(feature = "serialize"))]
+    impl_reflect_value!((in bevy_tui::action) InputBinding(Debug, Hash, PartialEq));
+    #[cfg(feature = "serialize")]
+    impl_reflect_value!(
+        (in bevy_tui::action) InputBinding(Debug, Hash, PartialEq, Serialize, Deserialize)
+    );
+}
+
+#[cfg(feature = "serialize")]
+mod serialize {
+    use std::fmt::{self, Formatter};
+    use std::marker::PhantomData;
+    use std::str::FromStr;
+
+    use serde::de::{self, Visitor};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{InputBinding, KeyChord};
+
+    /// Bindings are stored the same way they're written so configuration files stay readable.
+    struct FromText<T>(PhantomData<T>);
+
+    impl<T: FromStr> Visitor<'_> for FromText<T>
+    where
+        T::Err: fmt::Display,
+    {
+        type Value = T;
+
+        fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            f.write_str("a key binding such as \"ctrl+s\"")
+        }
+
+        fn visit_str<E: de::Error>(self, text: &str) -> Result<T, E> {
+            text.parse().map_err(E::custom)
+        }
+    }
+
+    impl Serialize for KeyChord {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for KeyChord {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_str(FromText(PhantomData))
+        }
+    }
+
+    impl Serialize for InputBinding {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(self)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for InputBinding {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_str(FromText(PhantomData))
+        }
+    }
+}
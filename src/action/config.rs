@@ -0,0 +1,112 @@
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+
+use super::{ActionMap, TuiAction};
+
+impl<A: TuiAction + DeserializeOwned> ActionMap<A> {
+    /// Read the bindings from a RON or TOML file, chosen by the file's extension. The file lists
+    /// each action along with its bindings, written the way [`InputBinding`] describes:
+    ///
+    /// ```toml
+    /// Save = ["ctrl+s", "ctrl+x ctrl+s"]
+    /// Top = ["g g", "home"]
+    /// Select = ["enter", "mouse:left"]
+    /// ```
+    ///
+    /// Actions left out of the file have no bindings, so an application wanting to keep its
+    /// defaults for those should fill them in afterwards.
+    ///
+    /// [`InputBinding`]: crate::prelude::InputBinding
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file can't be read, or doesn't contain a valid set of bindings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Clone, Copy, Debug, Deserialize, Hash, PartialEq, Eq)]
+    /// enum Action {
+    ///     Save,
+    ///     Quit,
+    /// }
+    ///
+    /// let path = std::env::temp_dir().join("bevy_tui_bindings.toml");
+    /// std::fs::write(&path, "Save = [\"ctrl+s\"]\nQuit = [\"q\", \"ctrl+c\"]\n")?;
+    ///
+    /// let map = ActionMap::<Action>::load(&path)?;
+    /// assert_eq!(map.bindings(Action::Quit).count(), 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ActionMapError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+
+        let is_toml = path.extension().map_or(false, |ext| ext == "toml");
+
+        let map = if is_toml {
+            toml::from_str(&text)?
+        } else {
+            ron::from_str(&text)?
+        };
+
+        Ok(map)
+    }
+}
+
+/// The reasons an [`ActionMap`] can fail to load.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+///
+/// let err = ActionMap::<u8>::load("/nonexistent/bindings.ron").unwrap_err();
+/// assert!(matches!(err, ActionMapError::Io(_)));
+/// ```
+#[derive(Debug)]
+pub enum ActionMapError {
+    /// The file couldn't be read.
+    Io(std::io::Error),
+
+    /// The file isn't valid RON, or its bindings are invalid.
+    Ron(ron::error::SpannedError),
+
+    /// The file isn't valid TOML, or its bindings are invalid.
+    Toml(toml::de::Error),
+}
+
+impl Display for ActionMapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read bindings: {err}"),
+            Self::Ron(err) => write!(f, "failed to parse RON bindings: {err}"),
+            Self::Toml(err) => write!(f, "failed to parse TOML bindings: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ActionMapError {}
+
+impl From<std::io::Error> for ActionMapError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for ActionMapError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        Self::Ron(err)
+    }
+}
+
+impl From<toml::de::Error> for ActionMapError {
+    fn from(err: toml::de::Error) -> Self {
+        Self::Toml(err)
+    }
+}
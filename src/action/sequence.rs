@@ -0,0 +1,266 @@
+use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+
+use bevy::ecs::system::Resource;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use super::{ActionMap, InputBinding, KeyChord, TuiAction};
+use crate::events::KeySequenceMatched;
+
+/// How long to wait for the next key of a sequence by default, the same as Vim.
+const DEFAULT_SEQUENCE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Controls how sequences of keys are matched against the bindings in an [`ActionMap`].
+///
+/// # Examples
+///
+/// Turning off counts so the digits can be typed as they are:
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+/// enum Action {
+///     Save,
+/// }
+///
+/// App::new()
+///     .add_plugins((MinimalTuiPlugins, ActionPlugin::<Action>::default()))
+///     .insert_resource(KeySequenceSettings {
+///         counts: false,
+///         ..default()
+///     })
+///     .run();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Resource)]
+pub struct KeySequenceSettings {
+    /// How long to wait for the next key of a sequence or count before giving up on it, or `None`
+    /// to wait indefinitely. When the keys typed so far are bound by themselves as well as being
+    /// the start of a longer sequence, such as `g` when `g g` is also bound, their binding is
+    /// triggered once this runs out.
+    pub timeout: Option<Duration>,
+
+    /// Whether digits typed before a binding are collected into a count for it, as in `5j`. Only
+    /// `1` to `9` start a count, so `0` can be bound while `10j` still works. Digits that start a
+    /// binding of their own keep it unless a count is already being typed.
+    pub counts: bool,
+}
+
+impl Default for KeySequenceSettings {
+    fn default() -> Self {
+        Self {
+            timeout: Some(DEFAULT_SEQUENCE_TIMEOUT),
+            counts: true,
+        }
+    }
+}
+
+/// The start of a key sequence that has been typed but not yet completed, along with any count
+/// typed before it. This is what a status line shows to let the player know more keys are
+/// expected, and is written the same way, such as `5 ctrl+x`.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::Paragraph;
+///
+/// #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+/// enum Action {
+///     Save,
+/// }
+///
+/// #[derive(Component)]
+/// struct StatusLine;
+///
+/// fn show_pending(
+///     actions: Res<ActionState<Action>>,
+///     mut status: Query<&mut TuiParagraph, With<StatusLine>>,
+/// ) {
+///     for mut paragraph in &mut status {
+///         paragraph.0 = Paragraph::new(actions.pending().to_string());
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PendingKeys {
+    count: Option<u32>,
+    chords: Vec<KeyChord>,
+
+    /// When the last of the chords or digits of the count was typed.
+    since: Duration,
+}
+
+impl PendingKeys {
+    /// The count typed so far, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let state = ActionState::<&str>::default();
+    /// assert_eq!(state.pending().count(), None);
+    /// ```
+    #[must_use]
+    pub fn count(&self) -> Option<u32> {
+        self.count
+    }
+
+    /// The chords typed so far that are the start of one or more bound sequences.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let state = ActionState::<&str>::default();
+    /// assert!(state.pending().chords().is_empty());
+    /// ```
+    #[must_use]
+    pub fn chords(&self) -> &[KeyChord] {
+        &self.chords
+    }
+
+    /// Whether nothing has been typed towards a binding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let state = ActionState::<&str>::default();
+    /// assert!(state.pending().is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.count.is_none() && self.chords.is_empty()
+    }
+
+    /// Forget everything typed so far.
+    pub(crate) fn clear(&mut self) {
+        self.count = None;
+        self.chords.clear();
+    }
+
+    /// Add a typed chord, returning the bindings it completes.
+    pub(crate) fn push<A: TuiAction>(
+        &mut self,
+        map: &ActionMap<A>,
+        settings: &KeySequenceSettings,
+        chord: KeyChord,
+        now: Duration,
+    ) -> Vec<KeySequenceMatched<A>> {
+        if settings.counts && self.chords.is_empty() {
+            if let Some(digit) = count_digit(chord) {
+                let starts_binding = !map.matching(&[chord]).is_empty() || map.continues(&[chord]);
+
+                let starts_count = digit != 0 && !starts_binding;
+
+                if self.count.is_some() || starts_count {
+                    let count = self.count.unwrap_or(0);
+                    self.count = Some(count.saturating_mul(10).saturating_add(digit));
+                    self.since = now;
+                    return Vec::new();
+                }
+            }
+        }
+
+        let mut typed = self.chords.clone();
+        typed.push(chord);
+
+        // Wait for more keys as long as they could still complete a longer sequence
+        if map.continues(&typed) {
+            self.chords = typed;
+            self.since = now;
+            return Vec::new();
+        }
+
+        let exact = map.matching(&typed);
+        if !exact.is_empty() {
+            return self.finish(exact);
+        }
+
+        // The chord doesn't continue the sequence, which is either abandoned or was complete by
+        // itself, and is then tried as the start of a new one
+        if self.chords.is_empty() {
+            self.clear();
+            return Vec::new();
+        }
+
+        let mut matched = self.finish(map.matching(&self.chords));
+        matched.extend(self.push(map, settings, chord, now));
+        matched
+    }
+
+    /// Give up on the sequence or count being typed if the timeout has passed, returning the
+    /// binding of the keys typed so far if they have one.
+    pub(crate) fn expire<A: TuiAction>(
+        &mut self,
+        map: &ActionMap<A>,
+        settings: &KeySequenceSettings,
+        now: Duration,
+    ) -> Vec<KeySequenceMatched<A>> {
+        let Some(timeout) = settings.timeout else {
+            return Vec::new();
+        };
+
+        if self.is_empty() || now.saturating_sub(self.since) < timeout {
+            return Vec::new();
+        }
+
+        // A count on its own isn't bound to anything and is just forgotten
+        if self.chords.is_empty() {
+            self.clear();
+            return Vec::new();
+        }
+
+        self.finish(map.matching(&self.chords))
+    }
+
+    /// Report the bindings that were matched with the count typed for them, and start over.
+    fn finish<A: TuiAction>(
+        &mut self,
+        bindings: Vec<(A, &InputBinding)>,
+    ) -> Vec<KeySequenceMatched<A>> {
+        let count = self.count;
+        self.clear();
+
+        bindings
+            .into_iter()
+            .map(|(action, binding)| KeySequenceMatched {
+                action,
+                binding: binding.clone(),
+                count,
+            })
+            .collect()
+    }
+}
+
+impl Display for PendingKeys {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut separator = "";
+
+        if let Some(count) = self.count {
+            write!(f, "{count}")?;
+            separator = " ";
+        }
+
+        for chord in &self.chords {
+            write!(f, "{separator}{chord}")?;
+            separator = " ";
+        }
+
+        Ok(())
+    }
+}
+
+/// The value of a chord typing a digit without any modifiers.
+fn count_digit(chord: KeyChord) -> Option<u32> {
+    match chord.code {
+        KeyCode::Char(ch) if chord.modifiers == KeyModifiers::NONE => ch.to_digit(10),
+        _ => None,
+    }
+}
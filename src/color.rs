@@ -0,0 +1,429 @@
+use std::io::{self, Write};
+
+use bevy::ecs::system::Resource;
+use ratatui::backend::{Backend, ClearType, WindowSize};
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+
+/// The 16 named colors in the order of their palette indexes along with the RGB values xterm uses
+/// for them by default. Terminals are free to change these so they are only used as a best guess
+/// when picking the nearest color.
+const ANSI_PALETTE: [(Color, [u8; 3]); 16] = [
+    (Color::Black, [0, 0, 0]),
+    (Color::Red, [205, 0, 0]),
+    (Color::Green, [0, 205, 0]),
+    (Color::Yellow, [205, 205, 0]),
+    (Color::Blue, [0, 0, 238]),
+    (Color::Magenta, [205, 0, 205]),
+    (Color::Cyan, [0, 205, 205]),
+    (Color::Gray, [229, 229, 229]),
+    (Color::DarkGray, [127, 127, 127]),
+    (Color::LightRed, [255, 0, 0]),
+    (Color::LightGreen, [0, 255, 0]),
+    (Color::LightYellow, [255, 255, 0]),
+    (Color::LightBlue, [92, 92, 255]),
+    (Color::LightMagenta, [255, 0, 255]),
+    (Color::LightCyan, [0, 255, 255]),
+    (Color::White, [255, 255, 255]),
+];
+
+/// The intensity of each step along the axes of the 6x6x6 color cube making up indexes 16 to 231
+/// of the 256 color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The range of colors the connected terminal is able to display. This is detected when the
+/// [`TuiPlugin`](crate::TuiPlugin) is built using
+/// [`detect_color_support`](crate::prelude::detect_color_support) and can be replaced by inserting
+/// the resource yourself if the detection gets it wrong.
+///
+/// The variants are ordered from the least to the most capable so they can be compared directly.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+/// use ratatui::style::Color;
+///
+/// assert!(ColorSupport::Ansi16 < ColorSupport::TrueColor);
+/// assert_eq!(ColorSupport::Ansi16.downsample(Color::Rgb(250, 10, 10)), Color::LightRed);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Resource)]
+pub enum ColorSupport {
+    /// Only the 16 named colors are available.
+    Ansi16,
+
+    /// The 256 color palette is available through [`Color::Indexed`].
+    Indexed256,
+
+    /// Any 24-bit color can be displayed using [`Color::Rgb`].
+    #[default]
+    TrueColor,
+}
+
+impl ColorSupport {
+    /// Map a color to the closest one the terminal is able to display. Named colors and
+    /// [`Color::Reset`] are always supported and returned as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::style::Color;
+    ///
+    /// assert_eq!(ColorSupport::Indexed256.downsample(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+    /// assert_eq!(ColorSupport::Ansi16.downsample(Color::Indexed(196)), Color::LightRed);
+    /// assert_eq!(ColorSupport::TrueColor.downsample(Color::Rgb(1, 2, 3)), Color::Rgb(1, 2, 3));
+    /// ```
+    #[must_use]
+    pub fn downsample(self, color: Color) -> Color {
+        match (self, color) {
+            (Self::Indexed256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_indexed([r, g, b])),
+            (Self::Ansi16, Color::Rgb(r, g, b)) => nearest_ansi([r, g, b]),
+            (Self::Ansi16, Color::Indexed(idx)) => nearest_ansi(indexed_rgb(idx)),
+            _ => color,
+        }
+    }
+
+    /// Downsample the colors of every cell in a buffer. Frames drawn to the terminal already have
+    /// this applied when [`TuiRenderSettings::downsample_colors`](crate::render::TuiRenderSettings)
+    /// is enabled, this is for buffers used in other ways.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::style::{Color, Style};
+    ///
+    /// let area = Rect::new(0, 0, 4, 1);
+    /// let mut buffer = Buffer::empty(area);
+    /// buffer.set_style(area, Style::default().bg(Color::Rgb(0, 0, 0)));
+    ///
+    /// ColorSupport::Ansi16.downsample_buffer(&mut buffer);
+    /// assert_eq!(buffer.get(0, 0).bg, Color::Black);
+    /// ```
+    pub fn downsample_buffer(self, buffer: &mut Buffer) {
+        if self == Self::TrueColor {
+            return;
+        }
+
+        for cell in &mut buffer.content {
+            cell.fg = self.downsample(cell.fg);
+            cell.bg = self.downsample(cell.bg);
+            cell.underline_color = self.downsample(cell.underline_color);
+        }
+    }
+}
+
+/// How the colors of each cell are changed as it is written out to the terminal by a
+/// [`ColorFilterBackend`]. The filter of the terminal the application draws to follows the
+/// [`TuiRenderSettings`](crate::prelude::TuiRenderSettings) and [`ColorSupport`] resources.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+/// use ratatui::style::Color;
+///
+/// assert_eq!(ColorFilter::Monochrome.apply(Color::Red), Color::Reset);
+/// assert_eq!(
+///     ColorFilter::Downsample(ColorSupport::Ansi16).apply(Color::Rgb(0, 0, 0)),
+///     Color::Black
+/// );
+/// assert_eq!(ColorFilter::None.apply(Color::Red), Color::Red);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum ColorFilter {
+    /// Colors are written out as they are.
+    #[default]
+    None,
+
+    /// Colors are replaced with the nearest the terminal is able to display.
+    Downsample(ColorSupport),
+
+    /// Colors are reset to the terminal's defaults, leaving modifiers such as bold and reverse in
+    /// place.
+    Monochrome,
+}
+
+impl ColorFilter {
+    /// Change a single color according to the filter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::style::Color;
+    ///
+    /// let filter = ColorFilter::Downsample(ColorSupport::Indexed256);
+    /// assert_eq!(filter.apply(Color::Rgb(255, 0, 0)), Color::Indexed(196));
+    /// ```
+    #[must_use]
+    pub fn apply(self, color: Color) -> Color {
+        match self {
+            Self::None => color,
+            Self::Downsample(support) => support.downsample(color),
+            Self::Monochrome => Color::Reset,
+        }
+    }
+
+    /// Change the colors of a single cell according to the filter.
+    fn apply_cell(self, cell: &mut Cell) {
+        cell.fg = self.apply(cell.fg);
+        cell.bg = self.apply(cell.bg);
+        cell.underline_color = self.apply(cell.underline_color);
+    }
+}
+
+/// A [`Backend`] that passes every cell through a [`ColorFilter`] before handing it to the backend
+/// it wraps. This is the backend of the [`BevyTerminal`](crate::BevyTerminal), so the colors of
+/// every frame are filtered, whether drawn by the render system or by hand with
+/// [`ratatui::Terminal::draw`].
+///
+/// Changing the filter doesn't redraw cells that have already been written out, the terminal
+/// should be cleared afterwards for it to apply to the whole screen.
+///
+/// # Examples
+///
+/// Drawing by hand with `NO_COLOR` respected:
+///
+/// ```
+/// use bevy_tui::prelude::*;
+/// use ratatui::backend::TestBackend;
+/// use ratatui::style::{Color, Stylize};
+/// use ratatui::widgets::Paragraph;
+///
+/// let mut backend = ColorFilterBackend::new(TestBackend::new(5, 1));
+/// backend.set_filter(ColorFilter::Monochrome);
+///
+/// let mut terminal = ratatui::Terminal::new(backend)?;
+/// terminal.draw(|frame| {
+///     frame.render_widget(Paragraph::new("hello".red().bold()), frame.size());
+/// })?;
+///
+/// let cell = terminal.backend().inner().buffer().get(0, 0);
+/// assert_eq!(cell.fg, Color::Reset);
+/// assert!(cell.modifier.contains(ratatui::style::Modifier::BOLD));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct ColorFilterBackend<B> {
+    inner: B,
+    filter: ColorFilter,
+}
+
+impl<B> ColorFilterBackend<B> {
+    /// Wrap a backend, writing colors out unchanged until a filter is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::backend::TestBackend;
+    ///
+    /// let backend = ColorFilterBackend::new(TestBackend::new(80, 24));
+    /// assert_eq!(backend.filter(), ColorFilter::None);
+    /// ```
+    #[must_use]
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            filter: ColorFilter::None,
+        }
+    }
+
+    /// The filter applied to the cells being drawn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::backend::TestBackend;
+    ///
+    /// let mut backend = ColorFilterBackend::new(TestBackend::new(80, 24));
+    /// backend.set_filter(ColorFilter::Monochrome);
+    /// assert_eq!(backend.filter(), ColorFilter::Monochrome);
+    /// ```
+    #[must_use]
+    pub fn filter(&self) -> ColorFilter {
+        self.filter
+    }
+
+    /// Replace the filter applied to the cells drawn from now on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::backend::TestBackend;
+    ///
+    /// let mut backend = ColorFilterBackend::new(TestBackend::new(80, 24));
+    /// backend.set_filter(ColorFilter::Downsample(ColorSupport::Ansi16));
+    /// ```
+    pub fn set_filter(&mut self, filter: ColorFilter) {
+        self.filter = filter;
+    }
+
+    /// The backend being drawn to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::backend::TestBackend;
+    ///
+    /// let backend = ColorFilterBackend::new(TestBackend::new(80, 24));
+    /// assert_eq!(backend.inner().buffer().area.width, 80);
+    /// ```
+    #[must_use]
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// The backend being drawn to, for changes that bypass the filter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::backend::TestBackend;
+    ///
+    /// let mut backend = ColorFilterBackend::new(TestBackend::new(80, 24));
+    /// backend.inner_mut().resize(100, 30);
+    /// ```
+    pub fn inner_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+}
+
+impl<B: Backend> Backend for ColorFilterBackend<B> {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        if self.filter == ColorFilter::None {
+            return self.inner.draw(content);
+        }
+
+        let filtered: Vec<(u16, u16, Cell)> = content
+            .map(|(x, y, cell)| {
+                let mut cell = cell.clone();
+                self.filter.apply_cell(&mut cell);
+                (x, y, cell)
+            })
+            .collect();
+
+        self.inner
+            .draw(filtered.iter().map(|(x, y, cell)| (*x, *y, cell)))
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.inner.append_lines(n)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
+        self.inner.get_cursor()
+    }
+
+    fn set_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        self.inner.set_cursor(x, y)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.inner.clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.inner.clear_region(clear_type)
+    }
+
+    fn size(&self) -> io::Result<Rect> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.inner.window_size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Backend::flush(&mut self.inner)
+    }
+}
+
+// Escape sequences queued on the backend, as is done with crossterm commands, bypass the filter
+impl<B: Write> Write for ColorFilterBackend<B> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Write::flush(&mut self.inner)
+    }
+}
+
+fn distance(a: [u8; 3], b: [u8; 3]) -> u32 {
+    a.iter()
+        .zip(b)
+        .map(|(&x, y)| u32::from(x.abs_diff(y)).pow(2))
+        .sum()
+}
+
+fn nearest_ansi(rgb: [u8; 3]) -> Color {
+    ANSI_PALETTE
+        .iter()
+        .min_by_key(|(_, candidate)| distance(rgb, *candidate))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+/// Finds the closest entry among the color cube and grayscale ramp of the 256 color palette. The
+/// first 16 entries are skipped as terminals commonly remap them.
+fn nearest_indexed(rgb: [u8; 3]) -> u8 {
+    let cube_step = |value: u8| -> u8 {
+        let closest = CUBE_LEVELS
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, level)| level.abs_diff(value))
+            .map_or(0, |(step, _)| step);
+        u8::try_from(closest).unwrap_or_default()
+    };
+
+    let steps = rgb.map(cube_step);
+    let cube_index = 16 + 36 * steps[0] + 6 * steps[1] + steps[2];
+
+    let average = rgb.iter().map(|&c| u16::from(c)).sum::<u16>() / 3;
+    let gray_step = u8::try_from(average.saturating_sub(3) / 10)
+        .unwrap_or_default()
+        .min(23);
+    let gray_index = 232 + gray_step;
+
+    if distance(rgb, indexed_rgb(gray_index)) < distance(rgb, indexed_rgb(cube_index)) {
+        gray_index
+    } else {
+        cube_index
+    }
+}
+
+/// The RGB value of an entry in the 256 color palette.
+fn indexed_rgb(index: u8) -> [u8; 3] {
+    match index {
+        0..=15 => ANSI_PALETTE[usize::from(index)].1,
+        16..=231 => {
+            let offset = index - 16;
+            [offset / 36, (offset / 6) % 6, offset % 6].map(|step| CUBE_LEVELS[usize::from(step)])
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            [level; 3]
+        }
+    }
+}
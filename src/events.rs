@@ -0,0 +1,399 @@
+// The `Reflect` derive makes use of the disallowed `Option#unwrap` method, see the input module
+// for why this can't be allowed any more narrowly. The events are kept together here so the
+// exception doesn't extend to any other code.
+#![allow(clippy::disallowed_methods)]
+
+use bevy::ecs::entity::Entity;
+use bevy::ecs::event::Event;
+use bevy::reflect::Reflect;
+#[cfg(feature = "serialize")]
+use bevy::reflect::{ReflectDeserialize, ReflectSerialize};
+use crossterm::event::Event as CrossEvent;
+
+use crate::action::{InputBinding, TuiAction};
+
+/// Sent whenever keyboard focus moves from one entity to another.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn log_focus(mut events: EventReader<FocusChanged>) {
+///     for event in events.read() {
+///         println!("focus moved from {:?} to {:?}", event.previous, event.current);
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Event, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct FocusChanged {
+    /// The entity that previously had focus.
+    pub previous: Option<Entity>,
+
+    /// The entity that now has focus.
+    pub current: Option<Entity>,
+}
+
+/// Key presses and pastes delivered to the entity that had focus when they were received. These
+/// are sent before the global keyboard input is updated so widgets get the first look at them.
+/// The Tab and Shift+Tab presses used to navigate between entities are not forwarded.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn typed_characters(mut events: EventReader<FocusedInput>) {
+///     for input in events.read() {
+///         if let crossterm::event::Event::Key(key) = input.event {
+///             println!("{:?} received {:?}", input.entity, key.code);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Event, Reflect)]
+#[reflect_value(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect_value(Serialize, Deserialize)
+)]
+pub struct FocusedInput {
+    /// The entity with focus the event is intended for.
+    pub entity: Entity,
+
+    /// The key or paste event received from the terminal.
+    pub event: CrossEvent,
+}
+
+/// The raw events received for a single [`TuiSession`](crate::session::TuiSession), the
+/// per-session equivalent of [`RawConsoleEvent`](crate::RawConsoleEvent).
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn log_pastes(mut events: EventReader<SessionEvent>) {
+///     for SessionEvent { session, event } in events.read() {
+///         if let crossterm::event::Event::Paste(text) = event {
+///             println!("{session:?} pasted {text}");
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Event, Reflect)]
+#[reflect_value(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect_value(Serialize, Deserialize)
+)]
+pub struct SessionEvent {
+    /// The session entity the event was received for.
+    pub session: Entity,
+
+    /// The event received from the session's terminal.
+    pub event: CrossEvent,
+}
+
+/// Sent when a new session entity has been spawned, such as when a client connects.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn greet(mut events: EventReader<SessionStarted>) {
+///     for event in events.read() {
+///         println!("{:?} connected", event.session);
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Event, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SessionStarted {
+    /// The entity of the new session.
+    pub session: Entity,
+}
+
+/// Sent when a session has ended and its entity has been despawned. Any screen spawned for the
+/// session is left in place for the app to clean up.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn farewell(mut events: EventReader<SessionEnded>) {
+///     for event in events.read() {
+///         println!("{:?} disconnected", event.session);
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Event, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SessionEnded {
+    /// The entity of the session that ended.
+    pub session: Entity,
+}
+
+/// Sent whenever the selected row of a [`TuiList`](crate::widgets::TuiList) or
+/// [`TuiTable`](crate::widgets::TuiTable) is changed by the keyboard or mouse. Holds the entity of
+/// the widget and the index of the newly selected row.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn server_selected(mut events: EventReader<Selected>) {
+///     for Selected(entity, index) in events.read() {
+///         println!("{entity:?} selected row {index}");
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Event, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Selected(pub Entity, pub usize);
+
+/// Sent when Enter is pressed while a [`TextInput`](crate::widgets::TextInput) has focus.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn login(mut events: EventReader<TextInputSubmitted>) {
+///     for submitted in events.read() {
+///         println!("{:?} submitted {}", submitted.entity, submitted.value);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Event, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct TextInputSubmitted {
+    /// The entity of the text input.
+    pub entity: Entity,
+
+    /// The value of the input when it was submitted.
+    pub value: String,
+}
+
+/// Sent when the application is about to be suspended. The frame it is sent in is the last one
+/// drawn before the process stops, making this the place to save anything that would be lost if
+/// the application were never resumed.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn pause(mut events: EventReader<Suspended>, mut time: ResMut<Time<Virtual>>) {
+///     if events.read().count() > 0 {
+///         time.pause();
+///     }
+/// }
+/// ```
+#[cfg(unix)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Event, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Suspended;
+
+/// Sent once the application continues after being suspended, and the terminal has been
+/// initialized again.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn unpause(mut events: EventReader<Resumed>, mut time: ResMut<Time<Virtual>>) {
+///     if events.read().count() > 0 {
+///         time.unpause();
+///     }
+/// }
+/// ```
+#[cfg(unix)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Event, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Resumed;
+
+/// Sent whenever the keys typed match a key binding in an [`ActionMap`], whether a single chord or
+/// a sequence of them, along with the count typed before it. Unlike the [`ActionState`] this
+/// reports every match, including an action matched several times in the same frame.
+///
+/// The event can only be reflected when the action type is, so unlike the other events it isn't
+/// registered by the [`ActionPlugin`]. Register it alongside the action type when it derives
+/// [`Reflect`], and the serde traits as well with the `serialize` feature.
+///
+/// [`ActionMap`]: crate::prelude::ActionMap
+/// [`ActionPlugin`]: crate::prelude::ActionPlugin
+/// [`ActionState`]: crate::prelude::ActionState
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+/// enum Action {
+///     Down,
+/// }
+///
+/// fn move_cursor(mut matches: EventReader<KeySequenceMatched<Action>>, mut line: Local<u32>) {
+///     for matched in matches.read() {
+///         if matched.action == Action::Down {
+///             *line += matched.count.unwrap_or(1);
+///         }
+///     }
+/// }
+/// ```
+///
+/// Registering the event for a reflected action type:
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Reflect)]
+/// #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+/// enum Action {
+///     Down,
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins(ActionPlugin::<Action>::default())
+///     .register_type::<Action>()
+///     .register_type::<KeySequenceMatched<Action>>();
+///
+/// let registry = app.world.resource::<AppTypeRegistry>().read();
+/// assert!(registry.get(std::any::TypeId::of::<KeySequenceMatched<Action>>()).is_some());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Event, Reflect)]
+#[reflect(PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize, where A: serde::Serialize + serde::de::DeserializeOwned)
+)]
+pub struct KeySequenceMatched<A: TuiAction> {
+    /// The action the keys are bound to.
+    pub action: A,
+
+    /// The binding that was matched.
+    pub binding: InputBinding,
+
+    /// The number typed before the binding, if any.
+    pub count: Option<u32>,
+}
+
+/// Sent once a program run with [`ShellOut`] has exited and the terminal has been initialized
+/// again. The outcome is kept as plain values so the event can be serialized like the others.
+///
+/// [`ShellOut`]: crate::prelude::ShellOut
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn reload_config(mut events: EventReader<ShellOutFinished>) {
+///     for event in events.read() {
+///         match (&event.error, event.code) {
+///             (Some(err), _) => eprintln!("failed to run {}: {err}", event.program),
+///             (None, Some(0)) => println!("{} finished", event.program),
+///             (None, Some(code)) => eprintln!("{} failed with {code}", event.program),
+///             (None, None) => eprintln!("{} was killed", event.program),
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Event, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct ShellOutFinished {
+    /// The name or path of the program that was run.
+    pub program: String,
+
+    /// The exit code of the program, or `None` if it couldn't be started or was ended by a
+    /// signal.
+    pub code: Option<i32>,
+
+    /// Why the program couldn't be started, if it couldn't.
+    pub error: Option<String>,
+}
+
+impl ShellOutFinished {
+    /// Whether the program was started and exited successfully.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_tui::prelude::*;
+    ///
+    /// fn check(mut events: EventReader<ShellOutFinished>) {
+    ///     for event in events.read() {
+    ///         if !event.success() {
+    ///             eprintln!("{} didn't finish cleanly", event.program);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn success(&self) -> bool {
+        self.error.is_none() && self.code == Some(0)
+    }
+}
@@ -0,0 +1,344 @@
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::event::{EventReader, EventWriter};
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::system::{Query, Res, ResMut, Resource, SystemParam};
+use bevy::hierarchy::{Children, Parent};
+use bevy::input::keyboard::KeyCode;
+use bevy::input::mouse::MouseButton;
+use bevy::input::ButtonState;
+use crossterm::event::{Event as CrossEvent, KeyCode as CrossKeyCode, KeyEventKind};
+use ratatui::layout::Position;
+
+use crate::events::{FocusChanged, FocusedInput};
+use crate::input::converters::{convert_key_code, convert_mouse_input};
+use crate::input::MouseInput;
+use crate::layout::{LayoutAreas, TuiNode};
+use crate::session::{unclaimed_roots, TuiSession};
+use crate::RawConsoleEvent;
+
+/// Marks an entity as able to receive keyboard focus. Focus moves between focusable entities in
+/// the order they appear in the [`TuiNode`] hierarchy when Tab or Shift+Tab are pressed, or
+/// directly to one when it is clicked on.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn spawn_fields(mut commands: Commands) {
+///     commands.spawn((ParagraphBundle::default(), Focusable::default()));
+///     commands.spawn((ParagraphBundle::default(), Focusable::capturing()));
+/// }
+/// ```
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+pub struct Focusable {
+    /// When set, key presses received while this entity has focus are only delivered to it
+    /// through [`FocusedInput`] and will not be reflected in the global `ButtonInput<KeyCode>`
+    /// resource. This is needed by text entry fields so typing doesn't trigger application wide
+    /// shortcuts. The Escape key is never captured.
+    pub capture_keyboard: bool,
+}
+
+impl Focusable {
+    /// A focusable entity that captures the keyboard while it has focus.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// assert!(Focusable::capturing().capture_keyboard);
+    /// ```
+    #[must_use]
+    pub fn capturing() -> Self {
+        Self {
+            capture_keyboard: true,
+        }
+    }
+}
+
+/// The entity that currently has keyboard focus, if any. The resource tracks focus on the terminal
+/// the process is attached to, while each [`TuiSession`] tracks its own focus with this as a
+/// component on the session's entity.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// #[derive(Component)]
+/// struct UsernameField;
+///
+/// fn focus_username(
+///     mut focused: ResMut<FocusedEntity>,
+///     fields: Query<Entity, With<UsernameField>>,
+/// ) {
+///     focused.set(fields.get_single().ok());
+/// }
+/// ```
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq, Resource)]
+pub struct FocusedEntity(Option<Entity>);
+
+impl FocusedEntity {
+    /// The currently focused entity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// assert!(FocusedEntity::default().get().is_none());
+    /// ```
+    #[must_use]
+    pub fn get(&self) -> Option<Entity> {
+        self.0
+    }
+
+    /// Change which entity has focus. Setting an entity that isn't [`Focusable`] will be undone
+    /// during the next focus update.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let mut focused = FocusedEntity::default();
+    /// focused.set(Some(Entity::from_raw(1)));
+    /// assert_eq!(focused.get(), Some(Entity::from_raw(1)));
+    /// ```
+    pub fn set(&mut self, entity: Option<Entity>) {
+        self.0 = entity;
+    }
+
+    /// Whether the provided entity is the one with focus.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_tui::prelude::*;
+    ///
+    /// assert!(!FocusedEntity::default().is(Entity::from_raw(1)));
+    /// ```
+    #[must_use]
+    pub fn is(&self, entity: Entity) -> bool {
+        self.0 == Some(entity)
+    }
+}
+
+/// Tracks whether the global keyboard input should ignore this frame's key presses as they were
+/// captured by the focused entity.
+#[derive(Debug, Default, Resource)]
+pub(crate) struct KeyboardCapture(pub(crate) bool);
+
+enum Navigation {
+    Next,
+    Previous,
+}
+
+fn navigation(event: &CrossEvent) -> Option<Navigation> {
+    let CrossEvent::Key(key) = event else {
+        return None;
+    };
+
+    if key.kind == KeyEventKind::Release {
+        return None;
+    }
+
+    match convert_key_code(key.code).as_slice() {
+        [KeyCode::Tab] => Some(Navigation::Next),
+        [KeyCode::ShiftLeft, KeyCode::Tab] => Some(Navigation::Previous),
+        _ => None,
+    }
+}
+
+/// Collects all the focusable entities in tab order. Entities in the layout hierarchy beneath the
+/// provided roots come first in the order they are drawn, followed by any that aren't part of the
+/// layout at all.
+pub(crate) fn tab_order(
+    scope: &[Entity],
+    all_roots: &[Entity],
+    children: &Query<&Children>,
+    focusables: &Query<(Entity, &Focusable, Option<&TuiNode>)>,
+) -> Vec<Entity> {
+    let ordered = descendants(scope, children)
+        .into_iter()
+        .filter(|entity| focusables.contains(*entity))
+        .collect::<Vec<_>>();
+
+    // Anything beneath a root outside of the scope belongs to another terminal session
+    let in_layout = descendants(all_roots, children);
+    let mut detached: Vec<Entity> = focusables
+        .iter()
+        .map(|(entity, _, _)| entity)
+        .filter(|entity| !ordered.contains(entity) && !in_layout.contains(entity))
+        .collect();
+    detached.sort();
+
+    let mut ordered = ordered;
+    ordered.extend(detached);
+    ordered
+}
+
+/// All of the entities beneath the provided roots, in the order they are drawn.
+pub(crate) fn descendants(roots: &[Entity], children: &Query<&Children>) -> Vec<Entity> {
+    let mut sorted_roots = roots.to_vec();
+    sorted_roots.sort();
+
+    let mut ordered = Vec::new();
+    let mut pending: Vec<Entity> = sorted_roots.into_iter().rev().collect();
+
+    while let Some(entity) = pending.pop() {
+        ordered.push(entity);
+
+        if let Ok(entity_children) = children.get(entity) {
+            pending.extend(entity_children.iter().rev());
+        }
+    }
+
+    ordered
+}
+
+fn step(order: &[Entity], current: Option<Entity>, direction: &Navigation) -> Option<Entity> {
+    if order.is_empty() {
+        return None;
+    }
+
+    let position = current.and_then(|c| order.iter().position(|e| *e == c));
+    let idx = match (position, direction) {
+        (None, Navigation::Next) => 0,
+        (None, Navigation::Previous) => order.len() - 1,
+        (Some(idx), Navigation::Next) => (idx + 1) % order.len(),
+        (Some(idx), Navigation::Previous) => (idx + order.len() - 1) % order.len(),
+    };
+
+    Some(order[idx])
+}
+
+/// The queries and event writers needed to move focus around a set of layout roots.
+#[derive(SystemParam)]
+pub(crate) struct FocusParams<'w, 's> {
+    focus_changed: EventWriter<'w, FocusChanged>,
+    focused_input: EventWriter<'w, FocusedInput>,
+    roots: Query<'w, 's, Entity, (With<TuiNode>, Without<Parent>)>,
+    children: Query<'w, 's, &'static Children>,
+    focusables: Query<'w, 's, (Entity, &'static Focusable, Option<&'static TuiNode>)>,
+}
+
+impl FocusParams<'_, '_> {
+    /// Every layout root, regardless of which terminal it is shown on.
+    pub(crate) fn all_roots(&self) -> Vec<Entity> {
+        self.roots.iter().collect()
+    }
+
+    /// Moves focus within the hierarchy beneath the provided roots in response to Tab, Shift+Tab,
+    /// and mouse clicks, then forwards the remaining key and paste events to the focused entity.
+    /// Clicks are tested against the areas the roots were laid out with on the terminal the events
+    /// came from. Returns whether the focused entity captured the keyboard.
+    pub(crate) fn route<'a>(
+        &mut self,
+        focused: &mut FocusedEntity,
+        scope: &[Entity],
+        areas: &LayoutAreas,
+        events: impl IntoIterator<Item = &'a CrossEvent>,
+    ) -> bool {
+        let initial = focused.get();
+        let all_roots = self.all_roots();
+
+        // Drop focus from anything that has been despawned or is no longer focusable
+        if let Some(entity) = focused.get() {
+            if !self.focusables.contains(entity) {
+                focused.set(None);
+            }
+        }
+
+        let mut captured = false;
+
+        for event in events {
+            if let Some([x, y]) = left_click(event) {
+                // The last entity in tab order is the one drawn on top
+                let clicked = tab_order(scope, &all_roots, &self.children, &self.focusables)
+                    .into_iter()
+                    .rev()
+                    .find(|entity| {
+                        areas
+                            .area(*entity)
+                            .map_or(false, |area| area.contains(Position { x, y }))
+                    });
+
+                if clicked.is_some() {
+                    focused.set(clicked);
+                }
+                continue;
+            }
+
+            if let Some(direction) = navigation(event) {
+                let order = tab_order(scope, &all_roots, &self.children, &self.focusables);
+                let next = step(&order, focused.get(), &direction);
+                focused.set(next);
+                continue;
+            }
+
+            let Some(entity) = focused.get() else {
+                continue;
+            };
+
+            if !matches!(event, CrossEvent::Key(_) | CrossEvent::Paste(_)) {
+                continue;
+            }
+
+            // Escape is never captured so there is always a way to reach the application's own
+            // bindings while a text field has focus.
+            let capturable = matches!(event, CrossEvent::Key(key) if key.code != CrossKeyCode::Esc);
+            if let Ok((_, focusable, _)) = self.focusables.get(entity) {
+                captured |= focusable.capture_keyboard && capturable;
+            }
+
+            self.focused_input.send(FocusedInput {
+                entity,
+                event: event.clone(),
+            });
+        }
+
+        if focused.get() != initial {
+            self.focus_changed.send(FocusChanged {
+                previous: initial,
+                current: focused.get(),
+            });
+        }
+
+        captured
+    }
+}
+
+fn left_click(event: &CrossEvent) -> Option<[u16; 2]> {
+    let CrossEvent::Mouse(mouse) = event else {
+        return None;
+    };
+
+    match convert_mouse_input(*mouse) {
+        MouseInput::Button(MouseButton::Left, ButtonState::Pressed, location) => Some(location),
+        _ => None,
+    }
+}
+
+/// Handles moving focus for the terminal the process is attached to, covering every layout root
+/// that isn't being shown by a [`TuiSession`].
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn focus_system(
+    mut focused: ResMut<FocusedEntity>,
+    mut capture: ResMut<KeyboardCapture>,
+    mut raw_events: EventReader<RawConsoleEvent>,
+    mut params: FocusParams,
+    areas: Res<LayoutAreas>,
+    sessions: Query<&TuiSession>,
+) {
+    let scope = unclaimed_roots(&params.all_roots(), &sessions);
+    let events = raw_events.read().map(|RawConsoleEvent(event)| event);
+
+    capture.0 = params.route(&mut focused, &scope, &areas, events);
+}
@@ -4,57 +4,247 @@
 // by the Reflect macro.
 #![allow(clippy::disallowed_methods)]
 
+use std::time::Duration;
+
 use bevy::app::App;
 use bevy::ecs::event::{EventReader, EventWriter};
-use bevy::ecs::system::{ResMut, Resource};
+use bevy::ecs::reflect::ReflectResource;
+use bevy::ecs::system::{Res, ResMut, Resource};
 use bevy::input::keyboard::KeyCode;
 use bevy::input::mouse::{MouseButton, MouseMotion};
 use bevy::input::{ButtonInput, ButtonState};
 use bevy::prelude::Event as BevyEvent;
+use bevy::reflect::std_traits::ReflectDefault;
 use bevy::reflect::Reflect;
+#[cfg(feature = "serialize")]
+use bevy::reflect::{ReflectDeserialize, ReflectSerialize};
 use crossterm::event::Event as CrossEvent;
 
-mod converters;
+pub(crate) mod converters;
+pub(crate) mod parser;
+
+use crate::focus::KeyboardCapture;
 
-use crate::RawConsoleEvent;
+/// How long to wait for the rest of an escape sequence by default.
+const DEFAULT_ESCAPE_TIMEOUT: Duration = Duration::from_millis(50);
 
-// todo: need to add a serialize feature and use it to add the additional serde and bevy reflect
-// traits to match bevy_winit.
+/// Controls how the raw input from a terminal is turned into events.
+///
+/// These settings apply to the terminal the process is attached to as well as sessions served over
+/// the network, such as with the [`TelnetPlugin`](crate::prelude::TelnetPlugin).
+///
+/// # Examples
+///
+/// Reporting Escape faster for a modal interface used over a quick connection:
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// App::new()
+///     .add_plugins(MinimalTuiPlugins)
+///     .insert_resource(TuiInputSettings {
+///         escape_timeout: Duration::from_millis(10),
+///     })
+///     .run();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Resource)]
+pub struct TuiInputSettings {
+    /// How long to wait for the rest of an escape sequence after receiving the Escape key before
+    /// reporting it as a key press of its own. Terminals send keys such as the arrows as a
+    /// sequence starting with Escape, so a timeout that's too short can split one that arrives
+    /// slowly into separate key presses, while a long one delays the Escape key. Defaults to 50
+    /// milliseconds, and zero reports Escape as soon as nothing else is waiting to be read.
+    ///
+    /// Terminals supporting the kitty keyboard protocol, which
+    /// [`initialize_terminal`](crate::prelude::initialize_terminal) asks for, send the Escape key
+    /// unambiguously so it is reported without waiting.
+    pub escape_timeout: Duration,
+}
 
-/// The Bevy version of `KeyboardInput` requires a scan code which we can't receive from a terminal
-/// as the code have already been adapted through a keyboard layout long before we receive the
-/// event.
+impl Default for TuiInputSettings {
+    fn default() -> Self {
+        Self {
+            escape_timeout: DEFAULT_ESCAPE_TIMEOUT,
+        }
+    }
+}
+
+/// A key being pressed or released, the terminal's equivalent of Bevy's own `KeyboardInput`
+/// event. The Bevy version requires a scan code which we can't receive from a terminal as the
+/// code has already been adapted through a keyboard layout long before we receive the event.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::input::ButtonState;
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn log_keys(mut events: EventReader<KeyboardInput>) {
+///     for event in events.read() {
+///         if event.state == ButtonState::Pressed {
+///             println!("{:?} pressed", event.key_code);
+///         }
+///     }
+/// }
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, BevyEvent, Reflect)]
-pub(crate) struct KeyboardInput {
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct KeyboardInput {
     /// The key code of button pressed.
-    key_code: KeyCode,
+    pub key_code: KeyCode,
 
     /// The press state of the key. The release state will only be available on a minor subset of
     /// terminals.
-    state: ButtonState,
+    pub state: ButtonState,
 }
 
+/// A mouse event reported by the terminal, with the column and row of the cell the pointer was
+/// over when it happened.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn log_clicks(mut events: EventReader<MouseInput>) {
+///     for event in events.read() {
+///         if let MouseInput::Button(button, state, [column, row]) = event {
+///             println!("{button:?} {state:?} at {column},{row}");
+///         }
+///     }
+/// }
+/// ```
 // This enum name triggers one of the pedantic clippy modules which I generally agree with, but in
 // this case we're matching the name of the similar data structure in Bevy proper.
 #[allow(clippy::module_name_repetitions)]
 #[derive(Debug, Clone, Copy, PartialEq, BevyEvent, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
 pub enum MouseInput {
+    /// A mouse button was pressed or released.
     Button(MouseButton, ButtonState, [u16; 2]),
+
+    /// The mouse was moved, with or without a button held down.
     Movement([u16; 2]),
+
+    /// A scroll wheel movement with the direction it was scrolled in, positive values scroll
+    /// down and to the right. The terminal only ever reports a single step at a time.
+    Scroll([i8; 2], [u16; 2]),
 }
 
-/// TODO: write documentation
+/// Tracks where the mouse was last reported in the terminal, in columns and rows from the top
+/// left corner. This is used to turn the absolute positions in [`MouseInput`] into the relative
+/// [`MouseMotion`] events Bevy expects, and stays empty until the terminal reports the mouse.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_tui::prelude::*;
+/// let mouse_state = MouseState::default();
+/// assert_eq!(mouse_state.last_location(), None);
+/// ```
 #[derive(Debug, Default, Reflect, Resource)]
+#[reflect(Debug, Default, Resource)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
 pub struct MouseState {
     last_location: Option<[u16; 2]>,
 }
+
+impl MouseState {
+    /// The column and row the mouse was last reported at, if the terminal has reported it at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_tui::prelude::*;
+    ///
+    /// fn hover(mouse_state: Res<MouseState>) {
+    ///     if let Some([column, row]) = mouse_state.last_location() {
+    ///         println!("the mouse is over {column},{row}");
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn last_location(&self) -> Option<[u16; 2]> {
+        self.last_location
+    }
+}
+
+/// A placeholder for a terminal resize notification. It isn't sent yet, resizes currently only
+/// reach the application as a [`RawConsoleEvent`] holding a
+/// [`Resize`](crossterm::event::Event::Resize) event.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_tui::prelude::*;
+/// let resized = WindowResized;
+/// assert_eq!(resized, WindowResized);
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq, Reflect)]
-/// TODO: write documentation
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
 pub struct WindowResized;
 
+/// A published version of the raw Crossterm events received. This is one of the reasons why this
+/// library is currently tied to this particular TUI backend for now. If you're going to be using
+/// text input in your UI, these events are likely what you want over the `Input<KeyCode>` events
+/// as letter casing and non-US/ASCII keyboard characters are preserved.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_tui::RawConsoleEvent;
+/// RawConsoleEvent(crossterm::event::Event::FocusGained);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, BevyEvent, Reflect)]
+#[reflect_value(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect_value(Serialize, Deserialize)
+)]
+pub struct RawConsoleEvent(pub crossterm::event::Event);
+
 pub(crate) fn keyboard_input_system(
     mut key_input: ResMut<ButtonInput<KeyCode>>,
     mut keyboard_input_events: EventReader<KeyboardInput>,
+    capture: Option<Res<KeyboardCapture>>,
+) {
+    // When the focused entity has captured the keyboard its presses are only delivered to it
+    let captured = capture.map_or(false, |c| c.0);
+
+    update_keys(&mut key_input, keyboard_input_events.read(), captured);
+}
+
+/// Applies a frame's worth of keyboard events to the pressed state of the keys.
+pub(crate) fn update_keys<'a>(
+    key_input: &mut ButtonInput<KeyCode>,
+    events: impl IntoIterator<Item = &'a KeyboardInput>,
+    captured: bool,
 ) {
     // We don't get key release events from the terminal. There is an enhancement in the kitty
     // protocol that extends the system to include these but we can't rely on them. Instead we
@@ -68,8 +258,9 @@ pub(crate) fn keyboard_input_system(
     let currently_pressed: Vec<KeyCode> = key_input.get_pressed().copied().collect();
     let mut pressed_events = vec![];
 
-    for event in keyboard_input_events.read() {
+    for event in events {
         match event.state {
+            ButtonState::Pressed if captured => {}
             ButtonState::Pressed => {
                 pressed_events.push(event.key_code);
                 key_input.press(event.key_code);
@@ -101,7 +292,9 @@ pub(crate) fn mouse_input_system(
 
     for event in mouse_input_events.read() {
         let new_location = match event {
-            MouseInput::Button(_, _, loc) | MouseInput::Movement(loc) => loc,
+            MouseInput::Button(_, _, loc)
+            | MouseInput::Movement(loc)
+            | MouseInput::Scroll(_, loc) => loc,
         };
 
         if let Some(last_location) = mouse_state.last_location {
@@ -1,5 +1,4 @@
 use bevy::input::keyboard::KeyCode;
-// todo: do I get mouse scroll events?
 use bevy::input::mouse::MouseButton;
 use bevy::input::ButtonState;
 
@@ -140,12 +139,31 @@ fn character_key_code(chr: char) -> Vec<KeyCode> {
     }
 }
 
-pub(super) fn convert_keyboard_input(
+pub(crate) fn convert_keyboard_input(
     keyboard_input: crossterm::event::KeyEvent,
 ) -> Vec<KeyboardInput> {
+    use crossterm::event::KeyModifiers;
+
     let button_state = convert_input_kind(keyboard_input.kind);
 
-    let events: Vec<KeyboardInput> = convert_key_code(keyboard_input.code)
+    // Terminals only report modifiers alongside another key, so they're pressed along with it
+    // rather than being dropped, allowing combinations such as Ctrl+C to be told apart from C
+    let key = convert_key_code(keyboard_input.code);
+    let mut key_codes = Vec::new();
+    for (modifier, key_code) in [
+        (KeyModifiers::CONTROL, KeyCode::ControlLeft),
+        (KeyModifiers::ALT, KeyCode::AltLeft),
+        (KeyModifiers::SUPER, KeyCode::SuperLeft),
+        (KeyModifiers::SHIFT, KeyCode::ShiftLeft),
+    ] {
+        // Shifted characters already include the Shift key
+        if keyboard_input.modifiers.contains(modifier) && !key.contains(&key_code) {
+            key_codes.push(key_code);
+        }
+    }
+    key_codes.extend(key);
+
+    let events: Vec<KeyboardInput> = key_codes
         .into_iter()
         .map(|key_code| KeyboardInput {
             key_code,
@@ -156,7 +174,7 @@ pub(super) fn convert_keyboard_input(
     events
 }
 
-pub(super) fn convert_mouse_input(mouse_input: crossterm::event::MouseEvent) -> MouseInput {
+pub(crate) fn convert_mouse_input(mouse_input: crossterm::event::MouseEvent) -> MouseInput {
     use crossterm::event::MouseEventKind;
 
     // TODO: I need to convert this to Bevy's coordinate system, maybe I need to do it somewhere
@@ -171,12 +189,10 @@ pub(super) fn convert_mouse_input(mouse_input: crossterm::event::MouseEvent) ->
             MouseInput::Button(convert_mouse_button(btn), ButtonState::Released, location)
         }
         MouseEventKind::Moved => MouseInput::Movement(location),
-        MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
-            unimplemented!("{mouse_input:?}\r");
-        }
-        MouseEventKind::ScrollLeft | MouseEventKind::ScrollRight => {
-            unimplemented!("{mouse_input:?}\r");
-        }
+        MouseEventKind::ScrollDown => MouseInput::Scroll([0, 1], location),
+        MouseEventKind::ScrollUp => MouseInput::Scroll([0, -1], location),
+        MouseEventKind::ScrollLeft => MouseInput::Scroll([-1, 0], location),
+        MouseEventKind::ScrollRight => MouseInput::Scroll([1, 0], location),
     }
 }
 
@@ -199,7 +215,7 @@ fn convert_input_kind(kind: crossterm::event::KeyEventKind) -> ButtonState {
     }
 }
 
-fn convert_key_code(key_code: crossterm::event::KeyCode) -> Vec<KeyCode> {
+pub(crate) fn convert_key_code(key_code: crossterm::event::KeyCode) -> Vec<KeyCode> {
     use crossterm::event::KeyCode as TerminalKeyCode;
 
     match key_code {
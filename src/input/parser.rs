@@ -0,0 +1,626 @@
+//! A parser turning the raw bytes sent by a terminal into crossterm events. Crossterm only reads
+//! from the terminal the process is attached to, this is used for input arriving from anywhere
+//! else such as a network connection.
+
+use crossterm::event::{
+    Event as CrossEvent, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+
+const ESC: u8 = 0x1b;
+
+/// The sequence a terminal sends when bracketed paste content ends.
+const PASTE_END: &[u8] = b"\x1b[201~";
+
+/// The outcome of trying to parse a single event from the front of the buffer.
+enum Parsed {
+    /// An event (or nothing, for sequences that are recognized but ignored) along with the number
+    /// of bytes it consumed.
+    Complete(Option<CrossEvent>, usize),
+
+    /// The buffer ends part way through a sequence, more bytes are needed to know what it is.
+    Incomplete,
+}
+
+/// Incrementally parses terminal input. Bytes can be fed in as they arrive, sequences that are
+/// split across reads are held on to until they can be completed.
+#[derive(Default)]
+pub(crate) struct InputParser {
+    buffer: Vec<u8>,
+
+    /// Holds the content of a bracketed paste while waiting for it to be terminated.
+    paste: Option<Vec<u8>>,
+}
+
+impl InputParser {
+    /// Parse as many events as possible out of the bytes received so far.
+    pub(crate) fn advance(&mut self, bytes: &[u8]) -> Vec<CrossEvent> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut events = Vec::new();
+        let mut consumed = 0;
+
+        loop {
+            let remaining = &self.buffer[consumed..];
+            if remaining.is_empty() {
+                break;
+            }
+
+            if let Some(paste) = &mut self.paste {
+                if let Some(end) = find(remaining, PASTE_END) {
+                    paste.extend_from_slice(&remaining[..end]);
+                    let content = String::from_utf8_lossy(paste).into_owned();
+                    events.push(CrossEvent::Paste(content));
+                    self.paste = None;
+                    consumed += end + PASTE_END.len();
+                } else {
+                    // Hold back enough to recognize a terminator split across reads
+                    let keep = PASTE_END.len().saturating_sub(1).min(remaining.len());
+                    let take = remaining.len() - keep;
+                    paste.extend_from_slice(&remaining[..take]);
+                    consumed += take;
+                    break;
+                }
+
+                continue;
+            }
+
+            match parse_event(remaining) {
+                Parsed::Complete(event, len) => {
+                    consumed += len;
+
+                    if let Some(CrossEvent::Paste(_)) = event {
+                        // The paste start marker, the content follows
+                        self.paste = Some(Vec::new());
+                    } else if let Some(event) = event {
+                        events.push(event);
+                    }
+                }
+                Parsed::Incomplete => break,
+            }
+        }
+
+        self.buffer.drain(..consumed);
+        events
+    }
+
+    /// Whether there are bytes being held waiting for the rest of a sequence. The end of a paste
+    /// held back in case it's the start of the terminator doesn't count, a paste only ends with
+    /// its terminator however long that takes to arrive.
+    pub(crate) fn is_pending(&self) -> bool {
+        self.paste.is_none() && !self.buffer.is_empty()
+    }
+
+    /// Resolve any incomplete sequence once no more input has arrived for a while. A lone escape
+    /// byte can't be told apart from the start of a sequence until this happens.
+    pub(crate) fn flush(&mut self) -> Vec<CrossEvent> {
+        if self.paste.is_some() {
+            return Vec::new();
+        }
+
+        let pending = std::mem::take(&mut self.buffer);
+        let Some((&first, rest)) = pending.split_first() else {
+            return Vec::new();
+        };
+
+        if first != ESC {
+            // A truncated UTF-8 character, there is nothing sensible to report
+            return Vec::new();
+        }
+
+        let mut events = vec![key(KeyCode::Esc, KeyModifiers::NONE)];
+        events.extend(self.advance(rest));
+
+        // Anything still incomplete isn't going to be finished
+        self.buffer.clear();
+
+        events
+    }
+}
+
+/// Whether an event is the Escape key on its own, which crossterm reports when nothing else was
+/// waiting to be read after the escape byte.
+pub(crate) fn is_lone_escape(event: &CrossEvent) -> bool {
+    matches!(
+        event,
+        CrossEvent::Key(KeyEvent {
+            code: KeyCode::Esc,
+            modifiers: KeyModifiers::NONE,
+            ..
+        })
+    )
+}
+
+/// Put back together a sequence crossterm split into a lone Escape and the keys making up the
+/// rest of it, as happens when the rest arrives after crossterm stopped waiting for it. The
+/// events must start with the lone Escape, anything after the keys that could belong to the
+/// sequence is returned untouched.
+///
+/// A terminal that disambiguates escape codes sends Escape as a sequence of its own, so the
+/// events are already what was pressed and are returned untouched.
+pub(crate) fn rejoin_escape(events: &[CrossEvent], disambiguated: bool) -> Vec<CrossEvent> {
+    if disambiguated {
+        return events.to_vec();
+    }
+
+    let mut bytes = vec![ESC];
+    let mut joined = 1;
+
+    for event in events.iter().skip(1) {
+        let CrossEvent::Key(KeyEvent {
+            code: KeyCode::Char(ch),
+            modifiers,
+            ..
+        }) = event
+        else {
+            break;
+        };
+
+        if !(*modifiers - KeyModifiers::SHIFT).is_empty() {
+            break;
+        }
+
+        bytes.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes());
+        joined += 1;
+    }
+
+    let mut parser = InputParser::default();
+    let mut rejoined = parser.advance(&bytes);
+    rejoined.extend(parser.flush());
+    rejoined.extend(events.iter().skip(joined).cloned());
+
+    rejoined
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn key(code: KeyCode, modifiers: KeyModifiers) -> CrossEvent {
+    CrossEvent::Key(KeyEvent::new(code, modifiers))
+}
+
+fn char_key(ch: char, modifiers: KeyModifiers) -> CrossEvent {
+    let modifiers = if ch.is_uppercase() {
+        modifiers | KeyModifiers::SHIFT
+    } else {
+        modifiers
+    };
+
+    key(KeyCode::Char(ch), modifiers)
+}
+
+fn parse_event(buffer: &[u8]) -> Parsed {
+    match buffer[0] {
+        ESC => parse_escape(buffer),
+        byte => match parse_byte(buffer) {
+            Some((event, len)) => Parsed::Complete(event, len),
+            None if utf8_len(byte).map_or(false, |len| buffer.len() < len) => Parsed::Incomplete,
+            // An invalid byte, skip over it
+            None => Parsed::Complete(None, 1),
+        },
+    }
+}
+
+/// Parse a key that doesn't start with an escape byte, either a control character or a UTF-8
+/// encoded character.
+fn parse_byte(buffer: &[u8]) -> Option<(Option<CrossEvent>, usize)> {
+    let event = match buffer[0] {
+        b'\r' | b'\n' => key(KeyCode::Enter, KeyModifiers::NONE),
+        b'\t' => key(KeyCode::Tab, KeyModifiers::NONE),
+        0x7f | 0x08 => key(KeyCode::Backspace, KeyModifiers::NONE),
+        0x00 => key(KeyCode::Char(' '), KeyModifiers::CONTROL),
+        byte @ 0x01..=0x1a => key(
+            KeyCode::Char(char::from(byte - 0x01 + b'a')),
+            KeyModifiers::CONTROL,
+        ),
+        byte @ 0x1c..=0x1f => key(
+            KeyCode::Char(char::from(byte - 0x1c + b'4')),
+            KeyModifiers::CONTROL,
+        ),
+        byte => {
+            let len = utf8_len(byte)?;
+            let ch = std::str::from_utf8(buffer.get(..len)?)
+                .ok()?
+                .chars()
+                .next()?;
+            return Some((Some(char_key(ch, KeyModifiers::NONE)), len));
+        }
+    };
+
+    Some((Some(event), 1))
+}
+
+fn utf8_len(byte: u8) -> Option<usize> {
+    match byte {
+        0x00..=0x7f => Some(1),
+        0xc2..=0xdf => Some(2),
+        0xe0..=0xef => Some(3),
+        0xf0..=0xf4 => Some(4),
+        _ => None,
+    }
+}
+
+fn parse_escape(buffer: &[u8]) -> Parsed {
+    let Some(&next) = buffer.get(1) else {
+        return Parsed::Incomplete;
+    };
+
+    match next {
+        b'[' => parse_csi(buffer),
+        b'O' => match buffer.get(2) {
+            None => Parsed::Incomplete,
+            Some(&final_byte) => Parsed::Complete(cursor_key(final_byte, KeyModifiers::NONE), 3),
+        },
+        ESC => Parsed::Complete(Some(key(KeyCode::Esc, KeyModifiers::ALT)), 2),
+        _ => match parse_byte(&buffer[1..]) {
+            Some((Some(CrossEvent::Key(mut event)), len)) => {
+                event.modifiers |= KeyModifiers::ALT;
+                Parsed::Complete(Some(CrossEvent::Key(event)), len + 1)
+            }
+            Some((event, len)) => Parsed::Complete(event, len + 1),
+            None if buffer.len() < 1 + utf8_len(next).unwrap_or(1) => Parsed::Incomplete,
+            None => Parsed::Complete(None, 2),
+        },
+    }
+}
+
+/// Keys which are sent with a final byte identifying them, either through SS3 or CSI.
+fn cursor_key(final_byte: u8, modifiers: KeyModifiers) -> Option<CrossEvent> {
+    let code = match final_byte {
+        b'A' => KeyCode::Up,
+        b'B' => KeyCode::Down,
+        b'C' => KeyCode::Right,
+        b'D' => KeyCode::Left,
+        b'H' => KeyCode::Home,
+        b'F' => KeyCode::End,
+        b'P' => KeyCode::F(1),
+        b'Q' => KeyCode::F(2),
+        b'R' => KeyCode::F(3),
+        b'S' => KeyCode::F(4),
+        _ => return None,
+    };
+
+    Some(key(code, modifiers))
+}
+
+/// Decode the modifier parameter used by xterm style sequences, which is one more than a bitmask.
+fn modifiers(param: Option<u16>) -> KeyModifiers {
+    let mask = param.unwrap_or(1).saturating_sub(1);
+    let mut modifiers = KeyModifiers::NONE;
+
+    if mask & 1 != 0 {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    if mask & 2 != 0 {
+        modifiers |= KeyModifiers::ALT;
+    }
+    if mask & 4 != 0 {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+    if mask & 8 != 0 {
+        modifiers |= KeyModifiers::SUPER;
+    }
+
+    modifiers
+}
+
+fn parse_csi(buffer: &[u8]) -> Parsed {
+    // Parameter and intermediate bytes followed by a single final byte
+    let Some(end) = buffer[2..]
+        .iter()
+        .position(|byte| (0x40..=0x7e).contains(byte))
+    else {
+        return Parsed::Incomplete;
+    };
+
+    let len = end + 3;
+    let final_byte = buffer[len - 1];
+    let raw_params = &buffer[2..len - 1];
+
+    if let Some(params) = raw_params.strip_prefix(b"<") {
+        return Parsed::Complete(parse_sgr_mouse(params, final_byte), len);
+    }
+
+    let params: Vec<Option<u16>> = raw_params
+        .split(|&byte| byte == b';')
+        .map(|param| std::str::from_utf8(param).ok()?.parse().ok())
+        .collect();
+    let param = |idx: usize| params.get(idx).copied().flatten();
+
+    let event = match final_byte {
+        b'I' => Some(CrossEvent::FocusGained),
+        b'O' => Some(CrossEvent::FocusLost),
+        b'Z' => Some(key(KeyCode::BackTab, KeyModifiers::SHIFT)),
+        b'~' => tilde_key(param(0), modifiers(param(1))),
+        b'u' => param(0)
+            .and_then(|code| char::from_u32(u32::from(code)))
+            .map(|ch| match ch {
+                '\u{1b}' => key(KeyCode::Esc, modifiers(param(1))),
+                '\r' => key(KeyCode::Enter, modifiers(param(1))),
+                '\t' => key(KeyCode::Tab, modifiers(param(1))),
+                '\u{7f}' => key(KeyCode::Backspace, modifiers(param(1))),
+                ch => char_key(ch, modifiers(param(1))),
+            }),
+        final_byte => cursor_key(final_byte, modifiers(param(1))),
+    };
+
+    Parsed::Complete(event, len)
+}
+
+/// Keys sent as `CSI number ~`, the paste start marker is reported as an empty paste.
+fn tilde_key(number: Option<u16>, modifiers: KeyModifiers) -> Option<CrossEvent> {
+    let code = match number? {
+        1 | 7 => KeyCode::Home,
+        2 => KeyCode::Insert,
+        3 => KeyCode::Delete,
+        4 | 8 => KeyCode::End,
+        5 => KeyCode::PageUp,
+        6 => KeyCode::PageDown,
+        n @ 11..=15 => KeyCode::F(u8::try_from(n - 10).ok()?),
+        n @ 17..=21 => KeyCode::F(u8::try_from(n - 11).ok()?),
+        n @ 23..=26 => KeyCode::F(u8::try_from(n - 12).ok()?),
+        n @ (28 | 29) => KeyCode::F(u8::try_from(n - 13).ok()?),
+        n @ 31..=34 => KeyCode::F(u8::try_from(n - 14).ok()?),
+        200 => return Some(CrossEvent::Paste(String::new())),
+        _ => return None,
+    };
+
+    Some(key(code, modifiers))
+}
+
+/// Parse the body of an SGR encoded mouse report: `CSI < button ; column ; row (M|m)`.
+fn parse_sgr_mouse(params: &[u8], final_byte: u8) -> Option<CrossEvent> {
+    let mut values = std::str::from_utf8(params)
+        .ok()?
+        .split(';')
+        .map(|value| value.parse::<u16>().ok());
+
+    let code = values.next()??;
+    let column = values.next()??.saturating_sub(1);
+    let row = values.next()??.saturating_sub(1);
+
+    let mut modifiers = KeyModifiers::NONE;
+    if code & 4 != 0 {
+        modifiers |= KeyModifiers::SHIFT;
+    }
+    if code & 8 != 0 {
+        modifiers |= KeyModifiers::ALT;
+    }
+    if code & 16 != 0 {
+        modifiers |= KeyModifiers::CONTROL;
+    }
+
+    let button = match code & 3 {
+        0 => MouseButton::Left,
+        1 => MouseButton::Middle,
+        _ => MouseButton::Right,
+    };
+
+    let kind = if code & 64 != 0 {
+        match code & 3 {
+            0 => MouseEventKind::ScrollUp,
+            1 => MouseEventKind::ScrollDown,
+            2 => MouseEventKind::ScrollLeft,
+            _ => MouseEventKind::ScrollRight,
+        }
+    } else if code & 32 != 0 {
+        if code & 3 == 3 {
+            MouseEventKind::Moved
+        } else {
+            MouseEventKind::Drag(button)
+        }
+    } else if final_byte == b'm' {
+        MouseEventKind::Up(button)
+    } else {
+        MouseEventKind::Down(button)
+    };
+
+    Some(CrossEvent::Mouse(MouseEvent {
+        kind,
+        column,
+        row,
+        modifiers,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(text: &str) -> impl Iterator<Item = CrossEvent> + '_ {
+        text.chars().map(|ch| char_key(ch, KeyModifiers::NONE))
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> CrossEvent {
+        CrossEvent::Mouse(MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+
+    /// Checks the bytes parse into the expected events when they arrive all at once, split in two
+    /// at every byte boundary, and one byte at a time.
+    fn assert_parses(bytes: &[u8], expected: &[CrossEvent]) {
+        for split in 0..=bytes.len() {
+            let mut parser = InputParser::default();
+            let mut events = parser.advance(&bytes[..split]);
+            events.extend(parser.advance(&bytes[split..]));
+
+            assert_eq!(events, expected, "split after {split} bytes");
+            assert!(!parser.is_pending(), "split after {split} bytes");
+        }
+
+        let mut parser = InputParser::default();
+        let events: Vec<CrossEvent> = bytes
+            .iter()
+            .flat_map(|byte| parser.advance(std::slice::from_ref(byte)))
+            .collect();
+        assert_eq!(events, expected, "one byte at a time");
+    }
+
+    #[test]
+    fn parses_csi_keys() {
+        assert_parses(
+            b"\x1b[A\x1b[1;5C\x1b[3~\x1b[5;2~\x1b[Z",
+            &[
+                key(KeyCode::Up, KeyModifiers::NONE),
+                key(KeyCode::Right, KeyModifiers::CONTROL),
+                key(KeyCode::Delete, KeyModifiers::NONE),
+                key(KeyCode::PageUp, KeyModifiers::SHIFT),
+                key(KeyCode::BackTab, KeyModifiers::SHIFT),
+            ],
+        );
+    }
+
+    #[test]
+    fn parses_ss3_keys() {
+        assert_parses(
+            b"\x1bOP\x1bOH\x1bOB",
+            &[
+                key(KeyCode::F(1), KeyModifiers::NONE),
+                key(KeyCode::Home, KeyModifiers::NONE),
+                key(KeyCode::Down, KeyModifiers::NONE),
+            ],
+        );
+    }
+
+    #[test]
+    fn parses_sgr_mouse_reports() {
+        assert_parses(
+            b"\x1b[<0;5;3M\x1b[<0;5;3m\x1b[<65;12;24M\x1b[<35;1;1M",
+            &[
+                mouse(MouseEventKind::Down(MouseButton::Left), 4, 2),
+                mouse(MouseEventKind::Up(MouseButton::Left), 4, 2),
+                mouse(MouseEventKind::ScrollDown, 11, 23),
+                mouse(MouseEventKind::Moved, 0, 0),
+            ],
+        );
+    }
+
+    #[test]
+    fn parses_bracketed_paste_split_across_reads() {
+        let mut expected = vec![CrossEvent::Paste("hello\r\n\x1b[Aworld".to_string())];
+        expected.extend(chars("x"));
+
+        assert_parses(b"\x1b[200~hello\r\n\x1b[Aworld\x1b[201~x", &expected);
+    }
+
+    #[test]
+    fn waits_for_the_end_of_a_paste() {
+        let mut parser = InputParser::default();
+
+        assert_eq!(parser.advance(b"\x1b[200~partial\x1b[20"), Vec::new());
+        assert!(!parser.is_pending());
+        assert_eq!(parser.flush(), Vec::new());
+        assert_eq!(
+            parser.advance(b"1~"),
+            [CrossEvent::Paste("partial".to_string())]
+        );
+    }
+
+    #[test]
+    fn parses_utf8_split_across_reads() {
+        assert_parses("aé日🎉".as_bytes(), &chars("aé日🎉").collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn parses_kitty_keys() {
+        assert_parses(
+            b"\x1b[27u\x1b[106;3u\x1b[13u\x1b[97;5u\x1b[65;2u",
+            &[
+                key(KeyCode::Esc, KeyModifiers::NONE),
+                key(KeyCode::Char('j'), KeyModifiers::ALT),
+                key(KeyCode::Enter, KeyModifiers::NONE),
+                key(KeyCode::Char('a'), KeyModifiers::CONTROL),
+                key(KeyCode::Char('A'), KeyModifiers::SHIFT),
+            ],
+        );
+    }
+
+    #[test]
+    fn parses_keys_pressed_with_alt() {
+        assert_parses(
+            b"\x1bj\x1b\x1b\x1b\x01",
+            &[
+                key(KeyCode::Char('j'), KeyModifiers::ALT),
+                key(KeyCode::Esc, KeyModifiers::ALT),
+                key(
+                    KeyCode::Char('a'),
+                    KeyModifiers::ALT | KeyModifiers::CONTROL,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn flushes_a_lone_escape() {
+        let mut parser = InputParser::default();
+
+        assert_eq!(parser.advance(b"\x1b"), Vec::new());
+        assert!(parser.is_pending());
+        assert_eq!(parser.flush(), [key(KeyCode::Esc, KeyModifiers::NONE)]);
+        assert!(!parser.is_pending());
+    }
+
+    #[test]
+    fn flushes_an_unfinished_sequence_as_escape_and_its_keys() {
+        let mut parser = InputParser::default();
+
+        assert_eq!(parser.advance(b"\x1b[1;5"), Vec::new());
+
+        let mut expected = vec![key(KeyCode::Esc, KeyModifiers::NONE)];
+        expected.extend(chars("[1;5"));
+        assert_eq!(parser.flush(), expected);
+        assert!(!parser.is_pending());
+    }
+
+    #[test]
+    fn flushes_a_truncated_character_as_nothing() {
+        let mut parser = InputParser::default();
+
+        assert_eq!(parser.advance(&[0xe6, 0x97]), Vec::new());
+        assert_eq!(parser.flush(), Vec::new());
+        assert_eq!(parser.advance(b"a"), Vec::from_iter(chars("a")));
+    }
+
+    #[test]
+    fn rejoins_a_split_sequence() {
+        let mut events = vec![key(KeyCode::Esc, KeyModifiers::NONE)];
+        events.extend(chars("[Ax"));
+
+        let mut expected = vec![key(KeyCode::Up, KeyModifiers::NONE)];
+        expected.extend(chars("x"));
+        assert_eq!(rejoin_escape(&events, false), expected);
+    }
+
+    #[test]
+    fn rejoins_a_key_pressed_with_alt_on_ambiguous_terminals() {
+        let mut events = vec![key(KeyCode::Esc, KeyModifiers::NONE)];
+        events.extend(chars("jk"));
+
+        let mut expected = vec![key(KeyCode::Char('j'), KeyModifiers::ALT)];
+        expected.extend(chars("k"));
+        assert_eq!(rejoin_escape(&events, false), expected);
+    }
+
+    #[test]
+    fn keeps_escape_followed_by_keys_on_disambiguating_terminals() {
+        let mut events = vec![key(KeyCode::Esc, KeyModifiers::NONE)];
+        events.extend(chars("jk"));
+
+        assert_eq!(rejoin_escape(&events, true), events);
+    }
+
+    #[test]
+    fn keeps_escape_followed_by_other_events() {
+        let events = vec![
+            key(KeyCode::Esc, KeyModifiers::NONE),
+            key(KeyCode::Enter, KeyModifiers::NONE),
+        ];
+        assert_eq!(rejoin_escape(&events, false), events);
+    }
+}
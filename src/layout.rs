@@ -0,0 +1,385 @@
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::system::{Query, ResMut, Resource, SystemState};
+use bevy::ecs::world::World;
+use bevy::hierarchy::{Children, Parent};
+use bevy::utils::HashMap;
+use ratatui::layout::{Constraint, Direction, Layout, Margin, Rect};
+
+use crate::session::{unclaimed_roots, TuiSession};
+use crate::widgets::TuiBlock;
+use crate::BevyTerminal;
+
+/// The screen area computed for an entity participating in the TUI layout. Entities with this
+/// component and no [`Parent`] are considered layout roots and are given the entire drawable area
+/// of the terminal they are shown on, see [`TuiSession`] for showing roots on separate terminals.
+/// Children of an entity with a [`TuiLayout`] receive their area from their parent's constraints,
+/// while children of a node without one simply inherit their parent's area.
+/// When the parent also has a [`TuiBlock`] its children are placed inside of the block's borders.
+///
+/// The area is recomputed every frame during [`PostUpdate`](bevy::app::PostUpdate) and should be
+/// treated as read-only by applications.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+///
+/// let node = TuiNode::default();
+/// assert_eq!(node.area(), ratatui::layout::Rect::default());
+/// ```
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+pub struct TuiNode {
+    area: Rect,
+}
+
+impl TuiNode {
+    /// The area of the terminal this node was assigned during the last layout pass.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let area = TuiNode::default().area();
+    /// assert_eq!(area.width, 0);
+    /// ```
+    #[must_use]
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+}
+
+/// The areas the nodes shown on a terminal were assigned during the last layout pass. The resource
+/// holds the areas on the terminal the process is attached to, while each [`TuiSession`] keeps the
+/// areas laid out for its own terminal with this as a component on the session's entity. Unlike
+/// [`TuiNode::area`] these stay correct when a root is shared between terminals of different sizes.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn log_session_areas(
+///     sessions: Query<(Entity, &LayoutAreas)>,
+///     nodes: Query<Entity, With<TuiNode>>,
+/// ) {
+///     for (session, areas) in &sessions {
+///         for node in &nodes {
+///             if let Some(area) = areas.area(node) {
+///                 println!("{node:?} is at {area:?} on {session:?}");
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Clone, Component, Debug, Default, PartialEq, Eq, Resource)]
+pub struct LayoutAreas(HashMap<Entity, Rect>);
+
+impl LayoutAreas {
+    /// The area the provided node was assigned on this terminal, if it is shown on it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_tui::prelude::*;
+    ///
+    /// assert!(LayoutAreas::default().area(Entity::from_raw(1)).is_none());
+    /// ```
+    #[must_use]
+    pub fn area(&self, node: Entity) -> Option<Rect> {
+        self.0.get(&node).copied()
+    }
+
+    /// Replaces the recorded areas, leaving change detection alone when nothing has moved.
+    fn update(&mut self, areas: Self) {
+        if *self != areas {
+            *self = areas;
+        }
+    }
+}
+
+/// Describes how the area of an entity's [`TuiNode`] should be divided amongst its children. This
+/// mirrors the options available on a [`ratatui::layout::Layout`] with each constraint being
+/// assigned to the children in the order they appear in [`Children`]. Any children in excess of
+/// the number of constraints are given an empty area.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::layout::Constraint;
+///
+/// fn spawn_ui(mut commands: Commands) {
+///     commands
+///         .spawn((
+///             TuiNode::default(),
+///             TuiLayout::vertical([Constraint::Length(3), Constraint::Min(0)]).margin(1),
+///         ))
+///         .with_children(|parent| {
+///             parent.spawn(TuiNode::default());
+///             parent.spawn(TuiNode::default());
+///         });
+/// }
+/// ```
+#[derive(Clone, Component, Debug, Default, PartialEq, Eq)]
+pub struct TuiLayout {
+    direction: Direction,
+    constraints: Vec<Constraint>,
+    margin: Margin,
+    spacing: u16,
+}
+
+impl TuiLayout {
+    /// Create a new layout splitting the area along the provided direction.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::layout::{Constraint, Direction};
+    ///
+    /// TuiLayout::new(Direction::Horizontal, [Constraint::Percentage(50); 2]);
+    /// ```
+    pub fn new<I>(direction: Direction, constraints: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Constraint>,
+    {
+        Self {
+            direction,
+            constraints: constraints.into_iter().map(Into::into).collect(),
+            margin: Margin::new(0, 0),
+            spacing: 0,
+        }
+    }
+
+    /// Create a new layout that places its children side by side.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::layout::Constraint;
+    ///
+    /// TuiLayout::horizontal([Constraint::Length(20), Constraint::Min(0)]);
+    /// ```
+    pub fn horizontal<I>(constraints: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Constraint>,
+    {
+        Self::new(Direction::Horizontal, constraints)
+    }
+
+    /// Create a new layout that stacks its children on top of each other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::layout::Constraint;
+    ///
+    /// TuiLayout::vertical([Constraint::Length(1), Constraint::Min(0)]);
+    /// ```
+    pub fn vertical<I>(constraints: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Constraint>,
+    {
+        Self::new(Direction::Vertical, constraints)
+    }
+
+    /// Sets both the horizontal and vertical margin left empty around the children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::layout::Constraint;
+    ///
+    /// TuiLayout::vertical([Constraint::Min(0)]).margin(1);
+    /// ```
+    #[must_use]
+    pub fn margin(mut self, margin: u16) -> Self {
+        self.margin = Margin::new(margin, margin);
+        self
+    }
+
+    /// Sets the margin left empty on the left and right side of the children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::layout::Constraint;
+    ///
+    /// TuiLayout::vertical([Constraint::Min(0)]).horizontal_margin(2);
+    /// ```
+    #[must_use]
+    pub fn horizontal_margin(mut self, margin: u16) -> Self {
+        self.margin.horizontal = margin;
+        self
+    }
+
+    /// Sets the margin left empty above and below the children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::layout::Constraint;
+    ///
+    /// TuiLayout::vertical([Constraint::Min(0)]).vertical_margin(1);
+    /// ```
+    #[must_use]
+    pub fn vertical_margin(mut self, margin: u16) -> Self {
+        self.margin.vertical = margin;
+        self
+    }
+
+    /// Sets the number of cells left empty between each of the children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::layout::Constraint;
+    ///
+    /// TuiLayout::horizontal([Constraint::Percentage(50); 2]).spacing(1);
+    /// ```
+    #[must_use]
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    fn split(&self, area: Rect) -> std::rc::Rc<[Rect]> {
+        Layout::default()
+            .direction(self.direction)
+            .constraints(self.constraints.clone())
+            .horizontal_margin(self.margin.horizontal)
+            .vertical_margin(self.margin.vertical)
+            .spacing(self.spacing)
+            .split(area)
+    }
+}
+
+/// The parts of a node that determine how its area is divided amongst its children.
+type Containers = (
+    Option<&'static TuiLayout>,
+    Option<&'static TuiBlock>,
+    &'static Children,
+);
+
+fn assign_area(
+    entity: Entity,
+    area: Rect,
+    nodes: &mut Query<&mut TuiNode>,
+    containers: &Query<Containers>,
+    areas: &mut LayoutAreas,
+) {
+    let Ok(mut node) = nodes.get_mut(entity) else {
+        return;
+    };
+
+    // Avoid triggering change detection when nothing has actually moved
+    if node.area != area {
+        node.area = area;
+    }
+    areas.0.insert(entity, area);
+
+    let Ok((layout, block, children)) = containers.get(entity) else {
+        return;
+    };
+
+    let content_area = block.map_or(area, |block| block.0.inner(area));
+
+    // Only children that are themselves part of the layout take up one of the constraint slots
+    let child_nodes: Vec<Entity> = children
+        .iter()
+        .copied()
+        .filter(|child| nodes.contains(*child))
+        .collect();
+
+    match layout {
+        Some(layout) => {
+            let chunks = layout.split(content_area);
+
+            for (idx, child) in child_nodes.into_iter().enumerate() {
+                let child_area = chunks.get(idx).copied().unwrap_or_default();
+                assign_area(child, child_area, nodes, containers, areas);
+            }
+        }
+        None => {
+            for child in child_nodes {
+                assign_area(child, content_area, nodes, containers, areas);
+            }
+        }
+    }
+}
+
+/// Walks every layout root and recomputes the areas of all of the [`TuiNode`] entities beneath
+/// them from the current size of the terminal they are shown on.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn layout_system(
+    terminal: Option<ResMut<BevyTerminal>>,
+    local_areas: Option<ResMut<LayoutAreas>>,
+    mut sessions: Query<(&TuiSession, &mut BevyTerminal, Option<&mut LayoutAreas>)>,
+    roots: Query<Entity, (With<TuiNode>, Without<Parent>)>,
+    mut nodes: Query<&mut TuiNode>,
+    containers: Query<Containers>,
+) {
+    let all_roots: Vec<Entity> = roots.iter().collect();
+    let unclaimed = unclaimed_roots(&all_roots, sessions.iter().map(|(session, ..)| session));
+
+    if let Some(mut terminal) = terminal {
+        // Pick up any size changes before the next draw does so we don't lag a frame behind
+        if terminal.0.autoresize().is_ok() {
+            let area = terminal.0.get_frame().size();
+            let mut areas = LayoutAreas::default();
+
+            for root in &unclaimed {
+                assign_area(*root, area, &mut nodes, &containers, &mut areas);
+            }
+
+            if let Some(mut local_areas) = local_areas {
+                local_areas.update(areas);
+            }
+        }
+    }
+
+    for (session, mut terminal, session_areas) in &mut sessions {
+        let area = terminal.0.get_frame().size();
+        let mut areas = LayoutAreas::default();
+
+        for root in session.roots(&unclaimed) {
+            assign_area(root, area, &mut nodes, &containers, &mut areas);
+        }
+
+        if let Some(mut session_areas) = session_areas {
+            session_areas.update(areas);
+        }
+    }
+}
+
+/// Recomputes the areas beneath the provided roots to fit the provided area, used to lay out a
+/// root again for each session that shows it.
+pub(crate) fn layout_roots(world: &mut World, roots: &[Entity], area: Rect) {
+    let mut state: SystemState<(Query<&mut TuiNode>, Query<Containers>)> = SystemState::new(world);
+    let (mut nodes, containers) = state.get_mut(world);
+
+    for root in roots {
+        assign_area(
+            *root,
+            area,
+            &mut nodes,
+            &containers,
+            &mut LayoutAreas::default(),
+        );
+    }
+}
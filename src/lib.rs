@@ -1,4 +1,4 @@
-#![feature(rustdoc_missing_doc_code_examples)]
+#![cfg_attr(doc, feature(rustdoc_missing_doc_code_examples))]
 
 //! A plugin for making interactive Bevy applications with a TUI instead of a graphical interface.
 //!
@@ -21,18 +21,49 @@
 //! }
 //! ```
 
-use bevy::app::{App, Plugin, PluginGroup, PluginGroupBuilder, PreUpdate, Startup};
+use bevy::app::{
+    App, Last, Plugin, PluginGroup, PluginGroupBuilder, PostUpdate, PreStartup, PreUpdate, Startup,
+};
 use bevy::core::{TaskPoolPlugin, TypeRegistrationPlugin};
-use bevy::ecs::system::{Commands, Resource};
+use bevy::ecs::component::Component;
+use bevy::ecs::schedule::common_conditions::resource_exists;
+use bevy::ecs::schedule::SystemSet;
+use bevy::ecs::system::{Commands, Res, Resource};
+use bevy::hierarchy::HierarchyPlugin;
 use bevy::input::keyboard::KeyCode;
 use bevy::input::mouse::{MouseButton, MouseMotion};
 use bevy::input::{ButtonInput, ButtonState, InputSystem};
-use bevy::prelude::{Event, IntoSystemConfigs};
+use bevy::prelude::IntoSystemConfigs;
 use bevy::time::TimePlugin;
 
+mod action;
+mod color;
+mod events;
+mod focus;
 mod input;
+mod layout;
+mod output;
+#[cfg(feature = "pty")]
+mod pty;
+mod quit;
+mod recording;
+mod render;
+#[cfg(feature = "replay")]
+mod replay;
 mod scheduler;
+mod session;
+mod shell;
+#[cfg(unix)]
+mod signals;
+mod snapshot;
+#[cfg(feature = "ssh")]
+mod ssh;
+#[cfg(unix)]
+mod suspend;
+mod telnet;
 mod terminal_helpers;
+mod theme;
+mod widgets;
 
 /// A quick helper module to allow including all the commonly used and exposed public portions of
 /// this library. It can be used in your project like so:
@@ -41,17 +72,72 @@ mod terminal_helpers;
 /// use bevy_tui::prelude::*;
 /// ```
 pub mod prelude {
-    pub use crate::input::{MouseState, WindowResized};
-    pub use crate::terminal_helpers::{initialize_terminal, teardown_terminal};
-    pub use crate::{MinimalTuiPlugins, TuiPlugin};
+    #[cfg(feature = "config")]
+    pub use crate::action::ActionMapError;
+    pub use crate::action::{
+        ActionMap, ActionPlugin, ActionState, InputBinding, InvalidBinding, KeyChord,
+        KeySequenceSettings, PendingKeys, TuiAction,
+    };
+    pub use crate::color::{ColorFilter, ColorFilterBackend, ColorSupport};
+    pub use crate::events::{
+        FocusChanged, FocusedInput, KeySequenceMatched, Selected, SessionEnded, SessionEvent,
+        SessionStarted, ShellOutFinished, TextInputSubmitted,
+    };
+    #[cfg(unix)]
+    pub use crate::events::{Resumed, Suspended};
+    pub use crate::focus::{Focusable, FocusedEntity};
+    pub use crate::input::{
+        KeyboardInput, MouseInput, MouseState, TuiInputSettings, WindowResized,
+    };
+    pub use crate::layout::{LayoutAreas, TuiLayout, TuiNode};
+    pub use crate::output::{TerminalOutput, TuiOutput};
+    #[cfg(feature = "pty")]
+    pub use crate::pty::PtyTerminal;
+    pub use crate::quit::TuiQuitSettings;
+    pub use crate::render::{TuiAppExt, TuiRenderContext, TuiRenderSettings, TuiStyle, TuiWidget};
+    #[cfg(feature = "replay")]
+    pub use crate::replay::TuiInputRecording;
+    pub use crate::session::{SessionBundle, SessionInput, TuiSession};
+    pub use crate::shell::ShellOut;
+    pub use crate::snapshot::{
+        assert_snapshot, buffer_snapshot, render_buffer, styled_buffer_snapshot,
+    };
+    #[cfg(feature = "ssh")]
+    pub use crate::ssh::{SshPlugin, SshServer, SshSettings};
+    #[cfg(unix)]
+    pub use crate::suspend::TuiSuspendSettings;
+    pub use crate::telnet::{TelnetPlugin, TelnetServer, TelnetSettings};
+    pub use crate::terminal_helpers::{
+        detect_color_support, initialize_inline_terminal, initialize_terminal,
+        initialize_terminal_with, teardown_inline_terminal, teardown_terminal,
+        teardown_terminal_with, TuiTerminalSettings,
+    };
+    #[cfg(feature = "assets")]
+    pub use crate::theme::TuiThemeHandle;
+    pub use crate::theme::{StyleRole, ThemeStyle, Themed, TuiTheme};
+    pub use crate::widgets::{
+        BlockBundle, GaugeBundle, ListBundle, ParagraphBundle, SparklineBundle, TableBundle,
+        TextInput, TextInputBundle, TuiBlock, TuiGauge, TuiList, TuiParagraph, TuiSparkline,
+        TuiTable,
+    };
+    pub use crate::{MinimalTuiPlugins, TuiPlugin, TuiSystem};
 }
 
+use crate::color::ColorSupport;
+use crate::events::{FocusChanged, FocusedInput, SessionEnded, SessionEvent, SessionStarted};
+use crate::focus::FocusedEntity;
+pub use crate::input::RawConsoleEvent;
+use crate::layout::LayoutAreas;
+
 use crate::input::{KeyboardInput, MouseInput};
-use crate::scheduler::{tui_schedule_runner, TuiPersistentState};
-use crate::terminal_helpers::create_terminal;
+use crate::render::{TuiAppExt, TuiRenderSettings};
+use crate::scheduler::{tui_schedule_runner, RemoteInput, TuiPersistentState};
+use crate::terminal_helpers::{create_terminal, detect_color_support, TuiTerminalSettings};
 
 /// The Bevy resource that gets exposed to perform frame render operations. This is a thin wrapper
-/// around a [`ratatui::Terminal`] with no specific backend specified.
+/// around a [`ratatui::Terminal`] with no specific backend specified. It is also used as a
+/// component on [`TuiSession`](crate::prelude::TuiSession) entities, each of which draws to its
+/// own terminal.
 ///
 /// # Examples
 ///
@@ -67,17 +153,22 @@ use crate::terminal_helpers::create_terminal;
 ///
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
-#[derive(Resource)]
+#[derive(Component, Resource)]
 pub struct Terminal<T: ratatui::backend::Backend>(pub ratatui::Terminal<T>);
 
-/// A short-hand type for a crossterm backed TUI terminal connected to STDOUT. This will likely go
-/// away in a more finalized version.
-pub type BevyTerminal = Terminal<ratatui::backend::CrosstermBackend<std::io::Stdout>>;
+/// A short-hand type for a crossterm backed TUI terminal connected to the output chosen in
+/// [`TuiTerminalSettings`]. The colors of everything drawn to it are filtered according to the
+/// [`TuiRenderSettings`]. This will likely go away in a more finalized version.
+pub type BevyTerminal =
+    Terminal<color::ColorFilterBackend<ratatui::backend::CrosstermBackend<output::TerminalOutput>>>;
 
 /// A helper plugin group that sets up the bare minimum plugins for use in a Bevy plugin project.
 /// This should be used in place of the Bevy `MinimalPlugins` plugin group as that includes a
 /// conflicting `InputPlugin`.
 ///
+/// When the `assets` feature is enabled this also includes the Bevy `AssetPlugin`, which must be
+/// added before the [`TuiPlugin`] if you're assembling the plugins yourself.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -92,11 +183,16 @@ pub struct MinimalTuiPlugins;
 
 impl PluginGroup for MinimalTuiPlugins {
     fn build(self) -> PluginGroupBuilder {
-        PluginGroupBuilder::start::<Self>()
+        let group = PluginGroupBuilder::start::<Self>()
             .add(TaskPoolPlugin::default())
             .add(TypeRegistrationPlugin)
             .add(TimePlugin)
-            .add(TuiPlugin)
+            .add(HierarchyPlugin);
+
+        #[cfg(feature = "assets")]
+        let group = group.add(bevy::asset::AssetPlugin::default());
+
+        group.add(TuiPlugin)
     }
 }
 
@@ -125,7 +221,13 @@ impl Plugin for TuiPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(TuiPersistentState::default())
             .set_runner(tui_schedule_runner)
+            .init_resource::<TuiTerminalSettings>()
+            .init_resource::<input::TuiInputSettings>()
             .add_systems(Startup, terminal_setup)
+            .add_systems(Last, terminal_helpers::inline_exit_system)
+            .add_event::<events::ShellOutFinished>()
+            .init_resource::<quit::TuiQuitSettings>()
+            .add_systems(PreUpdate, quit::quit_system)
             .add_event::<KeyboardInput>()
             .add_event::<RawConsoleEvent>()
             .init_resource::<ButtonInput<KeyCode>>()
@@ -134,42 +236,172 @@ impl Plugin for TuiPlugin {
             .add_event::<MouseMotion>()
             .init_resource::<ButtonInput<MouseButton>>()
             .init_resource::<input::MouseState>()
-            .add_systems(PreUpdate, input::mouse_input_system.in_set(InputSystem));
+            .add_systems(PreUpdate, input::mouse_input_system.in_set(InputSystem))
+            .init_resource::<TuiRenderSettings>()
+            .init_resource::<FocusedEntity>()
+            .init_resource::<LayoutAreas>()
+            .init_resource::<focus::KeyboardCapture>()
+            .add_event::<FocusChanged>()
+            .add_event::<FocusedInput>()
+            .add_event::<SessionEvent>()
+            .add_event::<SessionStarted>()
+            .add_event::<SessionEnded>()
+            .add_systems(
+                PreUpdate,
+                (focus::focus_system, session::session_input_system)
+                    .in_set(TuiSystem::Focus)
+                    .before(InputSystem),
+            )
+            .add_event::<events::TextInputSubmitted>()
+            .add_event::<events::Selected>()
+            .add_systems(
+                PreUpdate,
+                (
+                    widgets::text_input_system,
+                    widgets::scroll_system::<widgets::TuiList>,
+                    widgets::scroll_system::<widgets::TuiTable>,
+                )
+                    .after(TuiSystem::Focus),
+            )
+            .add_systems(PostUpdate, layout::layout_system.in_set(TuiSystem::Layout))
+            .add_systems(
+                PostUpdate,
+                render::render_system
+                    .in_set(TuiSystem::Render)
+                    .after(TuiSystem::Layout),
+            );
+
+        // The theme is picked once the app runs so render settings inserted after the plugin count
+        app.add_systems(PreStartup, theme::theme_setup).add_systems(
+            PostUpdate,
+            theme::default_theme_system
+                .run_if(resource_exists::<theme::DefaultTheme>)
+                .before(TuiSystem::Render),
+        );
+
+        // The handlers are in place before any startup system can hand the terminal to a program
+        #[cfg(unix)]
+        app.add_systems(PreStartup, quit::quit_signal_setup)
+            .add_plugins(suspend::SuspendPlugin);
+
+        // Respect a color support level provided before the plugin was added
+        if !app.world.contains_resource::<ColorSupport>() {
+            app.insert_resource(detect_color_support());
+        }
+
+        #[cfg(feature = "assets")]
+        {
+            use bevy::asset::AssetApp;
 
-        // Register the common type
-        app.register_type::<ButtonState>();
+            app.init_asset::<theme::TuiTheme>()
+                .init_asset_loader::<theme::TuiThemeLoader>()
+                .add_systems(
+                    PostUpdate,
+                    theme::theme_asset_system.before(TuiSystem::Render),
+                );
+        }
 
-        // Register keyboard types
-        app.register_type::<KeyCode>();
+        // Register the built-in widgets
+        app.register_tui_widget::<widgets::TuiParagraph>()
+            .register_tui_widget::<widgets::TuiList>()
+            .register_tui_widget::<widgets::TuiTable>()
+            .register_tui_widget::<widgets::TuiGauge>()
+            .register_tui_widget::<widgets::TuiSparkline>()
+            .register_tui_widget::<widgets::TextInput>();
 
-        // Register the mouse types
-        app.register_type::<MouseButton>()
-            .register_type::<MouseMotion>()
-            .register_type::<input::MouseState>();
+        register_types(app);
     }
 }
 
-/// A published version of the raw Crossterm events received. This is one of the reasons why this
-/// library is currently tied to this particular TUI backend for now. If you're going to be using
-/// text input in your UI, these events are likely what you want over the `Input<KeyCode>` events
-/// as letter casing and non-US/ASCII keyboard characters are preserved.
+/// Register the input and event types for reflection.
+fn register_types(app: &mut App) {
+    // Register the common types
+    app.register_type::<ButtonState>()
+        .register_type::<action::InputBinding>();
+
+    // Register keyboard types
+    app.register_type::<KeyCode>()
+        .register_type::<KeyboardInput>();
+
+    // Register the mouse types
+    app.register_type::<MouseButton>()
+        .register_type::<MouseMotion>()
+        .register_type::<MouseInput>()
+        .register_type::<input::MouseState>()
+        .register_type::<input::WindowResized>();
+
+    // Register the events
+    app.register_type::<RawConsoleEvent>()
+        .register_type::<FocusChanged>()
+        .register_type::<FocusedInput>()
+        .register_type::<SessionEvent>()
+        .register_type::<SessionStarted>()
+        .register_type::<SessionEnded>()
+        .register_type::<events::TextInputSubmitted>()
+        .register_type::<events::Selected>()
+        .register_type::<events::ShellOutFinished>();
+}
+
+/// Labels for the systems this library adds to the Bevy schedules, allowing application systems
+/// to be ordered relative to them.
 ///
 /// # Examples
 ///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn inspect_layout(nodes: Query<&TuiNode>) {
+///     // ...
+/// }
+///
+/// App::new()
+///     .add_plugins(MinimalTuiPlugins)
+///     .add_systems(PostUpdate, inspect_layout.after(TuiSystem::Layout))
+///     .run();
 /// ```
-/// # use bevy_tui::RawConsoleEvent;
-/// RawConsoleEvent(crossterm::event::Event::FocusGained);
-/// ```
-#[derive(Debug, Clone, PartialEq, Eq, Event)]
-pub struct RawConsoleEvent(pub crossterm::event::Event);
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, SystemSet)]
+pub enum TuiSystem {
+    /// Moves keyboard focus and forwards input to the focused entity. Runs during [`PreUpdate`]
+    /// before the global keyboard and mouse input is updated.
+    Focus,
+
+    /// Computes the area of every [`TuiNode`](crate::layout::TuiNode) from the entity hierarchy
+    /// and the current size of the terminal. Runs during [`PostUpdate`].
+    Layout,
+
+    /// Draws every node with a registered [`TuiWidget`](crate::render::TuiWidget) into the
+    /// terminal. Runs during [`PostUpdate`] after [`TuiSystem::Layout`].
+    Render,
+}
 
 /// Create and register a [`BevyTerminal`] inside the Bevy system for future use by a Terminal UI.
+/// A terminal that has already been inserted, such as one drawing to a custom writer, is kept. No
+/// terminal is created when input is being received from elsewhere, such as when serving sessions
+/// over the network, as the one the process is attached to isn't being used.
 ///
 /// # Panics
 ///
 /// This method will panic if the underlying [`create_terminal`] function fails to create a
-/// terminal likely due to STDOUT being unavailable, or can not be written to.
-fn terminal_setup(mut commands: Commands) {
-    let term = create_terminal().expect("terminal setup to succeed");
+/// terminal likely due to the output being unavailable, or can not be written to.
+#[allow(clippy::needless_pass_by_value)]
+fn terminal_setup(
+    mut commands: Commands,
+    settings: Res<TuiTerminalSettings>,
+    render_settings: Res<TuiRenderSettings>,
+    color_support: Res<ColorSupport>,
+    existing: Option<Res<BevyTerminal>>,
+    remote: Option<Res<RemoteInput>>,
+) {
+    if existing.is_some() || remote.is_some() {
+        return;
+    }
+
+    let mut term = create_terminal(&settings).expect("terminal setup to succeed");
+
+    // Frames drawn by hand before the render system first runs need the colors filtered as well
+    let filter = render_settings.color_filter(*color_support);
+    term.0.backend_mut().set_filter(filter);
+
     commands.insert_resource(term);
 }
@@ -0,0 +1,192 @@
+use std::fmt::{self, Debug, Formatter};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Stderr, Stdout, Write};
+use std::process::Stdio;
+
+/// Where the [`BevyTerminal`](crate::BevyTerminal) created at startup should draw.
+///
+/// Drawing somewhere other than STDOUT leaves it free for the application's own output, allowing
+/// the application to be used in a pipeline. Keyboard and mouse input is always read from the
+/// controlling terminal regardless of this setting.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let mut output = TerminalOutput::open(TuiOutput::Stderr)?;
+///     initialize_terminal_with(&mut output)?;
+///
+///     App::new()
+///         .add_plugins(MinimalTuiPlugins)
+///         .insert_resource(TuiTerminalSettings {
+///             output: TuiOutput::Stderr,
+///             ..default()
+///         })
+///         .run();
+///
+///     teardown_terminal_with(&mut output)?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum TuiOutput {
+    /// The standard output of the process.
+    #[default]
+    Stdout,
+
+    /// The standard error of the process.
+    Stderr,
+
+    /// The controlling terminal of the process (`/dev/tty`), which remains available even when
+    /// both STDOUT and STDERR have been redirected.
+    Tty,
+}
+
+/// A writer for any of the places a terminal UI can be drawn to. This is the output used by the
+/// [`BevyTerminal`](crate::BevyTerminal) backend.
+///
+/// # Examples
+///
+/// Any writer can be used by creating the terminal yourself, which will be used in place of the
+/// one the plugin would otherwise create:
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::backend::CrosstermBackend;
+///
+/// let output = TerminalOutput::writer(Vec::new());
+/// let terminal = ratatui::Terminal::new(CrosstermBackend::new(output))?;
+///
+/// App::new()
+///     .add_plugins(MinimalTuiPlugins)
+///     .insert_resource(bevy_tui::Terminal(terminal))
+///     .run();
+///
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub enum TerminalOutput {
+    /// Writes to the standard output of the process.
+    Stdout(Stdout),
+
+    /// Writes to the standard error of the process.
+    Stderr(Stderr),
+
+    /// Writes to the controlling terminal of the process.
+    Tty(File),
+
+    /// Writes to an arbitrary writer.
+    Writer(Box<dyn Write + Send + Sync>),
+}
+
+impl TerminalOutput {
+    /// Open one of the standard outputs.
+    ///
+    /// # Errors
+    ///
+    /// Opening [`TuiOutput::Tty`] will fail if the process doesn't have a controlling terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let output = TerminalOutput::open(TuiOutput::Stderr)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn open(target: TuiOutput) -> io::Result<Self> {
+        let output = match target {
+            TuiOutput::Stdout => Self::Stdout(io::stdout()),
+            TuiOutput::Stderr => Self::Stderr(io::stderr()),
+            TuiOutput::Tty => Self::Tty(open_tty()?),
+        };
+
+        Ok(output)
+    }
+
+    /// Wrap an arbitrary writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let output = TerminalOutput::writer(Vec::new());
+    /// ```
+    pub fn writer(writer: impl Write + Send + Sync + 'static) -> Self {
+        Self::Writer(Box::new(writer))
+    }
+}
+
+impl Debug for TerminalOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Stdout(_) => f.write_str("Stdout"),
+            Self::Stderr(_) => f.write_str("Stderr"),
+            Self::Tty(file) => f.debug_tuple("Tty").field(file).finish(),
+            Self::Writer(_) => f.write_str("Writer"),
+        }
+    }
+}
+
+impl Write for TerminalOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Self::Stdout(out) => out.write(buf),
+            Self::Stderr(out) => out.write(buf),
+            Self::Tty(out) => out.write(buf),
+            Self::Writer(out) => out.write(buf),
+        }
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        match self {
+            Self::Stdout(out) => out.write_all(buf),
+            Self::Stderr(out) => out.write_all(buf),
+            Self::Tty(out) => out.write_all(buf),
+            Self::Writer(out) => out.write_all(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Self::Stdout(out) => out.flush(),
+            Self::Stderr(out) => out.flush(),
+            Self::Tty(out) => out.flush(),
+            Self::Writer(out) => out.flush(),
+        }
+    }
+}
+
+/// Open the controlling terminal as the standard input, output and error of another program, for
+/// when those of the process itself may have been redirected.
+pub(crate) fn terminal_stdio() -> io::Result<[Stdio; 3]> {
+    let input = open_tty_input()?;
+    let output = open_tty()?;
+
+    Ok([input.into(), output.try_clone()?.into(), output.into()])
+}
+
+#[cfg(not(windows))]
+fn open_tty() -> io::Result<File> {
+    OpenOptions::new().read(true).write(true).open("/dev/tty")
+}
+
+#[cfg(windows)]
+fn open_tty() -> io::Result<File> {
+    OpenOptions::new().read(true).write(true).open("CONOUT$")
+}
+
+#[cfg(not(windows))]
+fn open_tty_input() -> io::Result<File> {
+    open_tty()
+}
+
+#[cfg(windows)]
+fn open_tty_input() -> io::Result<File> {
+    OpenOptions::new().read(true).write(true).open("CONIN$")
+}
@@ -0,0 +1,384 @@
+use std::io::{self, ErrorKind, Read, Write};
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc::{channel, Receiver};
+use std::time::{Duration, Instant};
+
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+
+/// A terminal UI application running in a child process attached to a pseudo-terminal. The child
+/// sees a real terminal, so everything from [`initialize_terminal`] to the input parsing of the
+/// app is exercised exactly as it would be with a person at the keyboard.
+///
+/// Keystrokes and other input are written to the terminal with [`PtyTerminal::send`], and
+/// everything the application draws is run through a VT parser, giving the resulting screen. This
+/// makes it suitable for end-to-end tests, or for embedding an application in another program.
+///
+/// The child process is killed when this is dropped.
+///
+/// [`initialize_terminal`]: crate::prelude::initialize_terminal
+///
+/// # Examples
+///
+/// Running an application on a pseudo-terminal and quitting it. The example runs itself a second
+/// time as the application, in the same way a test would run one of the project's binaries:
+///
+/// ```
+/// use std::process::Command;
+/// use std::time::Duration;
+///
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::Paragraph;
+///
+/// if std::env::var_os("PTY_EXAMPLE_APP").is_some() {
+///     initialize_terminal()?;
+///
+///     App::new()
+///         .add_plugins(MinimalTuiPlugins)
+///         .insert_resource(TuiQuitSettings {
+///             quit_keys: vec!["q".parse()?],
+///             ..default()
+///         })
+///         .add_systems(Startup, |mut commands: Commands| {
+///             commands.spawn(ParagraphBundle {
+///                 paragraph: TuiParagraph(Paragraph::new("Hello Bevy!")),
+///                 ..default()
+///             });
+///         })
+///         .run();
+///
+///     teardown_terminal()?;
+///     return Ok(());
+/// }
+///
+/// let mut command = Command::new(std::env::current_exe()?);
+/// command.env("PTY_EXAMPLE_APP", "1");
+///
+/// let mut pty = PtyTerminal::spawn(&command, 80, 24)?;
+/// assert!(pty.wait_for(Duration::from_secs(10), |screen| {
+///     screen.contents().contains("Hello Bevy!")
+/// }));
+///
+/// pty.send("q")?;
+/// assert_eq!(pty.wait_exit(Duration::from_secs(10))?, Some(0));
+///
+/// // The alternate screen was entered and left. The kitty keyboard mode isn't, as the
+/// // pseudo-terminal doesn't answer the query for whether it is supported
+/// let output = pty.output();
+/// for sequence in [&b"\x1b[?1049h"[..], b"\x1b[?1049l"] {
+///     assert!(output.windows(sequence.len()).any(|window| window == sequence));
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct PtyTerminal {
+    master: Box<dyn MasterPty + Send>,
+    input: Box<dyn Write + Send>,
+    output: Receiver<Vec<u8>>,
+    received: Vec<u8>,
+    parser: vt100::Parser,
+    child: Box<dyn Child + Send + Sync>,
+}
+
+impl PtyTerminal {
+    /// Allocate a pseudo-terminal of the provided size and run a command in it. The command's
+    /// standard input, output and error are all attached to the terminal, which also becomes its
+    /// controlling terminal.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a pseudo-terminal can't be allocated or the command can't be started.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let mut command = Command::new("printf");
+    /// command.arg("hello");
+    ///
+    /// let mut pty = PtyTerminal::spawn(&command, 20, 3)?;
+    /// assert!(pty.wait_for(Duration::from_secs(5), |screen| {
+    ///     screen.contents() == "hello"
+    /// }));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn spawn(command: &Command, width: u16, height: u16) -> io::Result<Self> {
+        let pair = native_pty_system()
+            .openpty(pty_size(width, height))
+            .map_err(pty_error)?;
+
+        let child = pair
+            .slave
+            .spawn_command(command_builder(command))
+            .map_err(pty_error)?;
+
+        // The child holds the only remaining handle on the terminal, so reading reaches the end
+        // once it exits
+        drop(pair.slave);
+
+        let mut reader = pair.master.try_clone_reader().map_err(pty_error)?;
+        let input = pair.master.take_writer().map_err(pty_error)?;
+
+        let (sender, output) = channel();
+        std::thread::spawn(move || {
+            let mut buffer = [0; 4096];
+            while let Ok(len @ 1..) = reader.read(&mut buffer) {
+                if sender.send(buffer[..len].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            master: pair.master,
+            input,
+            output,
+            received: Vec::new(),
+            parser: vt100::Parser::new(height, width, 0),
+            child,
+        })
+    }
+
+    /// Write input to the terminal, as if it had been typed. Special keys are sent as the escape
+    /// sequences a terminal would produce for them, such as `"\x1b[A"` for the up arrow.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the terminal can no longer be written to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let mut command = Command::new("head");
+    /// command.args(["-n", "1"]);
+    ///
+    /// let mut pty = PtyTerminal::spawn(&command, 20, 3)?;
+    /// pty.send("ping\r")?;
+    /// assert_eq!(pty.wait_exit(Duration::from_secs(5))?, Some(0));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn send(&mut self, input: impl AsRef<[u8]>) -> io::Result<()> {
+        self.input.write_all(input.as_ref())?;
+        self.input.flush()
+    }
+
+    /// Change the size of the terminal, notifying the application the same way resizing a window
+    /// would.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the size of the terminal can't be changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::process::Command;
+    ///
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let mut pty = PtyTerminal::spawn(&Command::new("true"), 80, 24)?;
+    /// pty.resize(100, 30)?;
+    /// assert_eq!(pty.screen().size(), (30, 100));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn resize(&mut self, width: u16, height: u16) -> io::Result<()> {
+        self.master
+            .resize(pty_size(width, height))
+            .map_err(pty_error)?;
+        self.parser.set_size(height, width);
+
+        Ok(())
+    }
+
+    /// The screen as drawn by everything the application has output so far.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::process::Command;
+    ///
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let mut pty = PtyTerminal::spawn(&Command::new("true"), 80, 24)?;
+    /// let (rows, columns) = pty.screen().size();
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn screen(&mut self) -> &vt100::Screen {
+        self.receive(Duration::ZERO);
+        self.parser.screen()
+    }
+
+    /// Every byte the application has output so far, for checking the escape sequences it sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let mut command = Command::new("printf");
+    /// command.arg(r"\033[?1049h");
+    ///
+    /// let mut pty = PtyTerminal::spawn(&command, 80, 24)?;
+    /// pty.wait_exit(Duration::from_secs(5))?;
+    /// assert!(pty.output().ends_with(b"\x1b[?1049h"));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn output(&mut self) -> &[u8] {
+        self.receive(Duration::ZERO);
+        &self.received
+    }
+
+    /// Wait until the screen satisfies a condition, returning whether it did before the timeout
+    /// was reached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let mut pty = PtyTerminal::spawn(&Command::new("true"), 80, 24)?;
+    /// let found = pty.wait_for(Duration::from_millis(100), |screen| {
+    ///     screen.contents().contains("never printed")
+    /// });
+    /// assert!(!found);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn wait_for(
+        &mut self,
+        timeout: Duration,
+        mut condition: impl FnMut(&vt100::Screen) -> bool,
+    ) -> bool {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if condition(self.screen()) {
+                return true;
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() || !self.receive(remaining) {
+                return condition(self.screen());
+            }
+        }
+    }
+
+    /// Wait for the application to exit, returning its exit code or `None` if it is still running
+    /// once the timeout has been reached.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the status of the child process can't be checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let mut pty = PtyTerminal::spawn(&Command::new("false"), 80, 24)?;
+    /// assert_eq!(pty.wait_exit(Duration::from_secs(5))?, Some(1));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn wait_exit(&mut self, timeout: Duration) -> io::Result<Option<u32>> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if let Some(status) = self.child.try_wait()? {
+                // Pick up anything output just before exiting
+                while self.receive(Duration::from_millis(10)) {}
+                return Ok(Some(status.exit_code()));
+            }
+
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(None);
+            }
+
+            self.receive(remaining.min(Duration::from_millis(10)));
+        }
+    }
+
+    /// Feed the output received within the provided time to the parser, returning whether there
+    /// was any.
+    fn receive(&mut self, wait: Duration) -> bool {
+        let Ok(first) = self.output.recv_timeout(wait) else {
+            return false;
+        };
+
+        for chunk in std::iter::once(first).chain(self.output.try_iter()) {
+            self.parser.process(&chunk);
+            self.received.extend_from_slice(&chunk);
+        }
+
+        true
+    }
+}
+
+impl Drop for PtyTerminal {
+    fn drop(&mut self) {
+        // The application is either finished or no longer wanted, there's nothing to report
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn pty_size(width: u16, height: u16) -> PtySize {
+    PtySize {
+        rows: height,
+        cols: width,
+        pixel_width: 0,
+        pixel_height: 0,
+    }
+}
+
+fn pty_error(err: impl std::fmt::Display) -> io::Error {
+    io::Error::new(ErrorKind::Other, err.to_string())
+}
+
+/// Carry over everything set on a standard command to one that can be run in a pseudo-terminal.
+fn command_builder(command: &Command) -> CommandBuilder {
+    let cwd = command
+        .get_current_dir()
+        .map_or_else(std::env::current_dir, |dir| Ok(dir.to_path_buf()));
+
+    // Programs given as a relative path are only searched for on the `PATH`, unlike the standard
+    // library which finds them relative to the working directory
+    let program = Path::new(command.get_program());
+    let mut builder = match &cwd {
+        Ok(cwd) if program.is_relative() && program.components().count() > 1 => {
+            CommandBuilder::new(cwd.join(program))
+        }
+        _ => CommandBuilder::new(program),
+    };
+    builder.args(command.get_args());
+
+    for (key, value) in command.get_envs() {
+        match value {
+            Some(value) => builder.env(key, value),
+            None => builder.env_remove(key),
+        }
+    }
+
+    if let Ok(cwd) = cwd {
+        builder.cwd(cwd);
+    }
+
+    builder
+}
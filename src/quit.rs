@@ -0,0 +1,186 @@
+#[cfg(unix)]
+use std::os::raw::c_int;
+#[cfg(unix)]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(unix)]
+use std::sync::Arc;
+
+use bevy::app::AppExit;
+use bevy::ecs::event::{EventReader, EventWriter};
+#[cfg(unix)]
+use bevy::ecs::system::Commands;
+use bevy::ecs::system::{Res, Resource};
+#[cfg(unix)]
+use bevy::utils::tracing::warn;
+use crossterm::event::{Event as CrossEvent, KeyCode, KeyEventKind, KeyModifiers};
+#[cfg(unix)]
+use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+
+use crate::action::KeyChord;
+use crate::focus::KeyboardCapture;
+use crate::input::RawConsoleEvent;
+#[cfg(unix)]
+use crate::signals::SignalHandlers;
+
+/// Controls how the application can be asked to exit. Every way of exiting sends an [`AppExit`]
+/// event rather than ending the process, so the app finishes its frame and the terminal is torn
+/// down cleanly afterwards.
+///
+/// The terminal doesn't turn Ctrl+C into a signal while it is in raw mode, so it is handled here
+/// as one of the quit keys instead. Keys without Ctrl are ignored while a focused widget has
+/// captured the keyboard, so a `q` typed into a [`TextInput`] doesn't exit the application.
+///
+/// Only keys pressed on the terminal the process is attached to are followed, not those of remote
+/// sessions.
+///
+/// [`TextInput`]: crate::prelude::TextInput
+///
+/// # Examples
+///
+/// Exiting with `q` as well as Ctrl+C:
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     initialize_terminal()?;
+///
+///     App::new()
+///         .add_plugins(MinimalTuiPlugins)
+///         .insert_resource(TuiQuitSettings {
+///             quit_keys: vec!["q".parse()?, "ctrl+c".parse()?],
+///             ..default()
+///         })
+///         .run();
+///
+///     teardown_terminal()?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Resource)]
+pub struct TuiQuitSettings {
+    /// The keys that exit the application when pressed, Ctrl+C by default. Removing Ctrl+C from
+    /// these lets the application handle it like any other key.
+    pub quit_keys: Vec<KeyChord>,
+
+    /// Whether receiving `SIGINT`, `SIGTERM` or `SIGHUP` exits the application instead of
+    /// immediately ending the process. A second signal received before the application has
+    /// finished exiting ends it immediately, in case it has stopped responding. This is read once
+    /// when the application starts and only applies to Unix systems.
+    pub handle_signals: bool,
+}
+
+impl Default for TuiQuitSettings {
+    fn default() -> Self {
+        Self {
+            quit_keys: vec![KeyChord::new(KeyCode::Char('c'), KeyModifiers::CONTROL)],
+            handle_signals: true,
+        }
+    }
+}
+
+/// Set when one of the signals asking the process to exit has been received.
+#[cfg(unix)]
+#[derive(Resource)]
+pub(crate) struct QuitSignals {
+    received: Arc<AtomicBool>,
+    handlers: SignalHandlers,
+}
+
+#[cfg(unix)]
+impl QuitSignals {
+    /// Stop following the signals while another program runs in the foreground with
+    /// [`ShellOut`](crate::prelude::ShellOut). The keys pressed for the program send them to the
+    /// application as well, and a second Ctrl+C would otherwise end the process underneath it.
+    ///
+    /// `SIGINT`, `SIGTERM` and `SIGHUP` are all ignored rather than given back their default
+    /// behavior until [`QuitSignals::resume`], as any of them could be meant for the program alone.
+    pub(crate) fn pause(&mut self) {
+        self.handlers.clear();
+    }
+
+    /// Follow the signals again after [`QuitSignals::pause`].
+    pub(crate) fn resume(&mut self) {
+        if self.handlers.is_empty() {
+            register_handlers(&mut self.handlers, &self.received);
+        }
+    }
+}
+
+/// Install the signal handlers if they're wanted.
+#[cfg(unix)]
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn quit_signal_setup(mut commands: Commands, settings: Res<TuiQuitSettings>) {
+    if !settings.handle_signals {
+        return;
+    }
+
+    let received = Arc::new(AtomicBool::new(false));
+    let mut handlers = SignalHandlers::hold(&QUIT_SIGNALS);
+    register_handlers(&mut handlers, &received);
+
+    commands.insert_resource(QuitSignals { received, handlers });
+}
+
+/// The signals asking the process to exit. Once the application is dropped these end the process
+/// the default way again.
+#[cfg(unix)]
+const QUIT_SIGNALS: [c_int; 3] = [SIGINT, SIGTERM, SIGHUP];
+
+/// Set the flag when any of the signals is received, ending the process immediately if it was
+/// already set.
+#[cfg(unix)]
+fn register_handlers(handlers: &mut SignalHandlers, received: &Arc<AtomicBool>) {
+    for signal in QUIT_SIGNALS {
+        // The shutdown needs to be registered first so it only sees signals after the first one
+        let registered =
+            signal_hook::flag::register_conditional_shutdown(signal, 1, Arc::clone(received))
+                .and_then(|shutdown| {
+                    handlers.push(shutdown);
+                    signal_hook::flag::register(signal, Arc::clone(received))
+                });
+
+        match registered {
+            Ok(handler) => handlers.push(handler),
+            Err(err) => warn!("failed to handle signal {signal}: {err}"),
+        }
+    }
+}
+
+/// Sends an [`AppExit`] when a quit key is pressed or a signal asking the process to exit is
+/// received.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn quit_system(
+    settings: Res<TuiQuitSettings>,
+    #[cfg(unix)] signals: Option<Res<QuitSignals>>,
+    mut events: EventReader<RawConsoleEvent>,
+    capture: Option<Res<KeyboardCapture>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    let captured = capture.map_or(false, |c| c.0);
+
+    let quit_keys = events.read().filter(|RawConsoleEvent(event)| {
+        let CrossEvent::Key(key) = event else {
+            return false;
+        };
+
+        let chord = KeyChord::from(*key);
+        let capturable = !chord.modifiers.contains(KeyModifiers::CONTROL);
+
+        key.kind != KeyEventKind::Release
+            && !(captured && capturable)
+            && settings.quit_keys.contains(&chord)
+    });
+    let quit_key = quit_keys.count() > 0;
+
+    #[cfg(unix)]
+    let signaled = signals.map_or(false, |s| s.received.load(Ordering::Relaxed));
+    #[cfg(not(unix))]
+    let signaled = false;
+
+    if quit_key || signaled {
+        exit.send(AppExit);
+    }
+}
@@ -0,0 +1,276 @@
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::output::TerminalOutput;
+
+/// The size assumed when the terminal being recorded can't be queried.
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+/// Records everything drawn to a terminal into an [asciicast v2] file while passing it through.
+/// Output is collected until the terminal is flushed, which ratatui does once at the end of every
+/// draw, and written as a single event so each frame can be stepped through during playback.
+///
+/// A recording that can't be written to is abandoned rather than interrupting the application.
+///
+/// [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+pub(crate) struct CastRecorder {
+    output: TerminalOutput,
+    cast: Option<BufWriter<File>>,
+    start: Instant,
+    size: (u16, u16),
+
+    /// Output written since the last flush, along with any incomplete UTF-8 sequence left over
+    /// from the one before.
+    pending: Vec<u8>,
+}
+
+impl CastRecorder {
+    /// Start recording to a new cast file at the provided path, replacing any that exists.
+    pub(crate) fn create(output: TerminalOutput, path: &Path) -> io::Result<Self> {
+        let size = terminal_size();
+        let mut cast = BufWriter::new(File::create(path)?);
+        cast.write_all(header(size).as_bytes())?;
+        cast.flush()?;
+
+        Ok(Self {
+            output,
+            cast: Some(cast),
+            start: Instant::now(),
+            size,
+            pending: Vec::new(),
+        })
+    }
+
+    /// Write an event for everything output since the last frame, and for the terminal having
+    /// been resized.
+    fn record_frame(&mut self) -> io::Result<()> {
+        let Some(cast) = &mut self.cast else {
+            return Ok(());
+        };
+
+        let time = self.start.elapsed().as_secs_f64();
+
+        let size = terminal_size();
+        if size != self.size {
+            self.size = size;
+            let dimensions = format!("{}x{}", size.0, size.1);
+            writeln!(cast, "[{time:.6}, \"r\", {}]", json_string(&dimensions))?;
+        }
+
+        // Frames end on a complete character, but hold back a partial one just in case
+        let complete = match std::str::from_utf8(&self.pending) {
+            Err(err) if err.error_len().is_none() => err.valid_up_to(),
+            _ => self.pending.len(),
+        };
+
+        if complete > 0 {
+            let text = String::from_utf8_lossy(&self.pending[..complete]);
+            writeln!(cast, "[{time:.6}, \"o\", {}]", json_string(&text))?;
+            self.pending.drain(..complete);
+        }
+
+        cast.flush()
+    }
+}
+
+impl Write for CastRecorder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.output.write(buf)?;
+        if self.cast.is_some() {
+            self.pending.extend_from_slice(&buf[..written]);
+        }
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.output.flush()?;
+
+        if self.record_frame().is_err() {
+            self.cast = None;
+            self.pending.clear();
+        }
+
+        Ok(())
+    }
+}
+
+fn terminal_size() -> (u16, u16) {
+    crossterm::terminal::size().unwrap_or(DEFAULT_SIZE)
+}
+
+/// The first line of a cast file, describing the recording.
+fn header((width, height): (u16, u16)) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+
+    let env: Vec<String> = ["SHELL", "TERM"]
+        .into_iter()
+        .filter_map(|name| {
+            let value = std::env::var(name).ok()?;
+            Some(format!("{}: {}", json_string(name), json_string(&value)))
+        })
+        .collect();
+
+    format!(
+        "{{\"version\": 2, \"width\": {width}, \"height\": {height}, \"timestamp\": {timestamp}, \
+         \"env\": {{{}}}}}\n",
+        env.join(", ")
+    )
+}
+
+/// Quote and escape text as a JSON string.
+fn json_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", u32::from(c));
+            }
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    /// Output shared with the test so what was passed through can be checked.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().expect("not poisoned").extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A cast file for a single test, removed once the test is done with it.
+    struct CastFile(PathBuf);
+
+    impl CastFile {
+        fn new(name: &str) -> Self {
+            let file = format!("bevy_tui_{name}_{}.cast", std::process::id());
+            Self(std::env::temp_dir().join(file))
+        }
+
+        fn lines(&self) -> Vec<String> {
+            let cast = std::fs::read_to_string(&self.0).expect("the cast was written");
+            cast.lines().map(str::to_owned).collect()
+        }
+    }
+
+    impl Drop for CastFile {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn header_describes_the_recording() {
+        let header = header((100, 30));
+
+        assert!(
+            header.starts_with("{\"version\": 2, \"width\": 100, \"height\": 30, \"timestamp\": "),
+            "{header}"
+        );
+        assert!(header.contains(", \"env\": {"), "{header}");
+        assert!(header.ends_with("}}\n"), "{header}");
+        assert_eq!(header.lines().count(), 1);
+    }
+
+    #[test]
+    fn json_string_escapes_quotes_and_backslashes() {
+        assert_eq!(json_string(""), r#""""#);
+        assert_eq!(json_string(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(json_string(r"C:\tmp"), r#""C:\\tmp""#);
+    }
+
+    #[test]
+    fn json_string_escapes_control_characters() {
+        assert_eq!(json_string("a\nb\r\tc"), r#""a\nb\r\tc""#);
+        assert_eq!(json_string("\x1b[0m"), r#""\u001b[0m""#);
+        assert_eq!(json_string("\0\x7f\u{85}"), r#""\u0000\u007f\u0085""#);
+    }
+
+    #[test]
+    fn json_string_keeps_non_ascii_text() {
+        assert_eq!(json_string("héllo wörld"), "\"héllo wörld\"");
+        assert_eq!(json_string("│ 日本 🦀"), "\"│ 日本 🦀\"");
+    }
+
+    #[test]
+    fn records_a_line_per_frame() {
+        let cast = CastFile::new("frames");
+        let shared = Shared::default();
+        let mut recorder = CastRecorder::create(TerminalOutput::writer(shared.clone()), &cast.0)
+            .expect("the cast can be created");
+
+        recorder.write_all(b"\x1b[Hfirst").expect("sink");
+        recorder.write_all(b" \"frame\"").expect("sink");
+        recorder.flush().expect("sink");
+
+        // Nothing was drawn, so there is nothing to record
+        recorder.flush().expect("sink");
+
+        recorder.write_all(b"second\n").expect("sink");
+        recorder.flush().expect("sink");
+
+        let lines = cast.lines();
+        assert_eq!(lines.len(), 3, "{lines:?}");
+        assert!(lines[0].starts_with("{\"version\": 2, "), "{lines:?}");
+        assert!(lines[1].starts_with('['), "{lines:?}");
+        assert!(
+            lines[1].ends_with(r#", "o", "\u001b[Hfirst \"frame\""]"#),
+            "{lines:?}"
+        );
+        assert!(lines[2].ends_with(r#", "o", "second\n"]"#), "{lines:?}");
+
+        let passed = shared.0.lock().expect("not poisoned").clone();
+        assert_eq!(passed, b"\x1b[Hfirst \"frame\"second\n");
+    }
+
+    #[test]
+    fn holds_back_a_partial_character() {
+        let cast = CastFile::new("partial");
+        let mut recorder = CastRecorder::create(TerminalOutput::writer(io::sink()), &cast.0)
+            .expect("the cast can be created");
+
+        let [first, second] = "é".as_bytes() else {
+            unreachable!("é is two bytes");
+        };
+
+        recorder.write_all(&[b'a', *first]).expect("sink");
+        recorder.flush().expect("sink");
+        recorder.write_all(&[*second]).expect("sink");
+        recorder.flush().expect("sink");
+
+        let lines = cast.lines();
+        assert_eq!(lines.len(), 3, "{lines:?}");
+        assert!(lines[1].ends_with(r#", "o", "a"]"#), "{lines:?}");
+        assert!(lines[2].ends_with(r#", "o", "é"]"#), "{lines:?}");
+    }
+}
@@ -0,0 +1,630 @@
+use std::any::TypeId;
+use std::io;
+
+use bevy::app::{App, AppExit};
+use bevy::ecs::change_detection::DetectChangesMut;
+use bevy::ecs::component::{Component, ComponentId};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::system::Resource;
+use bevy::ecs::world::{Mut, World};
+use bevy::hierarchy::{Children, Parent};
+use bevy::utils::tracing::{error, warn};
+use ratatui::backend::Backend;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Position, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::{Block, StatefulWidget, Widget};
+use ratatui::Frame;
+
+use crate::color::{ColorFilter, ColorFilterBackend, ColorSupport};
+use crate::focus::FocusedEntity;
+use crate::layout::{layout_roots, TuiNode};
+use crate::session::{unclaimed_roots, TuiSession};
+use crate::theme::{ThemeStyle, Themed, TuiTheme};
+use crate::widgets::TuiBlock;
+use crate::BevyTerminal;
+
+/// A base style applied to the entire area of a [`TuiNode`] before any of its widgets are
+/// rendered. This is most commonly used to set the foreground and background colors of a region.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+/// use ratatui::style::{Color, Style};
+///
+/// TuiStyle(Style::default().fg(Color::White).bg(Color::Blue));
+/// ```
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+pub struct TuiStyle(pub Style);
+
+/// Controls the passes applied to each frame as it is written out to the terminal, whether it was
+/// drawn by the render system or by hand.
+///
+/// The default settings respect the [`NO_COLOR`](https://no-color.org/) environment variable,
+/// enabling [`monochrome`](Self::monochrome) when it is set to anything other than an empty string.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// App::new()
+///     .add_plugins(MinimalTuiPlugins)
+///     .insert_resource(TuiRenderSettings {
+///         downsample_colors: true,
+///         ..default()
+///     })
+///     .run();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Resource)]
+pub struct TuiRenderSettings {
+    /// Replace any colors the terminal can't display, according to the [`ColorSupport`] resource,
+    /// with the nearest one it can.
+    pub downsample_colors: bool,
+
+    /// Remove the foreground, background, and underline colors from every cell, leaving modifiers
+    /// such as bold and reverse in place. Anything relying on color alone to stand out, such as a
+    /// selection highlight, should also set a modifier to remain visible. [`TuiTheme::monochrome`]
+    /// provides styles that only use modifiers and is used in place of the default theme while
+    /// this is enabled, unless the application has provided a theme of its own.
+    pub monochrome: bool,
+}
+
+impl TuiRenderSettings {
+    /// The default settings, with [`monochrome`](Self::monochrome) enabled if `NO_COLOR` is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let settings = TuiRenderSettings::from_env();
+    /// let no_color = std::env::var_os("NO_COLOR").map_or(false, |v| !v.is_empty());
+    /// assert_eq!(settings.monochrome, no_color);
+    /// ```
+    #[must_use]
+    pub fn from_env() -> Self {
+        let no_color = std::env::var_os("NO_COLOR").map_or(false, |value| !value.is_empty());
+
+        Self {
+            downsample_colors: false,
+            monochrome: no_color,
+        }
+    }
+
+    /// The filter these settings apply to the colors written out to a terminal with the provided
+    /// level of support, with [`monochrome`](Self::monochrome) taking precedence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let settings = TuiRenderSettings {
+    ///     downsample_colors: true,
+    ///     monochrome: false,
+    /// };
+    /// assert_eq!(
+    ///     settings.color_filter(ColorSupport::Ansi16),
+    ///     ColorFilter::Downsample(ColorSupport::Ansi16),
+    /// );
+    /// ```
+    #[must_use]
+    pub fn color_filter(&self, color_support: ColorSupport) -> ColorFilter {
+        if self.monochrome {
+            ColorFilter::Monochrome
+        } else if self.downsample_colors && color_support != ColorSupport::TrueColor {
+            ColorFilter::Downsample(color_support)
+        } else {
+            ColorFilter::None
+        }
+    }
+}
+
+impl Default for TuiRenderSettings {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+/// The view of the current frame handed to a [`TuiWidget`] when it is being rendered. The area
+/// has already been resolved by the layout pass and shrunk to fit inside of any [`TuiBlock`] on
+/// the same entity.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::Paragraph;
+///
+/// #[derive(Component)]
+/// struct Greeting;
+///
+/// impl TuiWidget for Greeting {
+///     fn render(&mut self, ctx: &mut TuiRenderContext) {
+///         ctx.render_widget(Paragraph::new("Hello Bevy!"));
+///     }
+/// }
+/// ```
+pub struct TuiRenderContext<'a, 'b> {
+    area: Rect,
+    focused: bool,
+    frame: &'a mut Frame<'b>,
+    theme: &'a TuiTheme,
+    themed: Themed,
+}
+
+impl<'b> TuiRenderContext<'_, 'b> {
+    /// The area of the terminal this widget should be drawn into.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// fn widget_width(ctx: &TuiRenderContext) -> u16 {
+    ///     ctx.area().width
+    /// }
+    /// ```
+    #[must_use]
+    pub fn area(&self) -> Rect {
+        self.area
+    }
+
+    /// Whether the entity being drawn currently has keyboard focus.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::widgets::Paragraph;
+    ///
+    /// fn label(ctx: &mut TuiRenderContext) {
+    ///     let text = if ctx.is_focused() { "> field" } else { "  field" };
+    ///     ctx.render_widget(Paragraph::new(text));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// The theme currently in use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::widgets::Paragraph;
+    ///
+    /// fn warning(ctx: &mut TuiRenderContext) {
+    ///     let style = ctx.theme().style(StyleRole::Error);
+    ///     ctx.render_widget(Paragraph::new("Disk full").style(style));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn theme(&self) -> &TuiTheme {
+        self.theme
+    }
+
+    /// Which of the widget's own settings should be replaced by the theme's, from the [`Themed`]
+    /// component of the entity being drawn. Nothing is themed when it doesn't have one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::style::Style;
+    /// use ratatui::widgets::Paragraph;
+    ///
+    /// fn title(ctx: &mut TuiRenderContext, style: Style) {
+    ///     let style = if ctx.themed().primary {
+    ///         ctx.theme().primary
+    ///     } else {
+    ///         style
+    ///     };
+    ///     ctx.render_widget(Paragraph::new("Status").style(style));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn themed(&self) -> Themed {
+        self.themed
+    }
+
+    /// Direct access to the buffer backing the current frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// fn mark_corner(ctx: &mut TuiRenderContext) {
+    ///     let area = ctx.area();
+    ///     ctx.buffer_mut().get_mut(area.x, area.y).set_char('*');
+    /// }
+    /// ```
+    pub fn buffer_mut(&mut self) -> &mut Buffer {
+        self.frame.buffer_mut()
+    }
+
+    /// Direct access to the frame currently being drawn, this is useful for operations that aren't
+    /// limited to the buffer such as positioning the terminal's cursor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// fn place_cursor(ctx: &mut TuiRenderContext) {
+    ///     let area = ctx.area();
+    ///     ctx.frame().set_cursor(area.x, area.y);
+    /// }
+    /// ```
+    pub fn frame(&mut self) -> &mut Frame<'b> {
+        self.frame
+    }
+
+    /// Render a ratatui widget filling the area assigned to this widget.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::widgets::Paragraph;
+    ///
+    /// fn hello(ctx: &mut TuiRenderContext) {
+    ///     ctx.render_widget(Paragraph::new("Hello"));
+    /// }
+    /// ```
+    pub fn render_widget<W: Widget>(&mut self, widget: W) {
+        self.frame.render_widget(widget, self.area);
+    }
+
+    /// Render a stateful ratatui widget filling the area assigned to this widget.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::widgets::{List, ListState};
+    ///
+    /// fn numbers(ctx: &mut TuiRenderContext, state: &mut ListState) {
+    ///     ctx.render_stateful_widget(List::new(["one", "two"]), state);
+    /// }
+    /// ```
+    pub fn render_stateful_widget<W: StatefulWidget>(&mut self, widget: W, state: &mut W::State) {
+        self.frame.render_stateful_widget(widget, self.area, state);
+    }
+}
+
+/// A component that knows how to draw itself into the area of its entity's [`TuiNode`]. Once
+/// registered with [`TuiAppExt::register_tui_widget`] every entity with both the node and this
+/// component will be drawn each frame, parents before their children.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::Paragraph;
+///
+/// #[derive(Component)]
+/// struct Counter(usize);
+///
+/// impl TuiWidget for Counter {
+///     fn render(&mut self, ctx: &mut TuiRenderContext) {
+///         ctx.render_widget(Paragraph::new(format!("Count: {}", self.0)));
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins(MinimalTuiPlugins)
+///     .register_tui_widget::<Counter>()
+///     .run();
+/// ```
+pub trait TuiWidget: Component {
+    /// Draw the widget into the provided context.
+    fn render(&mut self, ctx: &mut TuiRenderContext<'_, '_>);
+}
+
+/// Extension methods for registering this library's extension points on a Bevy [`App`].
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Blank;
+///
+/// impl TuiWidget for Blank {
+///     fn render(&mut self, _ctx: &mut TuiRenderContext) {}
+/// }
+///
+/// let mut app = App::new();
+/// app.register_tui_widget::<Blank>();
+/// ```
+pub trait TuiAppExt {
+    /// Register a component implementing [`TuiWidget`] so it will be drawn by the render pass.
+    /// Registering the same widget more than once has no additional effect.
+    fn register_tui_widget<W: TuiWidget>(&mut self) -> &mut Self;
+}
+
+impl TuiAppExt for App {
+    fn register_tui_widget<W: TuiWidget>(&mut self) -> &mut Self {
+        let component_id = self.world.init_component::<W>();
+
+        let mut registry = self
+            .world
+            .get_resource_or_insert_with(TuiWidgetRegistry::default);
+
+        if !registry
+            .widgets
+            .iter()
+            .any(|w| w.type_id == TypeId::of::<W>())
+        {
+            registry.widgets.push(RegisteredWidget {
+                component_id,
+                render: render_component::<W>,
+                type_id: TypeId::of::<W>(),
+            });
+        }
+
+        self
+    }
+}
+
+#[derive(Clone, Copy)]
+struct RegisteredWidget {
+    component_id: ComponentId,
+    render: fn(&mut World, Entity, &mut TuiRenderContext<'_, '_>),
+    type_id: TypeId,
+}
+
+#[derive(Clone, Default, Resource)]
+pub(crate) struct TuiWidgetRegistry {
+    widgets: Vec<RegisteredWidget>,
+}
+
+fn render_component<W: TuiWidget>(
+    world: &mut World,
+    entity: Entity,
+    ctx: &mut TuiRenderContext<'_, '_>,
+) {
+    if let Some(mut widget) = world.get_mut::<W>(entity) {
+        // Drawing a widget shouldn't count as modifying it as far as the rest of the app is
+        // concerned, otherwise every widget would appear changed every single frame.
+        widget.bypass_change_detection().render(ctx);
+    }
+}
+
+/// Builds the list of nodes beneath the provided roots in the order they should be drawn. Parents
+/// are always drawn before their children so that children end up on top, siblings are drawn in
+/// the order they appear.
+fn draw_order(world: &World, roots: &[Entity]) -> Vec<(Entity, Rect)> {
+    let mut sorted_roots = roots.to_vec();
+    sorted_roots.sort();
+
+    let mut ordered = Vec::new();
+    let mut pending: Vec<Entity> = sorted_roots.into_iter().rev().collect();
+
+    while let Some(entity) = pending.pop() {
+        let Some(node) = world.get::<TuiNode>(entity) else {
+            continue;
+        };
+
+        ordered.push((entity, node.area()));
+
+        if let Some(children) = world.get::<Children>(entity) {
+            pending.extend(children.iter().rev());
+        }
+    }
+
+    ordered
+}
+
+fn is_drawable(world: &World, registry: &TuiWidgetRegistry, entity: Entity) -> bool {
+    let entity_ref = world.entity(entity);
+
+    entity_ref.contains::<TuiBlock>()
+        || entity_ref.contains::<TuiStyle>()
+        || entity_ref.contains::<ThemeStyle>()
+        || registry
+            .widgets
+            .iter()
+            .any(|w| entity_ref.contains_id(w.component_id))
+}
+
+/// The state shared by every terminal drawn during a frame.
+pub(crate) struct FrameResources {
+    registry: TuiWidgetRegistry,
+    theme: TuiTheme,
+    settings: TuiRenderSettings,
+    color_support: ColorSupport,
+}
+
+impl FrameResources {
+    /// Gather the state from the world, falling back to the defaults for anything missing.
+    pub(crate) fn from_world(world: &World) -> Self {
+        Self {
+            registry: world
+                .get_resource::<TuiWidgetRegistry>()
+                .cloned()
+                .unwrap_or_default(),
+            theme: world
+                .get_resource::<TuiTheme>()
+                .cloned()
+                .unwrap_or_default(),
+            settings: world
+                .get_resource::<TuiRenderSettings>()
+                .copied()
+                .unwrap_or_default(),
+            color_support: world
+                .get_resource::<ColorSupport>()
+                .copied()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The filter to apply to the colors written out to every terminal.
+    pub(crate) fn color_filter(&self) -> ColorFilter {
+        self.settings.color_filter(self.color_support)
+    }
+}
+
+/// Draws every node that has something to display into the terminal the process is attached to,
+/// then into the terminal of every [`TuiSession`]. When no nodes have anything to draw on a
+/// terminal nothing is drawn to it, leaving applications that draw by hand alone apart from
+/// keeping its color filter up to date.
+///
+/// Failing to write a frame out to the terminal the process is attached to exits the app, the same
+/// as failing to read from it does. A session whose frame can't be written out skips it, its client
+/// has likely gone away and will be cleaned up.
+pub(crate) fn render_system(world: &mut World) {
+    let resources = FrameResources::from_world(world);
+
+    let all_roots: Vec<Entity> = world
+        .query_filtered::<Entity, (With<TuiNode>, Without<Parent>)>()
+        .iter(world)
+        .collect();
+    let sessions: Vec<(Entity, TuiSession, Option<Entity>)> = world
+        .query::<(Entity, &TuiSession, Option<&FocusedEntity>)>()
+        .iter(world)
+        .map(|(entity, session, focused)| (entity, *session, focused.and_then(FocusedEntity::get)))
+        .collect();
+    let unclaimed = unclaimed_roots(&all_roots, sessions.iter().map(|(_, session, _)| session));
+
+    if world.contains_resource::<BevyTerminal>() {
+        let focused = world
+            .get_resource::<FocusedEntity>()
+            .and_then(FocusedEntity::get);
+
+        let drawn = world.resource_scope(|world, mut terminal: Mut<BevyTerminal>| {
+            draw(world, &mut terminal.0, &unclaimed, focused, &resources)
+        });
+
+        if let Err(err) = drawn {
+            error!("exiting after failing to draw to the terminal: {err}");
+            world.send_event(AppExit);
+        }
+    }
+
+    for (entity, session, focused) in sessions {
+        let Some(mut terminal) = world.entity_mut(entity).take::<BevyTerminal>() else {
+            continue;
+        };
+
+        // A root shown on several sessions needs to be fit to each of them in turn
+        let roots = session.roots(&unclaimed);
+        let area = terminal.0.get_frame().size();
+        layout_roots(world, &roots, area);
+
+        if let Err(err) = draw(world, &mut terminal.0, &roots, focused, &resources) {
+            warn!("failed to draw to session {entity:?}: {err}");
+        }
+
+        world.entity_mut(entity).insert(terminal);
+    }
+}
+
+/// Draws the provided roots and everything beneath them into a terminal with any backend, after
+/// bringing the terminal's color filter up to date with the settings.
+pub(crate) fn draw<B: Backend>(
+    world: &mut World,
+    terminal: &mut ratatui::Terminal<ColorFilterBackend<B>>,
+    roots: &[Entity],
+    focused: Option<Entity>,
+    resources: &FrameResources,
+) -> io::Result<()> {
+    let filter = resources.color_filter();
+    if terminal.backend().filter() != filter {
+        terminal.backend_mut().set_filter(filter);
+
+        // Everything already on the screen was written out with the old filter
+        terminal.clear()?;
+    }
+
+    let FrameResources {
+        registry, theme, ..
+    } = resources;
+
+    let draw_list: Vec<(Entity, Rect)> = draw_order(world, roots)
+        .into_iter()
+        .filter(|(entity, _)| is_drawable(world, registry, *entity))
+        .collect();
+
+    if draw_list.is_empty() {
+        return Ok(());
+    }
+
+    terminal
+        .draw(|frame| {
+            for (entity, area) in draw_list {
+                let is_focused = focused == Some(entity);
+                let themed = world.get::<Themed>(entity).copied().unwrap_or(Themed::NONE);
+
+                if let Some(role) = world.get::<ThemeStyle>(entity) {
+                    frame.buffer_mut().set_style(area, theme.style(role.0));
+                }
+
+                if let Some(style) = world.get::<TuiStyle>(entity) {
+                    frame.buffer_mut().set_style(area, style.0);
+                }
+
+                let mut content_area = area;
+                if let Some(block) = world.get::<TuiBlock>(entity) {
+                    content_area = block.0.inner(area);
+                    render_block(frame, &block.0, area, theme, themed, is_focused);
+                }
+
+                let mut ctx = TuiRenderContext {
+                    area: content_area,
+                    focused: is_focused,
+                    frame,
+                    theme,
+                    themed,
+                };
+
+                for widget in &registry.widgets {
+                    if world.entity(entity).contains_id(widget.component_id) {
+                        (widget.render)(world, entity, &mut ctx);
+                    }
+                }
+            }
+        })
+        .map(|_| ())
+}
+
+/// Draws a block with the theme's border style underneath its own, switching to the focused style
+/// when the block's entity has focus. The theme's border type is only used when it is [`Themed`].
+fn render_block(
+    frame: &mut Frame,
+    block: &Block<'static>,
+    area: Rect,
+    theme: &TuiTheme,
+    themed: Themed,
+    focused: bool,
+) {
+    let inner = block.inner(area);
+    let border_style = if focused { theme.focused } else { theme.border };
+
+    let buffer = frame.buffer_mut();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            if !inner.contains(Position { x, y }) {
+                buffer.get_mut(x, y).set_style(border_style);
+            }
+        }
+    }
+
+    if themed.border_type {
+        frame.render_widget(block.clone().border_type(theme.border_type), area);
+    } else {
+        frame.render_widget(block.clone(), area);
+    }
+}
@@ -0,0 +1,215 @@
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Lines, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use bevy::app::{App, AppExit};
+use bevy::ecs::system::Resource;
+use bevy::time::TimeUpdateStrategy;
+use bevy::utils::tracing::error;
+use crossterm::event::Event as CrossEvent;
+use serde::{Deserialize, Serialize};
+
+/// Records the input received from the terminal so a session can be reproduced exactly, or plays
+/// back such a recording in place of reading the terminal. This needs to be inserted before the
+/// app is run for it to have any effect.
+///
+/// Every frame is recorded, along with the time it started and the events received for it. When
+/// replaying, each frame is given the same events and the [`Time`](bevy::time::Time) resources
+/// are advanced by the same amounts as they were during the recording, so an application that
+/// only depends on its input and the time behaves identically. Frames are played back at the
+/// speed they were recorded at, and input is read from the terminal as normal once the recording
+/// runs out.
+///
+/// Only input from the terminal the process is attached to is recorded, not that of remote
+/// sessions. If the file can't be opened the error is logged and the app exits after its first
+/// frame, as it does if the recording can't be written or read part way through.
+///
+/// # Examples
+///
+/// Reproducing a session previously recorded with `TuiInputRecording::Record`:
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     initialize_terminal()?;
+///
+///     App::new()
+///         .add_plugins(MinimalTuiPlugins)
+///         .insert_resource(TuiInputRecording::Replay("bug-report.ron".into()))
+///         .run();
+///
+///     teardown_terminal()?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Resource)]
+pub enum TuiInputRecording {
+    /// Input is read from the terminal without being recorded.
+    #[default]
+    Off,
+
+    /// Record the input into a file at this path, replacing any file that exists. Each line of the
+    /// file is a single frame in the RON format.
+    Record(PathBuf),
+
+    /// Play back the input recorded in the file at this path.
+    Replay(PathBuf),
+}
+
+/// A single line of a recording.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct RecordedFrame {
+    /// The number of frames that came before this one.
+    frame: u64,
+
+    /// When the frame started, relative to the start of the recording.
+    elapsed: Duration,
+
+    /// The events received from the terminal during the frame.
+    events: Vec<CrossEvent>,
+}
+
+/// The scheduler's side of a [`TuiInputRecording`], sitting between it and the terminal. This is
+/// opened by the scheduler before the first frame, so that frame is recorded and replayed too.
+pub(crate) enum InputRecorder {
+    Recording {
+        file: BufWriter<File>,
+        start: Instant,
+        frame: u64,
+        events: Vec<CrossEvent>,
+    },
+    Replaying {
+        frames: Lines<BufReader<File>>,
+        start: Instant,
+        current: Option<RecordedFrame>,
+    },
+    Live,
+}
+
+impl InputRecorder {
+    /// Open the file for the recording configured in the app, if any. If it can't be opened the
+    /// error is logged and the app is told to exit, leaving it to read the terminal for the frame
+    /// it gets to clean up in.
+    pub(crate) fn from_app(app: &mut App) -> Self {
+        let Some(settings) = app.world.get_resource::<TuiInputRecording>() else {
+            return Self::Live;
+        };
+
+        match Self::open(settings) {
+            Ok(recorder) => recorder,
+            Err(err) => {
+                error!("failed to open the input recording, exiting: {err}");
+                app.world.send_event(AppExit);
+                Self::Live
+            }
+        }
+    }
+
+    /// Open the file for a recording.
+    fn open(settings: &TuiInputRecording) -> Result<Self, Box<dyn Error>> {
+        let recorder = match settings.clone() {
+            TuiInputRecording::Off => Self::Live,
+            TuiInputRecording::Record(path) => Self::Recording {
+                file: BufWriter::new(File::create(path)?),
+                start: Instant::now(),
+                frame: 0,
+                events: Vec::new(),
+            },
+            TuiInputRecording::Replay(path) => {
+                // The first frame is played back without reading any events, so it's read upfront
+                let mut frames = BufReader::new(File::open(path)?).lines();
+                let current = next_frame(&mut frames)?;
+
+                Self::Replaying {
+                    frames,
+                    start: Instant::now(),
+                    current,
+                }
+            }
+        };
+
+        Ok(recorder)
+    }
+
+    /// Gather the events for the next frame, either from the terminal or the recording.
+    pub(crate) fn events(
+        &mut self,
+        local_events: impl FnOnce() -> Result<Vec<CrossEvent>, Box<dyn Error>>,
+    ) -> Result<Vec<CrossEvent>, Box<dyn Error>> {
+        match self {
+            Self::Recording { events, .. } => {
+                *events = local_events()?;
+                Ok(events.clone())
+            }
+            Self::Replaying {
+                frames, current, ..
+            } => {
+                *current = next_frame(frames)?;
+                match current {
+                    Some(frame) => Ok(frame.events.clone()),
+                    None => local_events(),
+                }
+            }
+            Self::Live => local_events(),
+        }
+    }
+
+    /// Set the time the upcoming update will see, recording the frame or waiting until it is due
+    /// to be played back.
+    pub(crate) fn start_frame(&mut self, app: &mut App) -> Result<(), Box<dyn Error>> {
+        let instant = match self {
+            Self::Recording {
+                file,
+                start,
+                frame,
+                events,
+            } => {
+                let now = Instant::now();
+                let recorded = RecordedFrame {
+                    frame: *frame,
+                    elapsed: now.duration_since(*start),
+                    events: std::mem::take(events),
+                };
+
+                writeln!(file, "{}", ron::to_string(&recorded)?)?;
+                file.flush()?;
+                *frame += 1;
+
+                now
+            }
+            Self::Replaying { start, current, .. } => {
+                // The frame was read along with its events, there are none left once it is missing
+                let Some(frame) = current.take() else {
+                    app.world.insert_resource(TimeUpdateStrategy::Automatic);
+                    *self = Self::Live;
+                    return Ok(());
+                };
+
+                let due = *start + frame.elapsed;
+                std::thread::sleep(due.saturating_duration_since(Instant::now()));
+
+                due
+            }
+            Self::Live => return Ok(()),
+        };
+
+        app.world
+            .insert_resource(TimeUpdateStrategy::ManualInstant(instant));
+
+        Ok(())
+    }
+}
+
+fn next_frame(
+    frames: &mut Lines<BufReader<File>>,
+) -> Result<Option<RecordedFrame>, Box<dyn Error>> {
+    match frames.next() {
+        Some(line) => Ok(Some(ron::from_str(&line?)?)),
+        None => Ok(None),
+    }
+}
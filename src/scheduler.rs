@@ -1,11 +1,19 @@
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
 use bevy::app::{App, AppExit};
 use bevy::ecs::event::{Events, ManualEventReader};
 use bevy::ecs::system::Resource;
-use crossterm::event::{poll as poll_term, read as read_term};
+use bevy::utils::tracing::error;
+use crossterm::event::{poll as poll_term, read as read_term, Event as CrossEvent};
 
-use crate::input::event_handler;
+use crate::events::SessionEvent;
+use crate::input::parser::{is_lone_escape, rejoin_escape};
+use crate::input::{event_handler, TuiInputSettings};
+#[cfg(feature = "replay")]
+use crate::replay::InputRecorder;
+use crate::terminal_helpers::escape_disambiguated;
 
 /// By default the loop will target 4 FPS
 const DEFAULT_LOOP_DELAY: Duration = Duration::from_millis(250);
@@ -38,9 +46,98 @@ impl Default for TuiPersistentState {
     }
 }
 
+/// Events arriving for sessions other than the terminal the process is attached to, such as
+/// network connections. When this is present the local terminal isn't read from at all.
+#[derive(Resource)]
+pub(crate) struct RemoteInput {
+    sender: Sender<SessionEvent>,
+    receiver: Mutex<Receiver<SessionEvent>>,
+}
+
+impl RemoteInput {
+    /// A handle for delivering events to the app from another thread.
+    pub(crate) fn sender(&self) -> Sender<SessionEvent> {
+        self.sender.clone()
+    }
+}
+
+impl Default for RemoteInput {
+    fn default() -> Self {
+        let (sender, receiver) = channel();
+
+        Self {
+            sender,
+            receiver: Mutex::new(receiver),
+        }
+    }
+}
+
+/// Wait for events from the local terminal, returning them all once any arrive or the loop delay
+/// has been reached.
+///
+/// Crossterm reports Escape as soon as nothing else is waiting to be read after it, so a sequence
+/// arriving slowly is split into Escape and the keys making up the rest of it. When the last event
+/// read is a lone Escape this waits up to the escape timeout for anything else, putting the
+/// sequence back together if it arrives the same way input from remote sessions is parsed. A
+/// terminal that disambiguates escape codes sends Escape as a sequence of its own, so it is
+/// reported without waiting.
+fn local_events(escape_timeout: Duration) -> Result<Vec<CrossEvent>, Box<dyn std::error::Error>> {
+    let mut events = Vec::new();
+    let disambiguated = escape_disambiguated();
+
+    if !poll_term(DEFAULT_LOOP_DELAY)? {
+        return Ok(events);
+    }
+
+    loop {
+        // Read all of the available events all at once
+        while poll_term(Duration::from_secs(0))? {
+            events.push(read_term()?);
+        }
+
+        let ends_with_escape = events.last().map_or(false, is_lone_escape);
+        if !ends_with_escape
+            || disambiguated
+            || escape_timeout.is_zero()
+            || !poll_term(escape_timeout)?
+        {
+            break;
+        }
+
+        let escape = events.len() - 1;
+        while poll_term(Duration::from_secs(0))? {
+            events.push(read_term()?);
+        }
+
+        let rest = events.split_off(escape);
+        events.extend(rejoin_escape(&rest, disambiguated));
+    }
+
+    Ok(events)
+}
+
+/// The same as [`local_events`] but waiting on events delivered through [`RemoteInput`].
+fn remote_events(remote: &RemoteInput) -> Result<Vec<SessionEvent>, Box<dyn std::error::Error>> {
+    let receiver = remote
+        .receiver
+        .lock()
+        .map_err(|_| "remote input receiver was poisoned")?;
+
+    let mut events = Vec::new();
+    match receiver.recv_timeout(DEFAULT_LOOP_DELAY) {
+        Ok(event) => events.push(event),
+        Err(RecvTimeoutError::Timeout) => return Ok(events),
+        Err(RecvTimeoutError::Disconnected) => return Err("remote input disconnected".into()),
+    }
+    events.extend(receiver.try_iter());
+
+    Ok(events)
+}
+
 fn tick(
     app: &mut App,
     app_exit_event_reader: &mut ManualEventReader<AppExit>,
+    #[cfg(feature = "replay")] recorder: &mut InputRecorder,
 ) -> Result<Option<Duration>, Box<dyn std::error::Error>> {
     let start_time = Instant::now();
 
@@ -50,14 +147,30 @@ fn tick(
     let first_run = app.world.resource::<TuiPersistentState>().is_first_run();
     if !first_run {
         // todo: need to adjust this delay based on how long the last loop took
-        let events_available = poll_term(DEFAULT_LOOP_DELAY)?;
-
-        if events_available {
-            // Read all of the available events all at once
-            while poll_term(Duration::from_secs(0))? {
-                event_handler(app, read_term()?);
+        let events_available = if let Some(remote) = app.world.get_resource::<RemoteInput>() {
+            let events = remote_events(remote)?;
+            let events_available = !events.is_empty();
+            app.world.send_event_batch(events);
+            events_available
+        } else {
+            let escape_timeout = app
+                .world
+                .get_resource::<TuiInputSettings>()
+                .copied()
+                .unwrap_or_default()
+                .escape_timeout;
+
+            #[cfg(feature = "replay")]
+            let events = recorder.events(|| local_events(escape_timeout))?;
+            #[cfg(not(feature = "replay"))]
+            let events = local_events(escape_timeout)?;
+
+            let events_available = !events.is_empty();
+            for event in events {
+                event_handler(app, event);
             }
-        }
+            events_available
+        };
 
         // Indicate that this tick was triggered by the timeout and not by an event
         app.world
@@ -65,6 +178,11 @@ fn tick(
             .timeout_reached = !events_available;
     }
 
+    #[cfg(feature = "replay")]
+    if app.world.get_resource::<RemoteInput>().is_none() {
+        recorder.start_frame(app)?;
+    }
+
     app.update();
     app.world
         .resource_mut::<TuiPersistentState>()
@@ -82,7 +200,27 @@ fn tick(
 pub(crate) fn tui_schedule_runner(mut app: App) {
     let mut app_exit_event_reader = ManualEventReader::<AppExit>::default();
 
-    while let Ok(Some(_tick_duration)) = tick(&mut app, &mut app_exit_event_reader) {
-        // more stuff to do
+    #[cfg(feature = "replay")]
+    let mut recorder = InputRecorder::from_app(&mut app);
+
+    loop {
+        let ticked = tick(
+            &mut app,
+            &mut app_exit_event_reader,
+            #[cfg(feature = "replay")]
+            &mut recorder,
+        );
+
+        match ticked {
+            Ok(Some(_tick_duration)) => {}
+            Ok(None) => break,
+            Err(err) => {
+                // Give the app a final frame to clean up in, the same as it gets for any other exit
+                error!("exiting after failing to run a frame: {err}");
+                app.world.send_event(AppExit);
+                app.update();
+                break;
+            }
+        }
     }
 }
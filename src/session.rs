@@ -0,0 +1,888 @@
+use std::io::{self, BufWriter, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use bevy::app::AppExit;
+use bevy::ecs::bundle::Bundle;
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::event::{EventReader, EventWriter};
+use bevy::ecs::query::With;
+use bevy::ecs::system::{Commands, Query, Res, ResMut, Resource};
+use bevy::input::keyboard::KeyCode;
+use bevy::input::mouse::MouseButton;
+use bevy::input::{ButtonInput, ButtonState};
+use crossterm::cursor::{Hide, Show};
+use crossterm::event::{
+    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    Event as CrossEvent,
+};
+use crossterm::terminal::{Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::QueueableCommand;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::Rect;
+use ratatui::{TerminalOptions, Viewport};
+
+use crate::color::ColorFilterBackend;
+use crate::events::{SessionEnded, SessionEvent};
+use crate::focus::{FocusParams, FocusedEntity};
+use crate::input::converters::{convert_keyboard_input, convert_mouse_input};
+use crate::input::{update_keys, MouseInput};
+use crate::layout::LayoutAreas;
+use crate::output::TerminalOutput;
+use crate::{BevyTerminal, Terminal};
+
+/// How long sending output to a remote client can be held up before the client is considered gone.
+pub(crate) const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The size assumed for a remote client until it reports its window size.
+pub(crate) const DEFAULT_SIZE: (u16, u16) = (80, 24);
+
+/// Sent to clients connecting once the maximum number of clients are connected.
+pub(crate) const TOO_MANY_CLIENTS: &[u8] = b"Too many operators are already connected.\r\n";
+
+/// How many frames can be waiting to be sent to a client before later ones are held back.
+const QUEUED_OUTPUT: usize = 2;
+
+/// How much output can be held back for a client before it's considered gone.
+const MAX_HELD_OUTPUT: usize = 1 << 20;
+
+/// Marks an entity as a terminal session, such as a single client connected over the network.
+/// Each session draws to the [`BevyTerminal`] component on its own entity rather than the global
+/// resource, and has its own focus and input state. This allows a single app to serve several
+/// operators at once, each looking at a different screen of the same state.
+///
+/// Session terminals use a fixed viewport which is resized whenever a resize event is received for
+/// the session.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn show_own_screen(mut commands: Commands, mut started: EventReader<SessionStarted>) {
+///     for event in started.read() {
+///         let screen = commands.spawn(ParagraphBundle::default()).id();
+///         commands.entity(event.session).insert(TuiSession { root: Some(screen) });
+///     }
+/// }
+/// ```
+#[derive(Clone, Copy, Component, Debug, Default, PartialEq, Eq)]
+pub struct TuiSession {
+    /// The layout root this session displays. When left empty the session shows every layout
+    /// root that isn't claimed by another session, the same as the terminal the process is
+    /// attached to would. A root shared between sessions of different sizes is laid out again
+    /// for each of them as they are drawn, so its [`TuiNode`](crate::layout::TuiNode) areas only
+    /// reflect one of the sessions at a time. The areas on each session's own terminal are kept in
+    /// its [`LayoutAreas`].
+    pub root: Option<Entity>,
+}
+
+impl TuiSession {
+    /// The layout roots this session displays, given those not claimed by any session.
+    pub(crate) fn roots(self, unclaimed: &[Entity]) -> Vec<Entity> {
+        self.root
+            .map_or_else(|| unclaimed.to_vec(), |root| vec![root])
+    }
+}
+
+/// The layout roots that aren't being shown by a specific session.
+pub(crate) fn unclaimed_roots<'a>(
+    all_roots: &[Entity],
+    sessions: impl IntoIterator<Item = &'a TuiSession>,
+) -> Vec<Entity> {
+    let claimed: Vec<Entity> = sessions
+        .into_iter()
+        .filter_map(|session| session.root)
+        .collect();
+
+    all_roots
+        .iter()
+        .copied()
+        .filter(|root| !claimed.contains(root))
+        .collect()
+}
+
+/// The keyboard and mouse state of a single [`TuiSession`], the per-session equivalent of the
+/// global `ButtonInput<KeyCode>` and `ButtonInput<MouseButton>` resources and [`MouseInput`]
+/// events.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn refresh_pressed(sessions: Query<(Entity, &SessionInput)>) {
+///     for (session, input) in &sessions {
+///         if input.keys().just_pressed(KeyCode::KeyR) {
+///             println!("{session:?} asked for a refresh");
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Debug, Default)]
+pub struct SessionInput {
+    keys: ButtonInput<KeyCode>,
+    mouse_buttons: ButtonInput<MouseButton>,
+    mouse_events: Vec<MouseInput>,
+}
+
+impl SessionInput {
+    /// The keys pressed in this session.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_tui::prelude::*;
+    ///
+    /// assert!(!SessionInput::default().keys().pressed(KeyCode::KeyQ));
+    /// ```
+    #[must_use]
+    pub fn keys(&self) -> &ButtonInput<KeyCode> {
+        &self.keys
+    }
+
+    /// The mouse buttons pressed in this session.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_tui::prelude::*;
+    ///
+    /// assert!(!SessionInput::default().mouse_buttons().pressed(MouseButton::Left));
+    /// ```
+    #[must_use]
+    pub fn mouse_buttons(&self) -> &ButtonInput<MouseButton> {
+        &self.mouse_buttons
+    }
+
+    /// The mouse events received for this session during the current frame, with positions
+    /// relative to the session's own terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_tui::prelude::*;
+    ///
+    /// fn log_scrolling(sessions: Query<(Entity, &SessionInput)>) {
+    ///     for (session, input) in &sessions {
+    ///         for event in input.mouse_events() {
+    ///             if let MouseInput::Scroll([_, delta], _) = event {
+    ///                 println!("{session:?} scrolled by {delta}");
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn mouse_events(&self) -> &[MouseInput] {
+        &self.mouse_events
+    }
+}
+
+/// Everything a [`TuiSession`] entity needs, drawing to the provided terminal.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::backend::CrosstermBackend;
+/// use ratatui::layout::Rect;
+/// use ratatui::{TerminalOptions, Viewport};
+///
+/// fn spawn_session(mut commands: Commands) {
+///     let output = TerminalOutput::writer(Vec::new());
+///     let options = TerminalOptions {
+///         viewport: Viewport::Fixed(Rect::new(0, 0, 80, 24)),
+///     };
+///     let backend = ColorFilterBackend::new(CrosstermBackend::new(output));
+///     let terminal = ratatui::Terminal::with_options(backend, options)
+///         .expect("fixed viewports don't query the terminal");
+///
+///     commands.spawn(SessionBundle::new(bevy_tui::Terminal(terminal)));
+/// }
+/// ```
+#[derive(Bundle)]
+pub struct SessionBundle {
+    /// The session marker and the root it displays.
+    pub session: TuiSession,
+
+    /// The terminal the session draws to.
+    pub terminal: BevyTerminal,
+
+    /// The entity with focus in this session.
+    pub focused: FocusedEntity,
+
+    /// The keyboard and mouse state of this session.
+    pub input: SessionInput,
+
+    /// The areas the nodes shown in this session were laid out with.
+    pub areas: LayoutAreas,
+}
+
+impl SessionBundle {
+    /// Create a session showing the unclaimed layout roots on the provided terminal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::backend::CrosstermBackend;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::{TerminalOptions, Viewport};
+    ///
+    /// let output = TerminalOutput::writer(Vec::new());
+    /// let options = TerminalOptions {
+    ///     viewport: Viewport::Fixed(Rect::new(0, 0, 80, 24)),
+    /// };
+    /// let backend = ColorFilterBackend::new(CrosstermBackend::new(output));
+    /// let terminal = ratatui::Terminal::with_options(backend, options)?;
+    /// SessionBundle::new(bevy_tui::Terminal(terminal));
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[must_use]
+    pub fn new(terminal: BevyTerminal) -> Self {
+        Self {
+            session: TuiSession::default(),
+            terminal,
+            focused: FocusedEntity::default(),
+            input: SessionInput::default(),
+            areas: LayoutAreas::default(),
+        }
+    }
+}
+
+/// How a server sends output to one of its clients, such as over telnet or SSH. Output is sent
+/// from a thread of the client's own, so these are free to block for up to [`WRITE_TIMEOUT`].
+pub(crate) trait ClientTransport: Send + Sync + 'static {
+    /// Send output to the client, failing once it can't be written to.
+    fn send(&self, data: Vec<u8>) -> io::Result<()>;
+
+    /// Send the last of the output for the client and close the connection.
+    fn close(&self, message: Vec<u8>);
+
+    /// Drop the connection straight away, waking up a thread blocked on sending to it.
+    fn abort(&self);
+}
+
+/// What the thread sending output to a client is asked to do.
+enum ClientOutput {
+    /// Send the bytes to the client.
+    Data(Vec<u8>),
+
+    /// Wake up to close the connection as asked through [`ClientShared::closing`].
+    Close,
+}
+
+/// How the connection to a client should be closed.
+enum Closing {
+    /// Send a final message, such as one restoring the client's terminal, before closing.
+    Message(Vec<u8>),
+
+    /// Drop the connection without sending anything more.
+    Abort,
+}
+
+/// The parts of a client's connection shared with the thread sending its output.
+struct ClientShared<T> {
+    transport: T,
+    open: AtomicBool,
+
+    /// Set when the connection is closed, and taken by the thread sending output. Output still
+    /// waiting to be sent at that point is discarded.
+    closing: Mutex<Option<Closing>>,
+
+    /// Set once the thread sending output has finished with the connection.
+    finished: Mutex<bool>,
+    finished_changed: Condvar,
+}
+
+/// A shared handle on a client connected to one of the servers. Output is queued for a thread of
+/// its own so a client that is slow to receive it can't hold up the app, and is discarded once
+/// the connection has been closed.
+pub(crate) struct RemoteClient<T> {
+    shared: Arc<ClientShared<T>>,
+    output: SyncSender<ClientOutput>,
+}
+
+impl<T> Clone for RemoteClient<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: Arc::clone(&self.shared),
+            output: self.output.clone(),
+        }
+    }
+}
+
+impl<T: ClientTransport> RemoteClient<T> {
+    /// Start sending output to the client through the transport.
+    pub(crate) fn new(transport: T) -> Self {
+        let shared = Arc::new(ClientShared {
+            transport,
+            open: AtomicBool::new(true),
+            closing: Mutex::new(None),
+            finished: Mutex::new(false),
+            finished_changed: Condvar::new(),
+        });
+
+        let (output, queued) = sync_channel(QUEUED_OUTPUT);
+        let writer_shared = Arc::clone(&shared);
+        std::thread::spawn(move || write_client(&writer_shared, &queued));
+
+        Self { shared, output }
+    }
+
+    /// Whether the client is still connected.
+    pub(crate) fn is_open(&self) -> bool {
+        self.shared.open.load(Ordering::Relaxed)
+    }
+
+    /// Queue output to be sent, waiting for room in the queue. Returns whether the client is still
+    /// connected.
+    pub(crate) fn send(&self, data: Vec<u8>) -> bool {
+        self.is_open() && self.output.send(ClientOutput::Data(data)).is_ok()
+    }
+
+    /// Queue output to be sent if there is room for it, handing it back otherwise.
+    fn try_send(&self, data: Vec<u8>) -> Result<(), Vec<u8>> {
+        match self.output.try_send(ClientOutput::Data(data)) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(ClientOutput::Data(data))) => Err(data),
+            Err(_) => {
+                self.closed();
+                Ok(())
+            }
+        }
+    }
+
+    /// Send a final message to the client and close the connection, once the output currently
+    /// being sent has been.
+    pub(crate) fn close_with(&self, message: Vec<u8>) {
+        if self.shared.open.swap(false, Ordering::Relaxed) {
+            self.close(Closing::Message(message));
+        }
+    }
+
+    /// Stop sending to the client, used once it has gone away.
+    pub(crate) fn closed(&self) {
+        self.shared.open.store(false, Ordering::Relaxed);
+        self.close(Closing::Abort);
+        self.shared.transport.abort();
+    }
+
+    fn close(&self, closing: Closing) {
+        if let Ok(mut pending) = self.shared.closing.lock() {
+            *pending = Some(closing);
+        }
+
+        // A full queue is fine, the thread checks for the connection being closed after each
+        // output it sends
+        let _ = self.output.try_send(ClientOutput::Close);
+    }
+
+    /// Restore the client's terminal and close the connection.
+    pub(crate) fn disconnect(&self) {
+        let mut restore = Vec::new();
+        let _ = restore_remote(&mut restore);
+        self.close_with(restore);
+    }
+
+    /// Wait up to the timeout for the connection to be closed after [`RemoteClient::disconnect`].
+    fn wait_disconnected(&self, timeout: Duration) {
+        let Ok(finished) = self.shared.finished.lock() else {
+            return;
+        };
+
+        let _ = self
+            .shared
+            .finished_changed
+            .wait_timeout_while(finished, timeout, |finished| !*finished);
+    }
+}
+
+/// Sends the output queued for a client until the connection is closed. A client that can't be
+/// written to, or has stopped reading long enough for a write to time out, has gone away and its
+/// session will be cleaned up.
+fn write_client<T: ClientTransport>(shared: &ClientShared<T>, queued: &Receiver<ClientOutput>) {
+    while let Ok(output) = queued.recv() {
+        let closing = shared
+            .closing
+            .lock()
+            .ok()
+            .and_then(|mut closing| closing.take());
+
+        match (closing, output) {
+            (Some(Closing::Message(message)), _) => {
+                shared.transport.close(message);
+                break;
+            }
+            (Some(Closing::Abort), _) => break,
+            (None, ClientOutput::Data(data)) => {
+                if shared.transport.send(data).is_err() {
+                    break;
+                }
+            }
+            (None, ClientOutput::Close) => {}
+        }
+    }
+
+    shared.open.store(false, Ordering::Relaxed);
+    shared.transport.abort();
+
+    if let Ok(mut finished) = shared.finished.lock() {
+        *finished = true;
+        shared.finished_changed.notify_all();
+    }
+}
+
+/// The output of a client's terminal. Frames drawn while the client is still receiving earlier
+/// ones are held back and sent along with the next.
+pub(crate) struct ClientWriter<T> {
+    client: RemoteClient<T>,
+    held: Vec<u8>,
+}
+
+impl<T: ClientTransport> ClientWriter<T> {
+    pub(crate) fn new(client: RemoteClient<T>) -> Self {
+        Self {
+            client,
+            held: Vec::new(),
+        }
+    }
+}
+
+impl<T: ClientTransport> Write for ClientWriter<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.client.is_open() {
+            self.held.extend_from_slice(buf);
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.client.is_open() {
+            self.held.clear();
+            return Ok(());
+        }
+
+        if self.held.is_empty() {
+            return Ok(());
+        }
+
+        if let Err(held) = self.client.try_send(std::mem::take(&mut self.held)) {
+            self.held = held;
+
+            // A client this far behind has stopped reading, and its session will be cleaned up
+            if self.held.len() > MAX_HELD_OUTPUT {
+                self.held.clear();
+                self.client.disconnect();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The clients connected to a server, each with the session it was given.
+pub(crate) struct RemoteClients<T>(Vec<(Entity, RemoteClient<T>)>);
+
+impl<T> Default for RemoteClients<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T: ClientTransport> RemoteClients<T> {
+    /// Whether another client can be given a session, or should be turned away with
+    /// [`TOO_MANY_CLIENTS`].
+    pub(crate) fn accepts(&self, max_clients: usize) -> bool {
+        self.0.len() < max_clients
+    }
+
+    pub(crate) fn push(&mut self, session: Entity, client: RemoteClient<T>) {
+        self.0.push((session, client));
+    }
+
+    pub(crate) fn sessions(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.0.iter().map(|(session, _)| *session)
+    }
+
+    pub(crate) fn disconnect(&self, session: Entity) {
+        for (_, client) in self.0.iter().filter(|(s, _)| *s == session) {
+            client.disconnect();
+        }
+    }
+}
+
+/// A server giving each of its clients a session.
+pub(crate) trait RemoteServer: Resource {
+    type Transport: ClientTransport;
+
+    fn clients(&self) -> &RemoteClients<Self::Transport>;
+
+    fn clients_mut(&mut self) -> &mut RemoteClients<Self::Transport>;
+}
+
+/// Despawns the sessions of clients that have disconnected, and disconnects clients whose session
+/// has been despawned by the app.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn remote_cleanup_system<S: RemoteServer>(
+    mut commands: Commands,
+    mut server: ResMut<S>,
+    sessions: Query<(), With<TuiSession>>,
+    mut ended: EventWriter<SessionEnded>,
+) {
+    server.clients_mut().0.retain(|(session, client)| {
+        let exists = sessions.contains(*session);
+        if exists && client.is_open() {
+            return true;
+        }
+
+        client.disconnect();
+        if exists {
+            commands.entity(*session).despawn();
+        }
+        ended.send(SessionEnded { session: *session });
+
+        false
+    });
+}
+
+/// Restores the terminals of all the clients when the app exits, waiting a limited time for those
+/// disconnecting in the background.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn remote_exit_system<S: RemoteServer>(
+    mut exit_events: EventReader<AppExit>,
+    server: Res<S>,
+) {
+    if exit_events.read().count() == 0 {
+        return;
+    }
+
+    let clients = &server.clients().0;
+    for (_, client) in clients {
+        client.disconnect();
+    }
+
+    let deadline = Instant::now() + WRITE_TIMEOUT;
+    for (_, client) in clients {
+        client.wait_disconnected(deadline.saturating_duration_since(Instant::now()));
+    }
+}
+
+/// Create a terminal drawing to a remote client, sized to the client's window.
+pub(crate) fn remote_terminal(
+    output: impl Write + Send + Sync + 'static,
+    (width, height): (u16, u16),
+) -> BevyTerminal {
+    let output = TerminalOutput::writer(BufWriter::new(output));
+    let options = TerminalOptions {
+        viewport: Viewport::Fixed(Rect::new(0, 0, width, height)),
+    };
+
+    let backend = ColorFilterBackend::new(CrosstermBackend::new(output));
+    let terminal = ratatui::Terminal::with_options(backend, options)
+        .expect("fixed viewports don't query the terminal");
+
+    Terminal(terminal)
+}
+
+/// Switch a remote client's terminal over to a blank screen accepting mouse and paste input, the
+/// same as [`initialize_terminal`](crate::prelude::initialize_terminal) does locally.
+pub(crate) fn prepare_remote(output: &mut impl Write) -> io::Result<()> {
+    output.queue(EnterAlternateScreen)?;
+    output.queue(EnableBracketedPaste)?;
+    output.queue(EnableMouseCapture)?;
+    output.queue(Clear(ClearType::All))?;
+    output.queue(Hide)?;
+
+    output.flush()
+}
+
+/// Undo [`prepare_remote`], returning the client's terminal to the state it was in.
+pub(crate) fn restore_remote(output: &mut impl Write) -> io::Result<()> {
+    output.queue(Show)?;
+    output.queue(DisableMouseCapture)?;
+    output.queue(DisableBracketedPaste)?;
+    output.queue(LeaveAlternateScreen)?;
+
+    output.flush()
+}
+
+/// Replace a session's terminal with one of the provided size drawing to the same output. The
+/// next frame will be drawn in full.
+fn resize_terminal(terminal: &mut BevyTerminal, width: u16, height: u16) {
+    let placeholder =
+        ColorFilterBackend::new(CrosstermBackend::new(TerminalOutput::writer(io::sink())));
+    let backend = std::mem::replace(terminal.0.backend_mut(), placeholder);
+    let options = TerminalOptions {
+        viewport: Viewport::Fixed(Rect::new(0, 0, width, height)),
+    };
+
+    terminal.0 = ratatui::Terminal::with_options(backend, options)
+        .expect("fixed viewports don't query the terminal");
+
+    // The new terminal has no record of what the session is showing
+    let _ = terminal.0.clear();
+}
+
+/// The parts of a session updated from the events received for it.
+type SessionState = (
+    Entity,
+    &'static TuiSession,
+    &'static mut FocusedEntity,
+    &'static mut SessionInput,
+    &'static mut BevyTerminal,
+    Option<&'static LayoutAreas>,
+);
+
+/// Updates the size, focus, and input state of every session from the events received for it.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn session_input_system(
+    mut session_events: EventReader<SessionEvent>,
+    mut sessions: Query<SessionState>,
+    mut params: FocusParams,
+) {
+    let received: Vec<&SessionEvent> = session_events.read().collect();
+
+    let unclaimed = unclaimed_roots(
+        &params.all_roots(),
+        sessions.iter().map(|(_, session, ..)| session),
+    );
+
+    for (entity, session, mut focused, mut input, mut terminal, areas) in &mut sessions {
+        let events: Vec<&CrossEvent> = received
+            .iter()
+            .filter(|received| received.session == entity)
+            .map(|received| &received.event)
+            .collect();
+
+        for event in &events {
+            if let CrossEvent::Resize(width, height) = event {
+                resize_terminal(&mut terminal, *width, *height);
+            }
+        }
+
+        let captured = params.route(
+            &mut focused,
+            &session.roots(&unclaimed),
+            areas.unwrap_or(&LayoutAreas::default()),
+            events.iter().copied(),
+        );
+
+        let key_events: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                CrossEvent::Key(key) => Some(convert_keyboard_input(*key)),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        update_keys(&mut input.keys, &key_events, captured);
+
+        input.mouse_buttons.clear();
+        input.mouse_events.clear();
+        for event in &events {
+            let CrossEvent::Mouse(mouse) = event else {
+                continue;
+            };
+
+            let mouse_input = convert_mouse_input(*mouse);
+            if let MouseInput::Button(button, state, _) = mouse_input {
+                match state {
+                    ButtonState::Pressed => input.mouse_buttons.press(button),
+                    ButtonState::Released => input.mouse_buttons.release(button),
+                }
+            }
+            input.mouse_events.push(mouse_input);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// How long a test waits for the thread sending output before giving up.
+    const TEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[derive(Default)]
+    struct Recorded {
+        /// Whether output can be sent, until then sending blocks the same as a client that isn't
+        /// reading.
+        released: bool,
+        sending: usize,
+        sent: Vec<Vec<u8>>,
+        closed_with: Option<Vec<u8>>,
+        aborted: bool,
+    }
+
+    /// A transport recording what is sent through it, and only sending once released.
+    #[derive(Clone, Default)]
+    struct RecordingTransport(Arc<(Mutex<Recorded>, Condvar)>);
+
+    impl RecordingTransport {
+        fn release(&self) {
+            self.0 .0.lock().expect("not poisoned").released = true;
+            self.0 .1.notify_all();
+        }
+
+        /// Wait for the check to pass on what has been recorded.
+        fn wait_until(&self, check: impl Fn(&Recorded) -> bool) {
+            let (recorded, changed) = &*self.0;
+            let recorded = recorded.lock().expect("not poisoned");
+            let (recorded, _) = changed
+                .wait_timeout_while(recorded, TEST_TIMEOUT, |recorded| !check(recorded))
+                .expect("not poisoned");
+
+            assert!(check(&recorded), "timed out, sent {:?}", recorded.sent);
+        }
+
+        fn sent(&self) -> Vec<Vec<u8>> {
+            self.0 .0.lock().expect("not poisoned").sent.clone()
+        }
+    }
+
+    impl ClientTransport for RecordingTransport {
+        fn send(&self, data: Vec<u8>) -> io::Result<()> {
+            let (recorded, changed) = &*self.0;
+            let mut recorded = recorded.lock().expect("not poisoned");
+            recorded.sending += 1;
+            changed.notify_all();
+
+            let mut recorded = changed
+                .wait_while(recorded, |recorded| !recorded.released && !recorded.aborted)
+                .expect("not poisoned");
+
+            if recorded.aborted {
+                return Err(io::ErrorKind::BrokenPipe.into());
+            }
+
+            recorded.sent.push(data);
+            changed.notify_all();
+            Ok(())
+        }
+
+        fn close(&self, message: Vec<u8>) {
+            self.0 .0.lock().expect("not poisoned").closed_with = Some(message);
+            self.0 .1.notify_all();
+        }
+
+        fn abort(&self) {
+            self.0 .0.lock().expect("not poisoned").aborted = true;
+            self.0 .1.notify_all();
+        }
+    }
+
+    /// A client whose transport is stuck sending the first frame, with the queue behind it full.
+    fn client_behind() -> (RecordingTransport, RemoteClient<RecordingTransport>) {
+        let transport = RecordingTransport::default();
+        let client = RemoteClient::new(transport.clone());
+        let mut writer = ClientWriter::new(client.clone());
+
+        writer.write_all(b"1").expect("writes are buffered");
+        writer.flush().expect("flushing doesn't fail");
+        transport.wait_until(|recorded| recorded.sending == 1);
+
+        for frame in [b"2", b"3"] {
+            writer.write_all(frame).expect("writes are buffered");
+            writer.flush().expect("flushing doesn't fail");
+        }
+
+        (transport, client)
+    }
+
+    fn restore() -> Vec<u8> {
+        let mut restore = Vec::new();
+        restore_remote(&mut restore).expect("writing to a vec doesn't fail");
+        restore
+    }
+
+    #[test]
+    fn holds_back_frames_while_the_client_is_behind() {
+        let (transport, client) = client_behind();
+        let mut writer = ClientWriter::new(client.clone());
+
+        // Neither of these fit in the queue and wait to be sent together
+        for frame in [b"4", b"5"] {
+            writer.write_all(frame).expect("writes are buffered");
+            writer.flush().expect("flushing doesn't fail");
+        }
+        assert!(client.is_open());
+
+        transport.release();
+        transport.wait_until(|recorded| recorded.sent.len() == 3);
+
+        writer.flush().expect("flushing doesn't fail");
+        transport.wait_until(|recorded| recorded.sent.len() == 4);
+
+        assert_eq!(transport.sent(), [&b"1"[..], b"2", b"3", b"45"]);
+    }
+
+    #[test]
+    fn disconnects_a_client_that_stops_reading() {
+        let (transport, client) = client_behind();
+        let mut writer = ClientWriter::new(client.clone());
+
+        writer
+            .write_all(&vec![b'x'; MAX_HELD_OUTPUT + 1])
+            .expect("writes are buffered");
+        writer.flush().expect("flushing doesn't fail");
+        assert!(!client.is_open());
+
+        // Nothing more is held back once disconnected
+        writer.write_all(b"6").expect("writes are buffered");
+        writer.flush().expect("flushing doesn't fail");
+
+        // The terminal is still restored, skipping the frames waiting behind the stuck one
+        transport.release();
+        client.wait_disconnected(TEST_TIMEOUT);
+        transport.wait_until(|recorded| recorded.closed_with.is_some());
+
+        assert_eq!(transport.sent(), [b"1"]);
+        let recorded = transport.0 .0.lock().expect("not poisoned");
+        assert_eq!(recorded.closed_with, Some(restore()));
+    }
+
+    #[test]
+    fn disconnecting_sends_the_restore_after_the_current_output() {
+        let transport = RecordingTransport::default();
+        let client = RemoteClient::new(transport.clone());
+        transport.release();
+
+        assert!(client.send(b"frame".to_vec()));
+        transport.wait_until(|recorded| recorded.sent.len() == 1);
+
+        client.disconnect();
+        client.wait_disconnected(TEST_TIMEOUT);
+
+        assert!(!client.send(b"late".to_vec()));
+        let recorded = transport.0 .0.lock().expect("not poisoned");
+        assert_eq!(recorded.sent, [b"frame"]);
+        assert_eq!(recorded.closed_with, Some(restore()));
+        assert!(recorded.aborted);
+    }
+
+    #[test]
+    fn a_client_that_went_away_is_dropped_without_a_message() {
+        let (transport, client) = client_behind();
+
+        client.closed();
+        client.wait_disconnected(TEST_TIMEOUT);
+
+        assert!(!client.is_open());
+        let recorded = transport.0 .0.lock().expect("not poisoned");
+        assert_eq!(recorded.sent, Vec::<Vec<u8>>::new());
+        assert_eq!(recorded.closed_with, None);
+        assert!(recorded.aborted);
+    }
+}
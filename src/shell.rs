@@ -0,0 +1,196 @@
+use std::path::Path;
+use std::process::{Command as ProcessCommand, Stdio};
+#[cfg(unix)]
+use std::sync::atomic::Ordering;
+
+use bevy::ecs::system::Command;
+use bevy::ecs::world::World;
+use bevy::utils::tracing::error;
+
+use crate::events::ShellOutFinished;
+use crate::output::{terminal_stdio, TuiOutput};
+#[cfg(unix)]
+use crate::quit::QuitSignals;
+#[cfg(unix)]
+use crate::suspend::SuspendSignals;
+use crate::terminal_helpers::{reclaim_terminal, release_terminal, TuiTerminalSettings};
+use crate::BevyTerminal;
+
+/// The editor used by [`ShellOut::editor`] when neither `VISUAL` nor `EDITOR` are set.
+#[cfg(unix)]
+const DEFAULT_EDITOR: &str = "vi";
+#[cfg(not(unix))]
+const DEFAULT_EDITOR: &str = "notepad";
+
+/// A command that hands the terminal over to another program, such as an editor or a pager, and
+/// waits for it to exit. The terminal is restored the same way the teardown functions would before
+/// the program starts, and initialized again afterwards with the whole screen redrawn. The
+/// program's exit status is sent as a [`ShellOutFinished`] event.
+///
+/// The program inherits the standard input and output of the application, unless the
+/// [`TuiTerminalSettings::output`] is something other than STDOUT. STDOUT has likely been
+/// redirected then, and the program gets the controlling terminal for all three instead. The
+/// application doesn't update while the program runs.
+///
+/// Ctrl+C and Ctrl+Z pressed while the program runs are left to it. The application ignores
+/// `SIGINT`, `SIGTERM` and `SIGHUP` until the program exits, including those sent from elsewhere
+/// such as with `kill`, and follows them again afterwards. Ctrl+Z stops the application along with
+/// the program, so `fg` in the shell continues them both.
+///
+/// # Examples
+///
+/// Opening a file in a pager when a key is pressed:
+///
+/// ```
+/// use std::process::Command;
+///
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn view_log(mut commands: Commands, keys: Res<ButtonInput<KeyCode>>) {
+///     if keys.just_pressed(KeyCode::KeyL) {
+///         let mut pager = Command::new("less");
+///         pager.arg("app.log");
+///
+///         commands.add(ShellOut::new(pager));
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ShellOut {
+    command: ProcessCommand,
+}
+
+impl ShellOut {
+    /// Run the program described by the [`Command`](std::process::Command) once the commands are
+    /// applied. Any standard input or output configured on it is replaced with the terminal the
+    /// application is drawn on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::process::Command;
+    ///
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let shell = ShellOut::new(Command::new("sh"));
+    /// ```
+    #[must_use]
+    pub fn new(command: ProcessCommand) -> Self {
+        Self { command }
+    }
+
+    /// Open a file in the user's preferred editor, taken from the `VISUAL` or `EDITOR` environment
+    /// variables in that order. These may include arguments for the editor, which are separated by
+    /// whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_tui::prelude::*;
+    ///
+    /// fn edit_config(mut commands: Commands) {
+    ///     commands.add(ShellOut::editor("config.toml"));
+    /// }
+    /// ```
+    #[must_use]
+    pub fn editor(path: impl AsRef<Path>) -> Self {
+        let editor = ["VISUAL", "EDITOR"]
+            .into_iter()
+            .filter_map(std::env::var_os)
+            .find(|editor| !editor.is_empty())
+            .unwrap_or_else(|| DEFAULT_EDITOR.into());
+
+        let editor = editor.to_string_lossy();
+        let mut words = editor.split_whitespace();
+
+        let mut command = ProcessCommand::new(words.next().unwrap_or(DEFAULT_EDITOR));
+        command.args(words).arg(path.as_ref());
+
+        Self { command }
+    }
+}
+
+impl From<ProcessCommand> for ShellOut {
+    fn from(command: ProcessCommand) -> Self {
+        Self::new(command)
+    }
+}
+
+impl Command for ShellOut {
+    fn apply(mut self, world: &mut World) {
+        let settings = world.resource::<TuiTerminalSettings>().clone();
+
+        #[cfg(unix)]
+        {
+            if let Some(mut signals) = world.get_resource_mut::<QuitSignals>() {
+                signals.pause();
+            }
+
+            if let Some(signals) = world.get_resource::<SuspendSignals>() {
+                signals.shelled_out.store(true, Ordering::Relaxed);
+            }
+        }
+
+        // Without a terminal of its own the application is serving remote sessions, and the
+        // program gets whatever the process was started with
+        let mut terminal_streams = None;
+        if let Some(mut terminal) = world.get_resource_mut::<BevyTerminal>() {
+            if let Err(err) = release_terminal(&settings, &mut terminal) {
+                error!("failed to restore the terminal: {err}");
+            }
+
+            if settings.output != TuiOutput::Stdout {
+                match terminal_stdio() {
+                    Ok(streams) => terminal_streams = Some(streams),
+                    Err(err) => error!("failed to open the terminal for the program: {err}"),
+                }
+            }
+        }
+
+        let [stdin, stdout, stderr] = terminal_streams
+            .unwrap_or_else(|| [Stdio::inherit(), Stdio::inherit(), Stdio::inherit()]);
+        let status = self
+            .command
+            .stdin(stdin)
+            .stdout(stdout)
+            .stderr(stderr)
+            .status();
+
+        if let Some(mut terminal) = world.get_resource_mut::<BevyTerminal>() {
+            if let Err(err) = reclaim_terminal(&settings, &mut terminal) {
+                error!("failed to reinitialize the terminal: {err}");
+            }
+        }
+
+        #[cfg(unix)]
+        {
+            if let Some(mut signals) = world.get_resource_mut::<QuitSignals>() {
+                signals.resume();
+            }
+
+            // Being stopped and continued along with the program isn't a suspend of the
+            // application's own, and the terminal has already been taken back
+            if let Some(signals) = world.get_resource::<SuspendSignals>() {
+                signals.shelled_out.store(false, Ordering::Relaxed);
+                signals.stop.store(false, Ordering::Relaxed);
+                signals.continued.store(false, Ordering::Relaxed);
+            }
+        }
+
+        let program = self.command.get_program().to_string_lossy().into_owned();
+        world.send_event(match status {
+            Ok(status) => ShellOutFinished {
+                program,
+                code: status.code(),
+                error: None,
+            },
+            Err(err) => ShellOutFinished {
+                program,
+                code: None,
+                error: Some(err.to_string()),
+            },
+        });
+    }
+}
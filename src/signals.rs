@@ -0,0 +1,105 @@
+use std::os::raw::c_int;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use bevy::utils::tracing::warn;
+use signal_hook::SigId;
+
+/// Every signal the application has taken over, along with how many [`SignalHandlers`] are
+/// currently holding it.
+static HELD_SIGNALS: Mutex<Vec<HeldSignal>> = Mutex::new(Vec::new());
+
+/// A signal along with the flag that has its fallback handler act on it the default way.
+struct HeldSignal {
+    signal: c_int,
+    holders: usize,
+    released: Arc<AtomicBool>,
+}
+
+fn held_signals() -> MutexGuard<'static, Vec<HeldSignal>> {
+    // Nothing can be left half updated by a panic while the lock is held
+    HELD_SIGNALS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Handlers installed for a set of signals on behalf of the application.
+///
+/// signal-hook never removes its own handler from the process once installed, so unregistering
+/// the last handler of a signal leaves it ignored rather than restoring its default behavior. A
+/// fallback handler is registered ahead of all the others the first time a signal is held, acting
+/// on it the default way whenever no [`SignalHandlers`] are holding it any longer. While held,
+/// the signal only does what the registered handlers do, which is nothing at all after
+/// [`SignalHandlers::clear`].
+pub(crate) struct SignalHandlers {
+    signals: Vec<c_int>,
+    handlers: Vec<SigId>,
+}
+
+impl SignalHandlers {
+    /// Take over the signals until these are dropped, before registering any handlers for them.
+    pub(crate) fn hold(signals: &[c_int]) -> Self {
+        let mut held = held_signals();
+        let mut holding = Vec::new();
+
+        for &signal in signals {
+            if let Some(existing) = held.iter_mut().find(|held| held.signal == signal) {
+                existing.holders += 1;
+                existing.released.store(false, Ordering::Relaxed);
+                holding.push(signal);
+                continue;
+            }
+
+            let released = Arc::new(AtomicBool::new(false));
+            match signal_hook::flag::register_conditional_default(signal, Arc::clone(&released)) {
+                Ok(_) => {
+                    held.push(HeldSignal {
+                        signal,
+                        holders: 1,
+                        released,
+                    });
+                    holding.push(signal);
+                }
+                Err(err) => warn!("failed to handle signal {signal}: {err}"),
+            }
+        }
+
+        Self {
+            signals: holding,
+            handlers: Vec::new(),
+        }
+    }
+
+    /// Keep a handler registered for one of the held signals until these are cleared or dropped.
+    pub(crate) fn push(&mut self, handler: SigId) {
+        self.handlers.push(handler);
+    }
+
+    /// Whether none of the registered handlers are left.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+
+    /// Unregister all the handlers while still holding the signals, leaving them ignored.
+    pub(crate) fn clear(&mut self) {
+        for handler in self.handlers.drain(..) {
+            signal_hook::low_level::unregister(handler);
+        }
+    }
+}
+
+impl Drop for SignalHandlers {
+    fn drop(&mut self) {
+        self.clear();
+
+        let mut held = held_signals();
+        for signal in self.signals.drain(..) {
+            let Some(existing) = held.iter_mut().find(|held| held.signal == signal) else {
+                continue;
+            };
+
+            existing.holders -= 1;
+            if existing.holders == 0 {
+                existing.released.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+}
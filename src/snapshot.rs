@@ -0,0 +1,316 @@
+use std::fmt::Write as _;
+use std::path::Path;
+
+use bevy::ecs::entity::Entity;
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::world::World;
+use bevy::hierarchy::Parent;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier};
+use ratatui::{Terminal, TerminalOptions, Viewport};
+use unicode_width::UnicodeWidthStr;
+
+use crate::color::ColorFilterBackend;
+use crate::focus::FocusedEntity;
+use crate::layout::{layout_roots, TuiNode};
+use crate::render::{draw, FrameResources};
+use crate::session::{unclaimed_roots, TuiSession};
+
+/// The environment variable that, when set to anything other than `0`, has
+/// [`assert_snapshot`] write the snapshot instead of comparing against it.
+const UPDATE_VAR: &str = "BEVY_TUI_UPDATE_SNAPSHOTS";
+
+/// Lay out and draw the nodes that would be shown on the terminal the process is attached to into
+/// a blank [`Buffer`] of the provided size, the same way a frame is drawn by the plugin. No
+/// terminal is needed, making this suitable for tests.
+///
+/// The [`TuiNode`] areas are computed for the size of the buffer, and the widgets, theme and
+/// render settings registered in the world are used as they would be for a real frame.
+///
+/// # Panics
+///
+/// Panics if a widget does while it is being drawn, which would have ended the application had it
+/// been drawing to a real terminal.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::Paragraph;
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalTuiPlugins);
+/// app.world.spawn(ParagraphBundle {
+///     paragraph: TuiParagraph(Paragraph::new("Hello Bevy!")),
+///     ..default()
+/// });
+///
+/// let buffer = render_buffer(&mut app.world, 20, 2);
+/// assert_eq!(buffer_snapshot(&buffer), "Hello Bevy!\n\n");
+/// ```
+pub fn render_buffer(world: &mut World, width: u16, height: u16) -> Buffer {
+    let area = Rect::new(0, 0, width, height);
+    let options = TerminalOptions {
+        viewport: Viewport::Fixed(area),
+    };
+    let backend = ColorFilterBackend::new(TestBackend::new(width, height));
+    let mut terminal =
+        Terminal::with_options(backend, options).expect("test backends can always be created");
+
+    let resources = FrameResources::from_world(world);
+    let all_roots: Vec<Entity> = world
+        .query_filtered::<Entity, (With<TuiNode>, Without<Parent>)>()
+        .iter(world)
+        .collect();
+    let sessions: Vec<TuiSession> = world.query::<&TuiSession>().iter(world).copied().collect();
+    let roots = unclaimed_roots(&all_roots, &sessions);
+    let focused = world
+        .get_resource::<FocusedEntity>()
+        .and_then(FocusedEntity::get);
+
+    layout_roots(world, &roots, area);
+    draw(world, &mut terminal, &roots, focused, &resources)
+        .expect("test backends can always be drawn to");
+
+    terminal.backend().inner().buffer().clone()
+}
+
+/// The text drawn into a buffer, one line per row with the trailing whitespace removed. This is
+/// the format [`assert_snapshot`] expects when only the content of the screen matters.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+/// use ratatui::buffer::Buffer;
+///
+/// let buffer = Buffer::with_lines(vec!["┌──┐", "│hi│", "└──┘"]);
+/// assert_eq!(buffer_snapshot(&buffer), "┌──┐\n│hi│\n└──┘\n");
+/// ```
+#[must_use]
+pub fn buffer_snapshot(buffer: &Buffer) -> String {
+    let mut snapshot = String::new();
+
+    for row in rows(buffer) {
+        let mut line = String::new();
+        let mut hidden = 0;
+
+        for cell in row {
+            // Wide characters cover the cells after them, which only hold padding
+            if hidden == 0 {
+                line.push_str(cell.symbol());
+            }
+            hidden = hidden.max(cell.symbol().width()).saturating_sub(1);
+        }
+
+        snapshot.push_str(line.trim_end());
+        snapshot.push('\n');
+    }
+
+    snapshot
+}
+
+/// The same as [`buffer_snapshot`] followed by a list of every styled run of cells, so changes to
+/// colors and modifiers are caught as well as changes to the text. Each run is written as the row,
+/// the range of columns it covers, and the parts of the style that differ from the default:
+///
+/// ```text
+/// 0:0..5 fg=Yellow modifiers=BOLD|ITALIC
+/// 2:3..8 bg=Rgb(30, 30, 46)
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+/// use ratatui::buffer::Buffer;
+/// use ratatui::style::{Style, Stylize};
+///
+/// let mut buffer = Buffer::with_lines(vec!["warning: low fuel"]);
+/// buffer.set_style(ratatui::layout::Rect::new(0, 0, 7, 1), Style::new().yellow().bold());
+///
+/// assert_eq!(
+///     styled_buffer_snapshot(&buffer),
+///     "warning: low fuel\n\nstyles:\n0:0..7 fg=Yellow modifiers=BOLD\n",
+/// );
+/// ```
+#[must_use]
+pub fn styled_buffer_snapshot(buffer: &Buffer) -> String {
+    let mut snapshot = buffer_snapshot(buffer);
+    snapshot.push_str("\nstyles:\n");
+
+    for (y, row) in rows(buffer).enumerate() {
+        let mut x = 0;
+
+        while x < row.len() {
+            let description = describe_style(&row[x]);
+            let end = x + row[x..]
+                .iter()
+                .take_while(|cell| describe_style(cell) == description)
+                .count();
+
+            if !description.is_empty() {
+                let _ = writeln!(snapshot, "{y}:{x}..{end} {description}");
+            }
+
+            x = end;
+        }
+    }
+
+    snapshot
+}
+
+/// Compare a snapshot produced by [`buffer_snapshot`] or [`styled_buffer_snapshot`] against the
+/// one stored in a file, typically checked in alongside the tests so changes to the interface show
+/// up in code review.
+///
+/// Setting the `BEVY_TUI_UPDATE_SNAPSHOTS` environment variable to anything other than `0` writes
+/// the snapshot to the file instead, creating it and any missing directories if needed. This is
+/// how snapshots are first recorded, and how they're accepted after an intended change:
+///
+/// ```sh
+/// BEVY_TUI_UPDATE_SNAPSHOTS=1 cargo test
+/// ```
+///
+/// # Panics
+///
+/// Panics with a line by line diff of the two if the snapshot doesn't match the stored one, or if
+/// the file can't be read or written.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::Paragraph;
+///
+/// let path = std::env::temp_dir().join("bevy_tui_greeting.txt");
+/// std::fs::write(&path, "Hello Bevy!\n")?;
+///
+/// let mut app = App::new();
+/// app.add_plugins(MinimalTuiPlugins);
+/// app.world.spawn(ParagraphBundle {
+///     paragraph: TuiParagraph(Paragraph::new("Hello Bevy!")),
+///     ..default()
+/// });
+///
+/// let buffer = render_buffer(&mut app.world, 20, 1);
+/// assert_snapshot(&buffer_snapshot(&buffer), &path);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn assert_snapshot(snapshot: &str, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+
+    if updating_snapshots() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap_or_else(|err| {
+                panic!(
+                    "failed to create the directory for {}: {err}",
+                    path.display()
+                )
+            });
+        }
+
+        std::fs::write(path, snapshot)
+            .unwrap_or_else(|err| panic!("failed to write {}: {err}", path.display()));
+
+        return;
+    }
+
+    let stored = match std::fs::read_to_string(path) {
+        Ok(stored) => stored,
+        Err(err) => panic!(
+            "failed to read the snapshot {}: {err}\n\nSet {UPDATE_VAR}=1 to create it from:\n\n\
+             {snapshot}",
+            path.display()
+        ),
+    };
+
+    // Checking out files on Windows can change the line endings
+    let stored = stored.replace("\r\n", "\n");
+
+    assert!(
+        stored == snapshot,
+        "the snapshot {} doesn't match (- stored, + actual):\n\n{}\n\
+         Set {UPDATE_VAR}=1 to accept the changes.",
+        path.display(),
+        line_diff(&stored, snapshot)
+    );
+}
+
+fn updating_snapshots() -> bool {
+    std::env::var_os(UPDATE_VAR).map_or(false, |value| !value.is_empty() && value != "0")
+}
+
+fn rows(buffer: &Buffer) -> impl Iterator<Item = &[Cell]> {
+    buffer
+        .content()
+        .chunks(usize::from(buffer.area.width).max(1))
+}
+
+/// The parts of a cell's style that differ from the default, or an empty string if none do.
+fn describe_style(cell: &Cell) -> String {
+    let style = cell.style();
+    let mut parts = Vec::new();
+
+    if let Some(color) = style.fg.filter(|color| *color != Color::Reset) {
+        parts.push(format!("fg={color:?}"));
+    }
+
+    if let Some(color) = style.bg.filter(|color| *color != Color::Reset) {
+        parts.push(format!("bg={color:?}"));
+    }
+
+    if let Some(color) = style.underline_color.filter(|color| *color != Color::Reset) {
+        parts.push(format!("underline={color:?}"));
+    }
+
+    if style.add_modifier != Modifier::empty() {
+        let modifiers = format!("{:?}", style.add_modifier).replace(' ', "");
+        parts.push(format!("modifiers={modifiers}"));
+    }
+
+    parts.join(" ")
+}
+
+/// A line by line diff showing the lines only in the expected text prefixed with `-`, the lines
+/// only in the actual text with `+`, and the lines common to both unmarked.
+fn line_diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // The length of the longest common subsequence of the remaining lines from each position
+    let mut common = vec![vec![0_usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            let _ = writeln!(diff, "  {}", expected[i]);
+            i += 1;
+            j += 1;
+        } else if j == actual.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1])
+        {
+            let _ = writeln!(diff, "- {}", expected[i]);
+            i += 1;
+        } else {
+            let _ = writeln!(diff, "+ {}", actual[j]);
+            j += 1;
+        }
+    }
+
+    diff
+}
@@ -0,0 +1,650 @@
+use std::future::{ready, Future};
+use std::io::{self, ErrorKind, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use bevy::app::{App, Last, Plugin, PreUpdate, Startup};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::event::EventWriter;
+use bevy::ecs::schedule::IntoSystemConfigs;
+use bevy::ecs::system::{Commands, Res, ResMut, Resource};
+use bevy::utils::tracing::error;
+use crossterm::event::Event as CrossEvent;
+use russh::keys::ssh_key::{AuthorizedKeys, PublicKey};
+use russh::server::{run_stream, Auth, ChannelOpenHandle, Config, Handler, Msg, Session};
+use russh::{Channel, ChannelId, ChannelOpenFailure, MethodKind, MethodSet};
+use tokio::runtime::Handle as RuntimeHandle;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::oneshot;
+
+use crate::events::{SessionEvent, SessionStarted};
+use crate::input::parser::InputParser;
+use crate::input::TuiInputSettings;
+use crate::scheduler::RemoteInput;
+use crate::session::{
+    prepare_remote, remote_cleanup_system, remote_exit_system, remote_terminal, ClientTransport,
+    ClientWriter, RemoteClient, RemoteClients, RemoteServer, SessionBundle, DEFAULT_SIZE,
+    TOO_MANY_CLIENTS, WRITE_TIMEOUT,
+};
+
+/// Configures the [`SshPlugin`]. This needs to be inserted before the app is run for it to have
+/// any effect.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// App::new()
+///     .add_plugins((MinimalTuiPlugins, SshPlugin))
+///     .insert_resource(SshSettings {
+///         address: ([0, 0, 0, 0], 2222).into(),
+///         max_clients: 4,
+///         host_key: "/etc/my_game/ssh_host_ed25519_key".into(),
+///         authorized_keys: "/etc/my_game/authorized_keys".into(),
+///     })
+///     .run();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Resource)]
+pub struct SshSettings {
+    /// The address to listen for connections on. Defaults to only accepting connections from the
+    /// local machine on port 2222.
+    pub address: SocketAddr,
+
+    /// How many clients can be connected at once, anyone connecting beyond this is turned away.
+    /// Defaults to a single client.
+    pub max_clients: usize,
+
+    /// The private key identifying the server to clients, in the OpenSSH format. One can be
+    /// created with `ssh-keygen -t ed25519 -N '' -f ssh_host_ed25519_key`. Defaults to
+    /// `ssh_host_ed25519_key` in the working directory.
+    pub host_key: PathBuf,
+
+    /// An OpenSSH `authorized_keys` file listing the public keys allowed to connect. It is read
+    /// again for every login attempt so keys can be added or revoked while the app is running.
+    /// Defaults to `authorized_keys` in the working directory.
+    pub authorized_keys: PathBuf,
+}
+
+impl Default for SshSettings {
+    fn default() -> Self {
+        Self {
+            address: ([127, 0, 0, 1], 2222).into(),
+            max_clients: 1,
+            host_key: "ssh_host_ed25519_key".into(),
+            authorized_keys: "authorized_keys".into(),
+        }
+    }
+}
+
+/// Serves the terminal UI to remote clients through an embedded SSH server instead of drawing to
+/// the terminal the process is attached to. This works the same as the
+/// [`TelnetPlugin`](crate::prelude::TelnetPlugin) but is encrypted, and only clients holding one
+/// of the keys listed in [`SshSettings::authorized_keys`] are let in. An operator can then connect
+/// with `ssh -p 2222 localhost` whenever needed.
+///
+/// Every client is given its own [`TuiSession`] entity once it asks for a shell, announced with a
+/// [`SessionStarted`] event and despawned after a [`SessionEnded`] event once the client
+/// disconnects. The session is sized to the pseudo-terminal requested by the client and resized
+/// whenever the client's window changes. A client can only open a single session per connection.
+///
+/// As the local terminal isn't used [`initialize_terminal`](crate::prelude::initialize_terminal)
+/// shouldn't be called, and color support isn't detected for remote clients.
+///
+/// When the host key can't be loaded or the address can't be listened on, the error is logged and
+/// the app runs without accepting any clients. [`SshServer::local_addr`] can be checked to tell
+/// whether the server has started.
+///
+/// [`SessionEnded`]: crate::prelude::SessionEnded
+/// [`TuiSession`]: crate::prelude::TuiSession
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// App::new()
+///     .add_plugins((MinimalTuiPlugins, SshPlugin))
+///     .run();
+/// ```
+pub struct SshPlugin;
+
+impl Plugin for SshPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SshSettings>()
+            .init_resource::<RemoteInput>()
+            .init_resource::<SshServer>()
+            .add_systems(Startup, ssh_listen_system)
+            .add_systems(
+                PreUpdate,
+                // Cleaning up first ensures new sessions have been spawned before being checked
+                (remote_cleanup_system::<SshServer>, ssh_accept_system).chain(),
+            )
+            .add_systems(Last, remote_exit_system::<SshServer>);
+    }
+}
+
+/// The state of the SSH server, available once the [`SshPlugin`] has been added.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn kick_operators(sessions: Query<(Entity, &SessionInput)>, server: Res<SshServer>) {
+///     for (session, input) in &sessions {
+///         if input.keys().just_pressed(KeyCode::Escape) {
+///             server.disconnect(session);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Default, Resource)]
+pub struct SshServer {
+    address: Option<SocketAddr>,
+    requests: Option<Mutex<Receiver<SessionRequest>>>,
+    clients: RemoteClients<SshTransport>,
+}
+
+impl SshServer {
+    /// The address the server is listening on. This is `None` until the app has started, and stays
+    /// that way if the server failed to start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_tui::prelude::*;
+    ///
+    /// fn print_address(server: Res<SshServer>) {
+    ///     if let Some(address) = server.local_addr() {
+    ///         println!("connect with: ssh -p {} {}", address.port(), address.ip());
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.address
+    }
+
+    /// The session entities of the connected clients.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// assert_eq!(SshServer::default().sessions().count(), 0);
+    /// ```
+    pub fn sessions(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.clients.sessions()
+    }
+
+    /// Restore the terminal of the client connected to a session and close its channel. The
+    /// session entity is despawned during the next update.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_tui::prelude::*;
+    ///
+    /// SshServer::default().disconnect(Entity::from_raw(1));
+    /// ```
+    pub fn disconnect(&self, session: Entity) {
+        self.clients.disconnect(session);
+    }
+}
+
+impl RemoteServer for SshServer {
+    type Transport = SshTransport;
+
+    fn clients(&self) -> &RemoteClients<SshTransport> {
+        &self.clients
+    }
+
+    fn clients_mut(&mut self) -> &mut RemoteClients<SshTransport> {
+        &mut self.clients
+    }
+}
+
+/// Sends output to an SSH client over the channel its terminal is attached to.
+pub(crate) struct SshTransport {
+    runtime: RuntimeHandle,
+    handle: russh::server::Handle,
+    channel: ChannelId,
+}
+
+impl ClientTransport for SshTransport {
+    fn send(&self, data: Vec<u8>) -> io::Result<()> {
+        let sent = self.runtime.block_on(async {
+            tokio::time::timeout(WRITE_TIMEOUT, self.handle.data(self.channel, data)).await
+        });
+
+        match sent {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(_)) => Err(ErrorKind::BrokenPipe.into()),
+            Err(_) => Err(ErrorKind::TimedOut.into()),
+        }
+    }
+
+    fn close(&self, message: Vec<u8>) {
+        let close = async {
+            self.handle.data(self.channel, message).await.ok()?;
+            self.handle.eof(self.channel).await.ok()?;
+            self.handle.close(self.channel).await.ok()
+        };
+
+        // The client is going away either way, there is nothing to do about failures
+        let _ = self
+            .runtime
+            .block_on(async { tokio::time::timeout(WRITE_TIMEOUT, close).await });
+    }
+
+    fn abort(&self) {
+        // This is also called from the runtime itself, which can't be blocked on
+        let handle = self.handle.clone();
+        let channel = self.channel;
+        self.runtime
+            .spawn(async move { handle.close(channel).await });
+    }
+}
+
+/// A client asking for a shell, waiting for the app to give it a session.
+struct SessionRequest {
+    client: RemoteClient<SshTransport>,
+    size: (u16, u16),
+    reply: oneshot::Sender<Entity>,
+}
+
+/// What every connection needs to reach the app.
+#[derive(Clone)]
+struct ServerContext {
+    authorized_keys: PathBuf,
+    requests: Sender<SessionRequest>,
+    events: Sender<SessionEvent>,
+    escape_timeout: Duration,
+}
+
+/// The session a connection's shell was given by the app.
+struct ConnectedSession {
+    session: Entity,
+    client: RemoteClient<SshTransport>,
+    input: UnboundedSender<Vec<u8>>,
+}
+
+/// Handles a single connection to the server.
+struct SshHandler {
+    context: ServerContext,
+
+    /// The channel the client's terminal is attached to, only one is allowed per connection.
+    channel: Option<ChannelId>,
+
+    size: (u16, u16),
+    connected: Option<ConnectedSession>,
+}
+
+impl SshHandler {
+    fn new(context: ServerContext) -> Self {
+        Self {
+            context,
+            channel: None,
+            size: DEFAULT_SIZE,
+            connected: None,
+        }
+    }
+
+    fn is_authorized(&self, key: &PublicKey) -> bool {
+        AuthorizedKeys::read_file(&self.context.authorized_keys).map_or(false, |entries| {
+            entries
+                .iter()
+                .any(|entry| entry.public_key().key_data() == key.key_data())
+        })
+    }
+
+    fn is_terminal(&self, channel: ChannelId) -> bool {
+        self.channel == Some(channel)
+    }
+
+    fn close(&mut self) {
+        if let Some(connected) = self.connected.take() {
+            connected.client.closed();
+        }
+    }
+}
+
+impl Drop for SshHandler {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl Handler for SshHandler {
+    type Error = russh::Error;
+
+    fn auth_publickey(
+        &mut self,
+        _user: &str,
+        key: &PublicKey,
+    ) -> impl Future<Output = Result<Auth, Self::Error>> + Send {
+        let auth = if self.is_authorized(key) {
+            Auth::Accept
+        } else {
+            Auth::reject()
+        };
+
+        ready(Ok(auth))
+    }
+
+    async fn channel_open_session(
+        &mut self,
+        channel: Channel<Msg>,
+        reply: ChannelOpenHandle,
+        _session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        if self.channel.is_some() {
+            reply
+                .reject(ChannelOpenFailure::AdministrativelyProhibited)
+                .await;
+        } else {
+            self.channel = Some(channel.id());
+            reply.accept().await;
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn pty_request(
+        &mut self,
+        channel: ChannelId,
+        _term: &str,
+        width: u32,
+        height: u32,
+        _pix_width: u32,
+        _pix_height: u32,
+        _modes: &[(russh::Pty, u32)],
+        session: &mut Session,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        if !self.is_terminal(channel) {
+            return ready(session.channel_failure(channel));
+        }
+
+        if let Some(size) = window_size(width, height) {
+            self.size = size;
+        }
+
+        ready(session.channel_success(channel))
+    }
+
+    async fn shell_request(
+        &mut self,
+        channel: ChannelId,
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        if !self.is_terminal(channel) || self.connected.is_some() {
+            return session.channel_failure(channel);
+        }
+        session.channel_success(channel)?;
+
+        let client = RemoteClient::new(SshTransport {
+            runtime: RuntimeHandle::current(),
+            handle: session.handle(),
+            channel,
+        });
+
+        let (reply, response) = oneshot::channel();
+        let request = SessionRequest {
+            client: client.clone(),
+            size: self.size,
+            reply,
+        };
+        if self.context.requests.send(request).is_err() {
+            return Ok(());
+        }
+
+        // The app drops the reply when turning the client away
+        if let Ok(entity) = response.await {
+            let (input, received) = unbounded_channel();
+            let events = self.context.events.clone();
+            let escape_timeout = self.context.escape_timeout;
+            tokio::spawn(read_client(received, entity, events, escape_timeout));
+
+            self.connected = Some(ConnectedSession {
+                session: entity,
+                client,
+                input,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn window_change_request(
+        &mut self,
+        channel: ChannelId,
+        width: u32,
+        height: u32,
+        _pix_width: u32,
+        _pix_height: u32,
+        _session: &mut Session,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        let size = window_size(width, height).filter(|_| self.is_terminal(channel));
+
+        if let Some((width, height)) = size {
+            self.size = (width, height);
+            if let Some(connected) = &self.connected {
+                let _ = self.context.events.send(SessionEvent {
+                    session: connected.session,
+                    event: CrossEvent::Resize(width, height),
+                });
+            }
+        }
+
+        ready(Ok(()))
+    }
+
+    fn data(
+        &mut self,
+        channel: ChannelId,
+        data: &[u8],
+        _session: &mut Session,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        if let Some(connected) = self
+            .connected
+            .as_ref()
+            .filter(|_| self.is_terminal(channel))
+        {
+            let _ = connected.input.send(data.to_vec());
+        }
+
+        ready(Ok(()))
+    }
+
+    fn channel_eof(
+        &mut self,
+        channel: ChannelId,
+        _session: &mut Session,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        if self.is_terminal(channel) {
+            self.close();
+        }
+
+        ready(Ok(()))
+    }
+
+    fn channel_close(
+        &mut self,
+        channel: ChannelId,
+        _session: &mut Session,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        if self.is_terminal(channel) {
+            self.close();
+        }
+
+        ready(Ok(()))
+    }
+}
+
+/// The size of a client's window, ignoring sizes that can't be drawn to.
+fn window_size(width: u32, height: u32) -> Option<(u16, u16)> {
+    let width = u16::try_from(width).unwrap_or(u16::MAX);
+    let height = u16::try_from(height).unwrap_or(u16::MAX);
+
+    (width > 0 && height > 0).then_some((width, height))
+}
+
+/// Parses the input of a client until it disconnects, forwarding it to the app as events for the
+/// client's session.
+async fn read_client(
+    mut received: UnboundedReceiver<Vec<u8>>,
+    session: Entity,
+    sender: Sender<SessionEvent>,
+    escape_timeout: Duration,
+) {
+    let mut parser = InputParser::default();
+
+    loop {
+        let events = if parser.is_pending() {
+            match tokio::time::timeout(escape_timeout, received.recv()).await {
+                Ok(Some(bytes)) => parser.advance(&bytes),
+                Ok(None) => break,
+                Err(_) => parser.flush(),
+            }
+        } else {
+            match received.recv().await {
+                Some(bytes) => parser.advance(&bytes),
+                None => break,
+            }
+        };
+
+        for event in events {
+            if sender.send(SessionEvent { session, event }).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+/// Accepts connections until the listener fails, handling each on its own task.
+async fn serve(listener: TcpListener, config: Arc<Config>, context: ServerContext) {
+    let Ok(listener) = tokio::net::TcpListener::from_std(listener) else {
+        return;
+    };
+
+    while let Ok((stream, _)) = listener.accept().await {
+        let handler = SshHandler::new(context.clone());
+        let config = Arc::clone(&config);
+
+        tokio::spawn(async move {
+            if let Ok(session) = run_stream(config, stream, handler).await {
+                let _ = session.await;
+            }
+        });
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn ssh_listen_system(
+    settings: Res<SshSettings>,
+    input_settings: Res<TuiInputSettings>,
+    remote: Res<RemoteInput>,
+    mut server: ResMut<SshServer>,
+) {
+    let host_key = match russh::keys::load_secret_key(&settings.host_key, None) {
+        Ok(host_key) => host_key,
+        Err(err) => {
+            let path = settings.host_key.display();
+            error!("failed to load the ssh host key from {path}, not serving: {err}");
+            return;
+        }
+    };
+
+    let config = Config {
+        keys: vec![host_key],
+        methods: MethodSet::from(&[MethodKind::PublicKey][..]),
+        auth_rejection_time: Duration::from_secs(1),
+        auth_rejection_time_initial: Some(Duration::ZERO),
+        nodelay: true,
+        ..Config::default()
+    };
+
+    let listener = TcpListener::bind(settings.address)
+        .and_then(|listener| listener.set_nonblocking(true).map(|()| listener));
+    let listener = match listener {
+        Ok(listener) => listener,
+        Err(err) => {
+            let address = settings.address;
+            error!("failed to listen for ssh clients on {address}, not serving: {err}");
+            return;
+        }
+    };
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build();
+    let runtime = match runtime {
+        Ok(runtime) => runtime,
+        Err(err) => {
+            error!("failed to start the ssh runtime, not serving: {err}");
+            return;
+        }
+    };
+
+    let (requests, received) = channel();
+    let context = ServerContext {
+        authorized_keys: settings.authorized_keys.clone(),
+        requests,
+        events: remote.sender(),
+        escape_timeout: input_settings.escape_timeout,
+    };
+
+    server.address = listener.local_addr().ok();
+    server.requests = Some(Mutex::new(received));
+
+    std::thread::spawn(move || runtime.block_on(serve(listener, Arc::new(config), context)));
+}
+
+/// Spawns a session for each client asking for a shell, turning them away once the maximum number
+/// of clients are connected.
+#[allow(clippy::needless_pass_by_value)]
+fn ssh_accept_system(
+    mut commands: Commands,
+    settings: Res<SshSettings>,
+    mut server: ResMut<SshServer>,
+    mut started: EventWriter<SessionStarted>,
+) {
+    let server = &mut *server;
+    let Some(Ok(requests)) = server.requests.as_ref().map(Mutex::lock) else {
+        return;
+    };
+
+    for SessionRequest {
+        client,
+        size,
+        reply,
+    } in requests.try_iter()
+    {
+        if !server.clients.accepts(settings.max_clients) {
+            client.close_with(TOO_MANY_CLIENTS.to_vec());
+            continue;
+        }
+
+        let mut writer = ClientWriter::new(client.clone());
+        let _ = prepare_remote(&mut writer).and_then(|()| writer.flush());
+        let terminal = remote_terminal(writer, size);
+        let session = commands.spawn(SessionBundle::new(terminal)).id();
+
+        // A client that went away while waiting is cleaned up with the rest
+        let _ = reply.send(session);
+
+        server.clients.push(session, client);
+        started.send(SessionStarted { session });
+    }
+}
@@ -0,0 +1,211 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use bevy::app::{App, Last, Plugin, PreStartup, PreUpdate};
+use bevy::ecs::event::{EventReader, EventWriter, Events};
+use bevy::ecs::system::{Commands, Res, ResMut, Resource};
+use bevy::ecs::world::World;
+use bevy::utils::tracing::{error, warn};
+use crossterm::event::{Event as CrossEvent, KeyCode, KeyEventKind, KeyModifiers};
+use signal_hook::consts::{SIGCONT, SIGTSTP};
+
+use crate::action::KeyChord;
+use crate::events::{Resumed, Suspended};
+use crate::input::RawConsoleEvent;
+use crate::signals::SignalHandlers;
+use crate::terminal_helpers::{reclaim_terminal, release_terminal, TuiTerminalSettings};
+use crate::BevyTerminal;
+
+/// Controls how the application is suspended to the shell's background, the way Ctrl+Z normally
+/// does outside of raw mode. The terminal is restored before the process stops and initialized
+/// again once it continues, with the whole screen redrawn.
+///
+/// Only keys pressed on the terminal the process is attached to are followed, not those of remote
+/// sessions. This is only available on Unix systems.
+///
+/// # Examples
+///
+/// Letting the application use Ctrl+Z for undo instead:
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// App::new()
+///     .add_plugins(MinimalTuiPlugins)
+///     .insert_resource(TuiSuspendSettings {
+///         suspend_keys: Vec::new(),
+///         ..default()
+///     })
+///     .run();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Resource)]
+pub struct TuiSuspendSettings {
+    /// The keys that suspend the application when pressed, Ctrl+Z by default.
+    pub suspend_keys: Vec<KeyChord>,
+
+    /// Whether `SIGTSTP` sent from elsewhere, such as with `kill -TSTP`, suspends the application
+    /// the same way the keys do, and whether the terminal is initialized again after being stopped
+    /// by any other means. This is read once when the application starts.
+    pub handle_signals: bool,
+}
+
+impl Default for TuiSuspendSettings {
+    fn default() -> Self {
+        Self {
+            suspend_keys: vec![KeyChord::new(KeyCode::Char('z'), KeyModifiers::CONTROL)],
+            handle_signals: true,
+        }
+    }
+}
+
+/// Handles suspending and resuming the application, added by the [`TuiPlugin`](crate::TuiPlugin).
+pub(crate) struct SuspendPlugin;
+
+impl Plugin for SuspendPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TuiSuspendSettings>()
+            .init_resource::<SuspendRequested>()
+            .add_event::<Suspended>()
+            .add_event::<Resumed>()
+            .add_systems(PreStartup, suspend_signal_setup)
+            .add_systems(PreUpdate, suspend_system)
+            .add_systems(Last, suspend_exit_system)
+            .register_type::<Suspended>()
+            .register_type::<Resumed>();
+    }
+}
+
+/// Whether the application should be suspended at the end of the current frame.
+#[derive(Debug, Default, Resource)]
+pub(crate) struct SuspendRequested(bool);
+
+/// Set when `SIGTSTP` or `SIGCONT` have been received.
+#[derive(Resource)]
+pub(crate) struct SuspendSignals {
+    pub(crate) stop: Arc<AtomicBool>,
+    pub(crate) continued: Arc<AtomicBool>,
+
+    /// Set while another program runs in the foreground with
+    /// [`ShellOut`](crate::prelude::ShellOut), so `SIGTSTP` stops the application along with it
+    /// and the shell can continue them both.
+    pub(crate) shelled_out: Arc<AtomicBool>,
+
+    /// Only held so the signals stop the process the default way again once the application is
+    /// no longer around to restore the terminal itself.
+    _handlers: SignalHandlers,
+}
+
+/// Install the signal handlers if they're wanted.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn suspend_signal_setup(mut commands: Commands, settings: Res<TuiSuspendSettings>) {
+    if !settings.handle_signals {
+        return;
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let continued = Arc::new(AtomicBool::new(false));
+    let shelled_out = Arc::new(AtomicBool::new(false));
+    let mut handlers = SignalHandlers::hold(&[SIGTSTP, SIGCONT]);
+
+    for (signal, flag) in [(SIGTSTP, &stop), (SIGCONT, &continued)] {
+        match signal_hook::flag::register(signal, Arc::clone(flag)) {
+            Ok(handler) => handlers.push(handler),
+            Err(err) => warn!("failed to handle signal {signal}: {err}"),
+        }
+    }
+
+    match signal_hook::flag::register_conditional_default(SIGTSTP, Arc::clone(&shelled_out)) {
+        Ok(handler) => handlers.push(handler),
+        Err(err) => warn!("failed to handle signal {SIGTSTP}: {err}"),
+    }
+
+    commands.insert_resource(SuspendSignals {
+        stop,
+        continued,
+        shelled_out,
+        _handlers: handlers,
+    });
+}
+
+/// Notices a suspend key or signal, sending [`Suspended`] so the app can prepare during this
+/// frame.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn suspend_system(
+    settings: Res<TuiSuspendSettings>,
+    signals: Option<Res<SuspendSignals>>,
+    mut requested: ResMut<SuspendRequested>,
+    mut events: EventReader<RawConsoleEvent>,
+    mut suspended: EventWriter<Suspended>,
+) {
+    let suspend_keys = events.read().filter(|RawConsoleEvent(event)| {
+        let CrossEvent::Key(key) = event else {
+            return false;
+        };
+
+        key.kind != KeyEventKind::Release && settings.suspend_keys.contains(&KeyChord::from(*key))
+    });
+    let suspend_key = suspend_keys.count() > 0;
+
+    let signaled = signals.map_or(false, |s| s.stop.swap(false, Ordering::Relaxed));
+
+    if (suspend_key || signaled) && !requested.0 {
+        requested.0 = true;
+        suspended.send(Suspended);
+    }
+}
+
+/// Stops the process once the frame is finished if it was asked to suspend, restoring the terminal
+/// beforehand and initializing it again once the process continues. The terminal is also
+/// initialized again if the process was stopped and continued by other means, as the shell may
+/// have changed its modes in the meantime.
+///
+/// The terminal is left alone when the process isn't drawing to it, such as when serving sessions
+/// over the network.
+pub(crate) fn suspend_exit_system(world: &mut World) {
+    let settings = world.resource::<TuiTerminalSettings>().clone();
+    let requested = world
+        .get_resource::<SuspendRequested>()
+        .map_or(false, |requested| requested.0);
+
+    if requested {
+        world.resource_mut::<SuspendRequested>().0 = false;
+
+        if let Some(mut terminal) = world.get_resource_mut::<BevyTerminal>() {
+            if let Err(err) = release_terminal(&settings, &mut terminal) {
+                error!("failed to restore the terminal: {err}");
+            }
+        }
+
+        // This doesn't return until the process is continued, usually by `fg` in the shell
+        if let Err(err) = signal_hook::low_level::emulate_default_handler(SIGTSTP) {
+            error!("failed to suspend: {err}");
+        }
+    }
+
+    let continued = world
+        .get_resource::<SuspendSignals>()
+        .map_or(false, |signals| {
+            signals.continued.swap(false, Ordering::Relaxed)
+        });
+
+    if !requested && !continued {
+        return;
+    }
+
+    if let Some(mut terminal) = world.get_resource_mut::<BevyTerminal>() {
+        // Stopped by other means the terminal was never released, undoing the initialization
+        // before repeating it keeps the keyboard flags pushed with it from piling up
+        if !requested {
+            if let Err(err) = release_terminal(&settings, &mut terminal) {
+                error!("failed to restore the terminal: {err}");
+            }
+        }
+
+        if let Err(err) = reclaim_terminal(&settings, &mut terminal) {
+            error!("failed to reinitialize the terminal: {err}");
+        }
+    }
+
+    world.resource_mut::<Events<Resumed>>().send(Resumed);
+}
@@ -0,0 +1,627 @@
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::Sender;
+use std::time::Duration;
+
+use bevy::app::{App, Last, Plugin, PreUpdate, Startup};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::event::EventWriter;
+use bevy::ecs::schedule::IntoSystemConfigs;
+use bevy::ecs::system::{Commands, Res, ResMut, Resource};
+use bevy::utils::tracing::error;
+use crossterm::event::Event as CrossEvent;
+
+use crate::events::{SessionEvent, SessionStarted};
+use crate::input::parser::InputParser;
+use crate::input::TuiInputSettings;
+use crate::scheduler::RemoteInput;
+use crate::session::{
+    prepare_remote, remote_cleanup_system, remote_exit_system, remote_terminal, ClientTransport,
+    ClientWriter, RemoteClient, RemoteClients, RemoteServer, SessionBundle, DEFAULT_SIZE,
+    TOO_MANY_CLIENTS, WRITE_TIMEOUT,
+};
+
+/// Interpret as command, the byte introducing every telnet command.
+const IAC: u8 = 255;
+const DONT: u8 = 254;
+const DO: u8 = 253;
+const WONT: u8 = 252;
+const WILL: u8 = 251;
+const SB: u8 = 250;
+const SE: u8 = 240;
+
+const OPT_ECHO: u8 = 1;
+const OPT_SUPPRESS_GO_AHEAD: u8 = 3;
+const OPT_NAWS: u8 = 31;
+
+/// The read timeout used for an escape timeout of zero, only long enough to pick up bytes that
+/// have already arrived. The socket is shared with the writer so it can't be switched into
+/// non-blocking mode for the read.
+const MIN_READ_TIMEOUT: Duration = Duration::from_micros(1);
+
+/// Puts the client into character at a time mode, with the server handling the echo, and asks it
+/// to report its window size.
+const NEGOTIATION: [u8; 12] = [
+    IAC,
+    WILL,
+    OPT_ECHO,
+    IAC,
+    WILL,
+    OPT_SUPPRESS_GO_AHEAD,
+    IAC,
+    DO,
+    OPT_SUPPRESS_GO_AHEAD,
+    IAC,
+    DO,
+    OPT_NAWS,
+];
+
+/// Configures the [`TelnetPlugin`]. This needs to be inserted before the app is run for it to have
+/// any effect.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// App::new()
+///     .add_plugins((MinimalTuiPlugins, TelnetPlugin))
+///     .insert_resource(TelnetSettings {
+///         address: ([0, 0, 0, 0], 2323).into(),
+///         max_clients: 4,
+///     })
+///     .run();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Resource)]
+pub struct TelnetSettings {
+    /// The address to listen for connections on. Telnet is unencrypted and unauthenticated so this
+    /// defaults to only accepting connections from the local machine on port 2323.
+    pub address: SocketAddr,
+
+    /// How many clients can be connected at once, anyone connecting beyond this is turned away.
+    /// Defaults to a single client.
+    pub max_clients: usize,
+}
+
+impl Default for TelnetSettings {
+    fn default() -> Self {
+        Self {
+            address: ([127, 0, 0, 1], 2323).into(),
+            max_clients: 1,
+        }
+    }
+}
+
+/// Serves the terminal UI to remote clients over telnet instead of drawing to the terminal the
+/// process is attached to. The application can then run in the background, such as on a game
+/// server, and have an operator connect to it with `telnet localhost 2323` whenever needed.
+///
+/// Every client is given its own [`TuiSession`] entity, announced with a [`SessionStarted`] event
+/// and despawned after a [`SessionEnded`] event once the client disconnects. Keyboard, mouse and
+/// paste input are read from the client and the session is sized to the client's window as
+/// reported through the NAWS telnet option. Sessions show the same screen as each other until the
+/// app gives them a root of their own.
+///
+/// As the local terminal isn't used [`initialize_terminal`](crate::prelude::initialize_terminal)
+/// shouldn't be called, and color support isn't detected for remote clients.
+///
+/// When the address can't be listened on, the error is logged and the app runs without accepting
+/// any clients. [`TelnetServer::local_addr`] can be checked to tell whether the server has started.
+///
+/// [`SessionEnded`]: crate::prelude::SessionEnded
+/// [`TuiSession`]: crate::prelude::TuiSession
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// App::new()
+///     .add_plugins((MinimalTuiPlugins, TelnetPlugin))
+///     .run();
+/// ```
+pub struct TelnetPlugin;
+
+impl Plugin for TelnetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TelnetSettings>()
+            .init_resource::<RemoteInput>()
+            .init_resource::<TelnetServer>()
+            .add_systems(Startup, telnet_listen_system)
+            .add_systems(
+                PreUpdate,
+                // Cleaning up first ensures new sessions have been spawned before being checked
+                (remote_cleanup_system::<TelnetServer>, telnet_accept_system).chain(),
+            )
+            .add_systems(Last, remote_exit_system::<TelnetServer>);
+    }
+}
+
+/// The state of the telnet server, available once the [`TelnetPlugin`] has been added.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn kick_operators(sessions: Query<(Entity, &SessionInput)>, server: Res<TelnetServer>) {
+///     for (session, input) in &sessions {
+///         if input.keys().just_pressed(KeyCode::Escape) {
+///             server.disconnect(session);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Default, Resource)]
+pub struct TelnetServer {
+    listener: Option<TcpListener>,
+    clients: RemoteClients<TelnetTransport>,
+}
+
+impl TelnetServer {
+    /// The address the server is listening on. This is `None` until the app has started, and stays
+    /// that way if the server failed to start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_tui::prelude::*;
+    ///
+    /// fn print_address(server: Res<TelnetServer>) {
+    ///     if let Some(address) = server.local_addr() {
+    ///         println!("connect with: telnet {} {}", address.ip(), address.port());
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.as_ref()?.local_addr().ok()
+    }
+
+    /// The session entities of the connected clients.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// assert_eq!(TelnetServer::default().sessions().count(), 0);
+    /// ```
+    pub fn sessions(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.clients.sessions()
+    }
+
+    /// Restore the terminal of the client connected to a session and close the connection. The
+    /// session entity is despawned during the next update.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_tui::prelude::*;
+    ///
+    /// TelnetServer::default().disconnect(Entity::from_raw(1));
+    /// ```
+    pub fn disconnect(&self, session: Entity) {
+        self.clients.disconnect(session);
+    }
+}
+
+impl RemoteServer for TelnetServer {
+    type Transport = TelnetTransport;
+
+    fn clients(&self) -> &RemoteClients<TelnetTransport> {
+        &self.clients
+    }
+
+    fn clients_mut(&mut self) -> &mut RemoteClients<TelnetTransport> {
+        &mut self.clients
+    }
+}
+
+/// Sends output to a telnet client over its TCP connection.
+pub(crate) struct TelnetTransport(TcpStream);
+
+impl ClientTransport for TelnetTransport {
+    fn send(&self, data: Vec<u8>) -> io::Result<()> {
+        (&self.0).write_all(&data)
+    }
+
+    fn close(&self, message: Vec<u8>) {
+        // The client is going away either way, there is nothing to do about failures
+        let _ = (&self.0).write_all(&message);
+        self.abort();
+    }
+
+    fn abort(&self) {
+        let _ = self.0.shutdown(Shutdown::Both);
+    }
+}
+
+fn prepare_client(stream: &mut TcpStream) -> io::Result<()> {
+    stream.write_all(&NEGOTIATION)?;
+    prepare_remote(stream)
+}
+
+/// What was extracted from a chunk of bytes received from a telnet client.
+#[derive(Default)]
+struct Decoded {
+    /// The terminal input, with telnet commands removed.
+    data: Vec<u8>,
+
+    /// Responses that need to be sent back to the client.
+    replies: Vec<u8>,
+
+    /// The most recent window size reported by the client.
+    size: Option<(u16, u16)>,
+}
+
+#[derive(Clone, Copy, Default)]
+enum DecoderState {
+    #[default]
+    Data,
+    CarriageReturn,
+    Command,
+    Negotiate(u8),
+    Subnegotiation,
+    SubnegotiationCommand,
+}
+
+/// Separates telnet commands from the input stream of a client.
+#[derive(Default)]
+struct TelnetDecoder {
+    state: DecoderState,
+    subnegotiation: Vec<u8>,
+}
+
+impl TelnetDecoder {
+    fn decode(&mut self, bytes: &[u8]) -> Decoded {
+        let mut decoded = Decoded::default();
+
+        for &byte in bytes {
+            self.state = match self.state {
+                DecoderState::Data => Self::data(byte, &mut decoded),
+                DecoderState::CarriageReturn => match byte {
+                    // Telnet sends the Enter key as either CR LF or CR NUL
+                    b'\n' | 0 => DecoderState::Data,
+                    byte => Self::data(byte, &mut decoded),
+                },
+                DecoderState::Command => match byte {
+                    IAC => {
+                        decoded.data.push(IAC);
+                        DecoderState::Data
+                    }
+                    WILL | WONT | DO | DONT => DecoderState::Negotiate(byte),
+                    SB => {
+                        self.subnegotiation.clear();
+                        DecoderState::Subnegotiation
+                    }
+                    _ => DecoderState::Data,
+                },
+                DecoderState::Negotiate(command) => {
+                    Self::negotiate(command, byte, &mut decoded);
+                    DecoderState::Data
+                }
+                DecoderState::Subnegotiation => {
+                    if byte == IAC {
+                        DecoderState::SubnegotiationCommand
+                    } else {
+                        self.subnegotiation.push(byte);
+                        DecoderState::Subnegotiation
+                    }
+                }
+                DecoderState::SubnegotiationCommand => match byte {
+                    IAC => {
+                        self.subnegotiation.push(IAC);
+                        DecoderState::Subnegotiation
+                    }
+                    SE => {
+                        if let Some(size) = self.window_size() {
+                            decoded.size = Some(size);
+                        }
+                        DecoderState::Data
+                    }
+                    _ => DecoderState::Data,
+                },
+            };
+        }
+
+        decoded
+    }
+
+    fn data(byte: u8, decoded: &mut Decoded) -> DecoderState {
+        match byte {
+            IAC => DecoderState::Command,
+            b'\r' => {
+                decoded.data.push(byte);
+                DecoderState::CarriageReturn
+            }
+            byte => {
+                decoded.data.push(byte);
+                DecoderState::Data
+            }
+        }
+    }
+
+    /// Refuse any options the client offers or requests other than the ones we asked for.
+    fn negotiate(command: u8, option: u8, decoded: &mut Decoded) {
+        let refusal = match command {
+            DO if option != OPT_ECHO && option != OPT_SUPPRESS_GO_AHEAD => WONT,
+            WILL if option != OPT_NAWS && option != OPT_SUPPRESS_GO_AHEAD => DONT,
+            _ => return,
+        };
+
+        decoded.replies.extend_from_slice(&[IAC, refusal, option]);
+    }
+
+    fn window_size(&self) -> Option<(u16, u16)> {
+        match self.subnegotiation.as_slice() {
+            [OPT_NAWS, w1, w2, h1, h2] => {
+                let width = u16::from_be_bytes([*w1, *w2]);
+                let height = u16::from_be_bytes([*h1, *h2]);
+                (width > 0 && height > 0).then_some((width, height))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Reads input from a client until it disconnects, forwarding it to the app as events for the
+/// client's session.
+fn read_client(
+    mut stream: TcpStream,
+    session: Entity,
+    client: &RemoteClient<TelnetTransport>,
+    sender: &Sender<SessionEvent>,
+    escape_timeout: Duration,
+) {
+    let mut telnet = TelnetDecoder::default();
+    let mut parser = InputParser::default();
+    let mut buffer = [0; 1024];
+
+    loop {
+        // Only wait a limited time for the rest of a sequence that has been started
+        let timeout = parser
+            .is_pending()
+            .then_some(escape_timeout.max(MIN_READ_TIMEOUT));
+        let read = stream
+            .set_read_timeout(timeout)
+            .and_then(|()| stream.read(&mut buffer));
+
+        let events = match read {
+            Ok(0) => break,
+            Ok(len) => {
+                let decoded = telnet.decode(&buffer[..len]);
+                if !decoded.replies.is_empty() && !client.send(decoded.replies) {
+                    break;
+                }
+
+                let mut events = Vec::new();
+                if let Some((width, height)) = decoded.size {
+                    events.push(CrossEvent::Resize(width, height));
+                }
+                events.extend(parser.advance(&decoded.data));
+                events
+            }
+            Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                if !parser.is_pending() {
+                    continue;
+                }
+                parser.flush()
+            }
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        };
+
+        for event in events {
+            if sender.send(SessionEvent { session, event }).is_err() {
+                return;
+            }
+        }
+    }
+
+    client.disconnect();
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn telnet_listen_system(settings: Res<TelnetSettings>, mut server: ResMut<TelnetServer>) {
+    let listener = TcpListener::bind(settings.address)
+        .and_then(|listener| listener.set_nonblocking(true).map(|()| listener));
+
+    match listener {
+        Ok(listener) => server.listener = Some(listener),
+        Err(err) => {
+            let address = settings.address;
+            error!("failed to listen for telnet clients on {address}, not serving: {err}");
+        }
+    }
+}
+
+/// Spawns a session for each newly connected client, turning them away once the maximum number of
+/// clients are connected.
+#[allow(clippy::needless_pass_by_value)]
+fn telnet_accept_system(
+    mut commands: Commands,
+    settings: Res<TelnetSettings>,
+    input_settings: Res<TuiInputSettings>,
+    mut server: ResMut<TelnetServer>,
+    remote: Res<RemoteInput>,
+    mut started: EventWriter<SessionStarted>,
+) {
+    let server = &mut *server;
+    let Some(listener) = &server.listener else {
+        return;
+    };
+
+    while let Ok((mut stream, _)) = listener.accept() {
+        if !server.clients.accepts(settings.max_clients) {
+            let _ = stream.write_all(TOO_MANY_CLIENTS);
+            continue;
+        }
+
+        let reader = stream
+            .set_nonblocking(false)
+            .and_then(|()| stream.set_nodelay(true))
+            .and_then(|()| stream.set_write_timeout(Some(WRITE_TIMEOUT)))
+            .and_then(|()| prepare_client(&mut stream))
+            .and_then(|()| stream.try_clone());
+
+        let Ok(reader) = reader else {
+            continue;
+        };
+        let client = RemoteClient::new(TelnetTransport(stream));
+
+        let terminal = remote_terminal(ClientWriter::new(client.clone()), DEFAULT_SIZE);
+        let session = commands.spawn(SessionBundle::new(terminal)).id();
+
+        let reader_client = client.clone();
+        let sender = remote.sender();
+        let escape_timeout = input_settings.escape_timeout;
+        std::thread::spawn(move || {
+            read_client(reader, session, &reader_client, &sender, escape_timeout);
+        });
+
+        server.clients.push(session, client);
+        started.send(SessionStarted { session });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Decode the chunks one after another, combining what was extracted from them.
+    fn decode_chunks<'a>(chunks: impl IntoIterator<Item = &'a [u8]>) -> Decoded {
+        let mut telnet = TelnetDecoder::default();
+        let mut combined = Decoded::default();
+
+        for chunk in chunks {
+            let decoded = telnet.decode(chunk);
+            combined.data.extend(decoded.data);
+            combined.replies.extend(decoded.replies);
+            combined.size = decoded.size.or(combined.size);
+        }
+
+        combined
+    }
+
+    /// Check the bytes decode the same way whole, a byte at a time, and split in two at every
+    /// position.
+    fn assert_decodes(bytes: &[u8], data: &[u8], replies: &[u8], size: Option<(u16, u16)>) {
+        let whole = decode_chunks([bytes]);
+        assert_eq!(whole.data, data, "data of {bytes:?}");
+        assert_eq!(whole.replies, replies, "replies to {bytes:?}");
+        assert_eq!(whole.size, size, "size from {bytes:?}");
+
+        let bytewise = decode_chunks(bytes.chunks(1));
+        assert_eq!(bytewise.data, data, "data of {bytes:?} a byte at a time");
+        assert_eq!(
+            bytewise.replies, replies,
+            "replies to {bytes:?} a byte at a time"
+        );
+        assert_eq!(bytewise.size, size, "size from {bytes:?} a byte at a time");
+
+        for split in 1..bytes.len() {
+            let (first, second) = bytes.split_at(split);
+            let halves = decode_chunks([first, second]);
+            assert_eq!(halves.data, data, "data of {bytes:?} split at {split}");
+            assert_eq!(
+                halves.replies, replies,
+                "replies to {bytes:?} split at {split}"
+            );
+            assert_eq!(halves.size, size, "size from {bytes:?} split at {split}");
+        }
+    }
+
+    #[test]
+    fn passes_plain_input_through() {
+        assert_decodes(b"hello\x1b[A", b"hello\x1b[A", &[], None);
+    }
+
+    #[test]
+    fn unescapes_doubled_iac() {
+        assert_decodes(&[b'a', IAC, IAC, b'b'], &[b'a', 0xff, b'b'], &[], None);
+    }
+
+    #[test]
+    fn enter_is_a_single_carriage_return() {
+        assert_decodes(b"a\r\0b\r\nc", b"a\rb\rc", &[], None);
+
+        // A carriage return followed by anything else keeps both
+        assert_decodes(b"a\rb\r\r\0", b"a\rb\r\r", &[], None);
+    }
+
+    #[test]
+    fn reads_the_window_size() {
+        assert_decodes(
+            &[b'a', IAC, SB, OPT_NAWS, 0, 80, 0, 24, IAC, SE, b'b'],
+            b"ab",
+            &[],
+            Some((80, 24)),
+        );
+
+        // Later reports replace earlier ones
+        assert_decodes(
+            &[
+                IAC, SB, OPT_NAWS, 0, 80, 0, 24, IAC, SE, IAC, SB, OPT_NAWS, 0, 100, 0, 30, IAC, SE,
+            ],
+            &[],
+            &[],
+            Some((100, 30)),
+        );
+    }
+
+    #[test]
+    fn reads_a_window_size_containing_iac() {
+        assert_decodes(
+            &[IAC, SB, OPT_NAWS, 0, IAC, IAC, IAC, IAC, 0, IAC, SE],
+            &[],
+            &[],
+            Some((255, 0xff00)),
+        );
+    }
+
+    #[test]
+    fn ignores_empty_or_malformed_window_sizes() {
+        assert_decodes(&[IAC, SB, OPT_NAWS, 0, 0, 0, 24, IAC, SE], &[], &[], None);
+        assert_decodes(&[IAC, SB, OPT_NAWS, 0, 80, 0, IAC, SE], &[], &[], None);
+        assert_decodes(&[IAC, SB, 24, 0, 80, 0, 24, IAC, SE], &[], &[], None);
+    }
+
+    #[test]
+    fn refuses_options_it_didnt_ask_for() {
+        let terminal_type = 24;
+
+        assert_decodes(
+            &[IAC, DO, terminal_type, IAC, WILL, terminal_type, b'x'],
+            b"x",
+            &[IAC, WONT, terminal_type, IAC, DONT, terminal_type],
+            None,
+        );
+
+        // The options offered during negotiation are accepted silently
+        assert_decodes(
+            &[
+                IAC,
+                DO,
+                OPT_ECHO,
+                IAC,
+                DO,
+                OPT_SUPPRESS_GO_AHEAD,
+                IAC,
+                WILL,
+                OPT_NAWS,
+            ],
+            &[],
+            &[],
+            None,
+        );
+
+        // Refusals are acknowledged without a reply
+        assert_decodes(&[IAC, WONT, OPT_NAWS, IAC, DONT, OPT_ECHO], &[], &[], None);
+    }
+}
@@ -1,31 +1,120 @@
 use std::error::Error;
 use std::io::Write;
+use std::path::PathBuf;
+#[cfg(unix)]
+use std::sync::atomic::{AtomicBool, Ordering};
 
+use bevy::app::AppExit;
+use bevy::ecs::event::EventReader;
+use bevy::ecs::system::{Res, ResMut, Resource};
 use crossterm::event::{
     DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
     EnableFocusChange, EnableMouseCapture,
 };
+#[cfg(unix)]
+use crossterm::event::{
+    KeyboardEnhancementFlags, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+};
+#[cfg(unix)]
+use crossterm::terminal::supports_keyboard_enhancement;
 use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
 use crossterm::QueueableCommand;
-use ratatui::backend::CrosstermBackend;
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::{TerminalOptions, Viewport};
 
+use crate::color::{ColorFilterBackend, ColorSupport};
+use crate::output::{TerminalOutput, TuiOutput};
+use crate::recording::CastRecorder;
 use crate::{BevyTerminal, Terminal};
 
+/// Whether the terminal the process is attached to was asked to disambiguate escape codes, see
+/// [`push_keyboard_flags`].
+#[cfg(unix)]
+static KEYBOARD_FLAGS_PUSHED: AtomicBool = AtomicBool::new(false);
+
+/// The terminfo magic number of files storing numeric capabilities as 16-bit values.
+const TERMINFO_MAGIC_16: u16 = 0o432;
+
+/// The terminfo magic number of files storing numeric capabilities as 32-bit values.
+const TERMINFO_MAGIC_32: u16 = 0o1036;
+
+/// The position of the `colors` capability in the numeric section of a compiled terminfo entry.
+const TERMINFO_COLORS: usize = 13;
+
+/// Controls how the [`BevyTerminal`] is created when the application starts. This needs to be
+/// inserted before the app is run for it to have any effect, and is ignored entirely if a
+/// [`BevyTerminal`] has already been inserted.
+///
+/// # Examples
+///
+/// Running a two line tall UI underneath the shell prompt rather than taking over the screen:
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::Viewport;
+///
+/// fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     initialize_inline_terminal()?;
+///
+///     App::new()
+///         .add_plugins(MinimalTuiPlugins)
+///         .insert_resource(TuiTerminalSettings {
+///             viewport: Viewport::Inline(2),
+///             ..default()
+///         })
+///         .run();
+///
+///     teardown_inline_terminal()?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Resource)]
+pub struct TuiTerminalSettings {
+    /// The region of the screen the application will draw to. [`Viewport::Inline`] draws below the
+    /// current cursor position and should be paired with [`initialize_inline_terminal`] so the
+    /// alternate screen isn't entered. Lines can be added to the scrollback above an inline
+    /// viewport using [`ratatui::Terminal::insert_before`].
+    ///
+    /// Inline viewports locate the cursor by querying the terminal through STDOUT, and so can only
+    /// be used with [`TuiOutput::Stdout`].
+    pub viewport: Viewport,
+
+    /// Where the terminal will be drawn to. This should be the same output passed to
+    /// [`initialize_terminal_with`].
+    pub output: TuiOutput,
+
+    /// Record every frame drawn into an
+    /// [asciicast v2](https://docs.asciinema.org/manual/asciicast/v2/) file at this path, which
+    /// can be played back with `asciinema play` or attached to a bug report. The size of the
+    /// terminal is recorded as it changes. An existing file at the path is replaced.
+    pub record: Option<PathBuf>,
+}
+
 /// Helper method for creating a crossterm backed TUI terminal object. Currently only the crossterm
 /// backend is supported but this will be expanded once all of the minimal functionality has been
 /// implemented to my satisfaction.
 ///
 /// # Errors
 ///
-/// The Terminal will fail to be created if STDOUT isn't available, or not a terminal. See
-/// [`ratatui::Terminal::new`] for details on the failure cases.
-pub(crate) fn create_terminal() -> Result<BevyTerminal, Box<dyn Error>> {
-    let stdout = std::io::stdout();
+/// The Terminal will fail to be created if the output isn't available, or not a terminal. See
+/// [`ratatui::Terminal::with_options`] for details on the failure cases.
+pub(crate) fn create_terminal(
+    settings: &TuiTerminalSettings,
+) -> Result<BevyTerminal, Box<dyn Error>> {
+    let mut output = TerminalOutput::open(settings.output)?;
+    if let Some(path) = &settings.record {
+        output = TerminalOutput::writer(CastRecorder::create(output, path)?);
+    }
 
-    let backend = CrosstermBackend::new(stdout);
-    let terminal = ratatui::Terminal::new(backend)?;
+    let backend = ColorFilterBackend::new(CrosstermBackend::new(output));
+    let options = TerminalOptions {
+        viewport: settings.viewport.clone(),
+    };
+    let terminal = ratatui::Terminal::with_options(backend, options)?;
 
     Ok(Terminal(terminal))
 }
@@ -34,6 +123,9 @@ pub(crate) fn create_terminal() -> Result<BevyTerminal, Box<dyn Error>> {
 /// application such as enabling raw mode and requesting the common set of features this library
 /// intends to support at a minimum such as mouse and keyboard support.
 ///
+/// Terminals supporting the kitty keyboard protocol are also asked to disambiguate the Escape key,
+/// so it can be reported as soon as it is pressed.
+///
 /// This does not handle additional terminal extensions, setting the title, or window dimensions
 /// which is currently the responsibility of the end-user. Eventually additional helpers and
 /// configuration will be exposed for these purposes.
@@ -43,19 +135,40 @@ pub(crate) fn create_terminal() -> Result<BevyTerminal, Box<dyn Error>> {
 /// This performs a series of escape sequences against STDOUT, if an I/O error occurs while writing
 /// out or flushing these various sequences to the terminal an `Err` will be returned.
 pub fn initialize_terminal() -> Result<(), Box<dyn Error>> {
-    enable_raw_mode()?;
+    initialize_terminal_with(&mut std::io::stdout())
+}
 
-    let mut stdout = std::io::stdout();
+/// The same as [`initialize_terminal`] but sending the escape sequences to another output, such
+/// as the one chosen with [`TuiTerminalSettings::output`].
+///
+/// # Errors
+///
+/// If an I/O error occurs while writing out or flushing the escape sequences to the output an
+/// `Err` will be returned.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy_tui::prelude::*;
+///
+/// let mut tty = TerminalOutput::open(TuiOutput::Tty)?;
+/// initialize_terminal_with(&mut tty)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn initialize_terminal_with(output: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    enable_raw_mode()?;
 
-    stdout.queue(EnterAlternateScreen)?;
-    stdout.queue(EnableBracketedPaste)?;
-    stdout.queue(EnableFocusChange)?;
-    stdout.queue(EnableMouseCapture)?;
+    output.queue(EnterAlternateScreen)?;
+    output.queue(EnableBracketedPaste)?;
+    output.queue(EnableFocusChange)?;
+    output.queue(EnableMouseCapture)?;
+    #[cfg(unix)]
+    push_keyboard_flags(output)?;
 
     // TODO: Make this a setting for the application
-    //stdout.queue(crossterm::terminal::SetTitle("Hello Bevy"))?;
+    //output.queue(crossterm::terminal::SetTitle("Hello Bevy"))?;
 
-    stdout.flush()?;
+    output.flush()?;
 
     Ok(())
 }
@@ -68,14 +181,415 @@ pub fn initialize_terminal() -> Result<(), Box<dyn Error>> {
 /// This performs a series of escape sequences against STDOUT, if an I/O error occurs while writing
 /// out or flushing these various sequences to the terminal an `Err` will be returned.
 pub fn teardown_terminal() -> Result<(), Box<dyn Error>> {
+    teardown_terminal_with(&mut std::io::stdout())
+}
+
+/// Bring the terminal back into a usable mode after [`initialize_terminal_with`], sending the
+/// escape sequences to the same output.
+///
+/// # Errors
+///
+/// If an I/O error occurs while writing out or flushing the escape sequences to the output an
+/// `Err` will be returned.
+pub fn teardown_terminal_with(output: &mut impl Write) -> Result<(), Box<dyn Error>> {
     disable_raw_mode()?;
 
+    #[cfg(unix)]
+    pop_keyboard_flags(output)?;
+    output.queue(LeaveAlternateScreen)?;
+    output.queue(DisableBracketedPaste)?;
+    output.queue(DisableFocusChange)?;
+    output.queue(DisableMouseCapture)?;
+    output.flush()?;
+
+    Ok(())
+}
+
+/// Prepares the terminal connected to STDOUT for an application using an inline
+/// [`Viewport`](TuiTerminalSettings::viewport). This is the same as [`initialize_terminal`] except
+/// the alternate screen is left alone so the UI appears below the shell prompt, and the mouse isn't
+/// captured so the scrollback can still be scrolled.
+///
+/// # Errors
+///
+/// This performs a series of escape sequences against STDOUT, if an I/O error occurs while writing
+/// out or flushing these various sequences to the terminal an `Err` will be returned.
+pub fn initialize_inline_terminal() -> Result<(), Box<dyn Error>> {
+    enable_raw_mode()?;
+
     let mut stdout = std::io::stdout();
-    stdout.queue(LeaveAlternateScreen)?;
+
+    stdout.queue(EnableBracketedPaste)?;
+    stdout.queue(EnableFocusChange)?;
+    #[cfg(unix)]
+    push_keyboard_flags(&mut stdout)?;
+    stdout.flush()?;
+
+    Ok(())
+}
+
+/// Bring the terminal back into a usable mode after [`initialize_inline_terminal`]. The last frame
+/// drawn is left on the screen with the cursor placed on the line below it.
+///
+/// # Errors
+///
+/// This performs a series of escape sequences against STDOUT, if an I/O error occurs while writing
+/// out or flushing these various sequences to the terminal an `Err` will be returned.
+pub fn teardown_inline_terminal() -> Result<(), Box<dyn Error>> {
+    disable_raw_mode()?;
+
+    let mut stdout = std::io::stdout();
+    #[cfg(unix)]
+    pop_keyboard_flags(&mut stdout)?;
     stdout.queue(DisableBracketedPaste)?;
     stdout.queue(DisableFocusChange)?;
-    stdout.queue(DisableMouseCapture)?;
     stdout.flush()?;
 
     Ok(())
 }
+
+/// Ask terminals supporting the kitty keyboard protocol to send Escape, and keys pressed with Alt
+/// or Ctrl, as sequences of their own. Escape then never has to be told apart from the start of
+/// another sequence, so is reported as soon as it arrives. Terminals without support are left
+/// alone, and keep having Escape told apart by waiting for what follows it.
+///
+/// Windows reports keys through the console API rather than escape sequences so has no need for
+/// this.
+#[cfg(unix)]
+fn push_keyboard_flags(output: &mut impl Write) -> std::io::Result<()> {
+    // Terminals that don't answer the query are treated as not supporting the protocol
+    if !supports_keyboard_enhancement().unwrap_or(false) {
+        return Ok(());
+    }
+
+    output.queue(PushKeyboardEnhancementFlags(
+        KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES,
+    ))?;
+    KEYBOARD_FLAGS_PUSHED.store(true, Ordering::Relaxed);
+
+    Ok(())
+}
+
+/// Undo [`push_keyboard_flags`].
+#[cfg(unix)]
+fn pop_keyboard_flags(output: &mut impl Write) -> std::io::Result<()> {
+    if KEYBOARD_FLAGS_PUSHED.swap(false, Ordering::Relaxed) {
+        output.queue(PopKeyboardEnhancementFlags)?;
+    }
+
+    Ok(())
+}
+
+/// Whether the terminal the process is attached to sends Escape unambiguously, as it was found to
+/// support the kitty keyboard protocol when it was initialized.
+pub(crate) fn escape_disambiguated() -> bool {
+    #[cfg(unix)]
+    {
+        KEYBOARD_FLAGS_PUSHED.load(Ordering::Relaxed)
+    }
+
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+/// Moves the cursor below an inline viewport as the application exits so the shell prompt doesn't
+/// overwrite the last frame.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn inline_exit_system(
+    mut exit_events: EventReader<AppExit>,
+    settings: Res<TuiTerminalSettings>,
+    terminal: Option<ResMut<BevyTerminal>>,
+) {
+    if exit_events.read().count() == 0 || !matches!(settings.viewport, Viewport::Inline(_)) {
+        return;
+    }
+
+    let Some(mut terminal) = terminal else {
+        return;
+    };
+
+    // Failing to reposition the cursor isn't worth interrupting the exit over
+    let _ = move_below_viewport(&mut terminal);
+}
+
+/// Place the cursor on the line below the area drawn to.
+fn move_below_viewport(terminal: &mut BevyTerminal) -> std::io::Result<()> {
+    let area = terminal.0.get_frame().size();
+    let backend = terminal.0.backend_mut();
+
+    backend.set_cursor(0, area.bottom().saturating_sub(1))?;
+    backend.append_lines(1)?;
+    Backend::flush(backend)
+}
+
+/// Hand the terminal back to the shell or another program while the application keeps running,
+/// undoing the initialization the same way the matching teardown function would. The output and
+/// the kind of initialization are taken from the [`TuiTerminalSettings`].
+pub(crate) fn release_terminal(
+    settings: &TuiTerminalSettings,
+    terminal: &mut BevyTerminal,
+) -> Result<(), Box<dyn Error>> {
+    let inline = matches!(settings.viewport, Viewport::Inline(_));
+
+    // Anything printed in the meantime should appear below the last frame, not over it
+    if inline {
+        move_below_viewport(terminal)?;
+    }
+    terminal.0.show_cursor()?;
+
+    if inline {
+        teardown_inline_terminal()
+    } else {
+        teardown_terminal_with(&mut TerminalOutput::open(settings.output)?)
+    }
+}
+
+/// Take the terminal back after [`release_terminal`], clearing it so the next frame is drawn in
+/// full over whatever was left on the screen.
+pub(crate) fn reclaim_terminal(
+    settings: &TuiTerminalSettings,
+    terminal: &mut BevyTerminal,
+) -> Result<(), Box<dyn Error>> {
+    if matches!(settings.viewport, Viewport::Inline(_)) {
+        initialize_inline_terminal()?;
+    } else {
+        initialize_terminal_with(&mut TerminalOutput::open(settings.output)?)?;
+    }
+
+    // Resizing picks up any change to the size of the terminal, and moves an inline viewport to
+    // where the cursor is now, before clearing the screen
+    let size = terminal.0.size()?;
+    terminal.0.resize(size)?;
+
+    Ok(())
+}
+
+/// Work out how many colors the terminal connected to this process can display. This is run when
+/// the [`TuiPlugin`](crate::TuiPlugin) is built and the result made available as the
+/// [`ColorSupport`] resource.
+///
+/// `COLORTERM` is checked first as it's the only reliable way terminals advertise 24-bit color,
+/// followed by the `colors` capability of the terminfo entry for `TERM`, and finally the name in
+/// `TERM` itself. When nothing can be determined only the 16 named colors are assumed to work.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+///
+/// if detect_color_support() < ColorSupport::TrueColor {
+///     println!("RGB colors will be approximated");
+/// }
+/// ```
+#[must_use]
+pub fn detect_color_support() -> ColorSupport {
+    let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+    if colorterm == "truecolor" || colorterm == "24bit" {
+        return ColorSupport::TrueColor;
+    }
+
+    let term = std::env::var("TERM").unwrap_or_default();
+    if term.is_empty() || term == "dumb" {
+        return ColorSupport::Ansi16;
+    }
+
+    if term.ends_with("-direct") || term.contains("truecolor") || term.contains("24bit") {
+        return ColorSupport::TrueColor;
+    }
+
+    match terminfo_colors(&term) {
+        Some(colors) if colors >= 1 << 24 => ColorSupport::TrueColor,
+        Some(colors) if colors >= 256 => ColorSupport::Indexed256,
+        None if term.contains("256color") => ColorSupport::Indexed256,
+        _ => ColorSupport::Ansi16,
+    }
+}
+
+/// The directories searched for compiled terminfo entries, in the order ncurses searches them.
+fn terminfo_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    if let Some(dir) = std::env::var_os("TERMINFO") {
+        dirs.push(PathBuf::from(dir));
+    }
+
+    if let Some(home) = std::env::var_os("HOME") {
+        dirs.push(PathBuf::from(home).join(".terminfo"));
+    }
+
+    if let Ok(extra) = std::env::var("TERMINFO_DIRS") {
+        dirs.extend(
+            extra
+                .split(':')
+                .filter(|d| !d.is_empty())
+                .map(PathBuf::from),
+        );
+    }
+
+    dirs.extend(
+        [
+            "/etc/terminfo",
+            "/lib/terminfo",
+            "/usr/share/terminfo",
+            "/usr/lib/terminfo",
+        ]
+        .into_iter()
+        .map(PathBuf::from),
+    );
+
+    dirs
+}
+
+/// Look up the `colors` capability of a terminal in the terminfo database, returning `None` when
+/// the entry can't be found, can't be parsed, or doesn't specify the capability.
+fn terminfo_colors(term: &str) -> Option<i32> {
+    let first = term.chars().next()?;
+
+    // Entries are grouped by their first character, or its hex value on some platforms
+    let entry = terminfo_dirs().into_iter().find_map(|dir| {
+        [first.to_string(), format!("{:x}", u32::from(first))]
+            .into_iter()
+            .find_map(|group| std::fs::read(dir.join(group).join(term)).ok())
+    })?;
+
+    parse_terminfo_colors(&entry)
+}
+
+/// Read the `colors` capability out of a compiled terminfo entry in either the legacy 16-bit or
+/// the extended 32-bit number format. Truncated or malformed entries return `None`.
+fn parse_terminfo_colors(entry: &[u8]) -> Option<i32> {
+    let header = |idx: usize| -> Option<u16> {
+        let bytes = entry.get(idx * 2..idx * 2 + 2)?;
+        Some(u16::from_le_bytes(bytes.try_into().ok()?))
+    };
+
+    let number_size = match header(0)? {
+        TERMINFO_MAGIC_16 => 2,
+        TERMINFO_MAGIC_32 => 4,
+        _ => return None,
+    };
+
+    let names_size = usize::from(header(1)?);
+    let bools_count = usize::from(header(2)?);
+    let numbers_count = usize::from(header(3)?);
+
+    if TERMINFO_COLORS >= numbers_count {
+        return None;
+    }
+
+    // The numbers section is aligned to an even offset
+    let mut numbers_start = 12 + names_size + bools_count;
+    numbers_start += numbers_start % 2;
+
+    let offset = numbers_start + TERMINFO_COLORS * number_size;
+    let value = entry.get(offset..offset + number_size)?;
+    let colors = if number_size == 2 {
+        i32::from(i16::from_le_bytes(value.try_into().ok()?))
+    } else {
+        i32::from_le_bytes(value.try_into().ok()?)
+    };
+
+    (colors >= 0).then_some(colors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a compiled terminfo entry with a single name, three boolean capabilities, and the
+    /// provided numeric capabilities written in the format chosen by the magic number.
+    fn entry(magic: u16, numbers: &[i32]) -> Vec<u8> {
+        let names = b"test|fixture\0";
+        let bools = [1, 0, 1];
+
+        let numbers_count = u16::try_from(numbers.len()).expect("a handful of numbers");
+
+        let mut entry = Vec::new();
+        for value in [magic, 13, 3, numbers_count, 0, 0] {
+            entry.extend(value.to_le_bytes());
+        }
+        entry.extend(names);
+        entry.extend(bools);
+
+        // 12 + 13 + 3 is even, so no padding is needed before the numbers
+        for &number in numbers {
+            if magic == TERMINFO_MAGIC_16 {
+                let number = i16::try_from(number).expect("fits in the legacy format");
+                entry.extend(number.to_le_bytes());
+            } else {
+                entry.extend(number.to_le_bytes());
+            }
+        }
+
+        entry
+    }
+
+    fn numbers_with_colors(colors: i32) -> Vec<i32> {
+        let mut numbers = vec![-1; TERMINFO_COLORS + 2];
+        numbers[TERMINFO_COLORS] = colors;
+        numbers
+    }
+
+    #[test]
+    fn reads_colors_from_the_legacy_format() {
+        let entry = entry(TERMINFO_MAGIC_16, &numbers_with_colors(256));
+        assert_eq!(parse_terminfo_colors(&entry), Some(256));
+    }
+
+    #[test]
+    fn reads_colors_from_the_extended_number_format() {
+        let entry = entry(TERMINFO_MAGIC_32, &numbers_with_colors(1 << 24));
+        assert_eq!(parse_terminfo_colors(&entry), Some(1 << 24));
+    }
+
+    #[test]
+    fn aligns_the_numbers_to_an_even_offset() {
+        let mut entry = entry(TERMINFO_MAGIC_16, &numbers_with_colors(88));
+
+        // Growing the names section by one byte needs a padding byte before the numbers
+        entry[2] = 14;
+        entry.insert(12, b'x');
+        entry.insert(12 + 14 + 3, 0);
+
+        assert_eq!(parse_terminfo_colors(&entry), Some(88));
+    }
+
+    #[test]
+    fn missing_capabilities_are_none() {
+        let absent = entry(TERMINFO_MAGIC_16, &numbers_with_colors(-1));
+        assert_eq!(parse_terminfo_colors(&absent), None);
+
+        let cancelled = entry(TERMINFO_MAGIC_32, &numbers_with_colors(-2));
+        assert_eq!(parse_terminfo_colors(&cancelled), None);
+
+        let too_few = entry(TERMINFO_MAGIC_16, &[8; TERMINFO_COLORS]);
+        assert_eq!(parse_terminfo_colors(&too_few), None);
+    }
+
+    #[test]
+    fn truncated_or_malformed_entries_are_none() {
+        for (magic, number_size) in [(TERMINFO_MAGIC_16, 2), (TERMINFO_MAGIC_32, 4)] {
+            let entry = entry(magic, &numbers_with_colors(256));
+            let colors_end = 28 + (TERMINFO_COLORS + 1) * number_size;
+
+            for len in 0..entry.len() {
+                let expected = (len >= colors_end).then_some(256);
+                assert_eq!(
+                    parse_terminfo_colors(&entry[..len]),
+                    expected,
+                    "length {len}"
+                );
+            }
+        }
+
+        let mut bad_magic = entry(TERMINFO_MAGIC_16, &numbers_with_colors(256));
+        bad_magic[0] = 0xff;
+        assert_eq!(parse_terminfo_colors(&bad_magic), None);
+
+        let mut huge_names = entry(TERMINFO_MAGIC_32, &numbers_with_colors(256));
+        huge_names[2..4].copy_from_slice(&u16::MAX.to_le_bytes());
+        assert_eq!(parse_terminfo_colors(&huge_names), None);
+    }
+}
@@ -0,0 +1,359 @@
+use bevy::ecs::change_detection::DetectChanges;
+use bevy::ecs::component::Component;
+use bevy::ecs::system::{Commands, Res, ResMut, Resource};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, BorderType, Borders};
+
+use crate::render::TuiRenderSettings;
+
+#[cfg(feature = "assets")]
+mod asset;
+
+#[cfg(feature = "assets")]
+pub use asset::TuiThemeHandle;
+#[cfg(feature = "assets")]
+pub(crate) use asset::{theme_asset_system, TuiThemeLoader};
+
+/// The named styles a [`TuiTheme`] provides.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+///
+/// let theme = TuiTheme::dark();
+/// let error_style = theme.style(StyleRole::Error);
+/// ```
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum StyleRole {
+    /// Highlighted content such as gauges, sparklines, and headings.
+    Primary,
+
+    /// The borders of blocks that don't have focus.
+    Border,
+
+    /// The borders of blocks that have focus.
+    Focused,
+
+    /// Content reporting a failure or otherwise requiring attention.
+    Error,
+
+    /// The selected row of lists and tables, and the selected text of inputs.
+    Selection,
+}
+
+/// The set of styles widgets resolve at render time, allowing the look of an entire application to
+/// be changed in one place. Replacing the resource at runtime will restyle everything on the next
+/// frame.
+///
+/// The built-in widgets only replace their own settings with the theme's on entities with a
+/// [`Themed`] component, which every widget bundle includes. Custom widgets can access the theme
+/// through [`TuiRenderContext::theme`](crate::render::TuiRenderContext::theme).
+///
+/// With the `assets` feature enabled themes can also be loaded from files, see `TuiThemeHandle`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn toggle_theme(key_code: Res<ButtonInput<KeyCode>>, mut theme: ResMut<TuiTheme>) {
+///     if key_code.just_pressed(KeyCode::KeyT) {
+///         *theme = if *theme == TuiTheme::dark() {
+///             TuiTheme::light()
+///         } else {
+///             TuiTheme::dark()
+///         };
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins(MinimalTuiPlugins)
+///     .insert_resource(TuiTheme::light())
+///     .add_systems(Update, toggle_theme)
+///     .run();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Resource)]
+#[cfg_attr(feature = "assets", derive(bevy::asset::Asset))]
+pub struct TuiTheme {
+    /// The style used by [`StyleRole::Primary`].
+    pub primary: Style,
+
+    /// The style used by [`StyleRole::Border`].
+    pub border: Style,
+
+    /// The style used by [`StyleRole::Focused`].
+    pub focused: Style,
+
+    /// The style used by [`StyleRole::Error`].
+    pub error: Style,
+
+    /// The style used by [`StyleRole::Selection`].
+    pub selection: Style,
+
+    /// The border type given to the blocks of [`Themed`] entities.
+    pub border_type: BorderType,
+}
+
+impl TuiTheme {
+    /// A theme intended for terminals with a dark background.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// assert_eq!(TuiTheme::default(), TuiTheme::dark());
+    /// ```
+    #[must_use]
+    pub fn dark() -> Self {
+        Self {
+            primary: Style::default().fg(Color::Cyan),
+            border: Style::default().fg(Color::White),
+            focused: Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            error: Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            selection: Style::default().fg(Color::Black).bg(Color::Cyan),
+            border_type: BorderType::Rounded,
+        }
+    }
+
+    /// A theme intended for terminals with a light background.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// assert_ne!(TuiTheme::light(), TuiTheme::dark());
+    /// ```
+    #[must_use]
+    pub fn light() -> Self {
+        Self {
+            primary: Style::default().fg(Color::Blue),
+            border: Style::default().fg(Color::DarkGray),
+            focused: Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+            error: Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            selection: Style::default().fg(Color::White).bg(Color::Blue),
+            border_type: BorderType::Rounded,
+        }
+    }
+
+    /// A theme that only uses modifiers so it remains usable when colors are unavailable or have
+    /// been disabled with `NO_COLOR`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::style::Modifier;
+    ///
+    /// let theme = TuiTheme::monochrome();
+    /// assert!(theme.selection.add_modifier.contains(Modifier::REVERSED));
+    /// ```
+    #[must_use]
+    pub fn monochrome() -> Self {
+        Self {
+            primary: Style::default().add_modifier(Modifier::BOLD),
+            border: Style::default(),
+            focused: Style::default().add_modifier(Modifier::BOLD),
+            error: Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            selection: Style::default().add_modifier(Modifier::REVERSED),
+            border_type: BorderType::Rounded,
+        }
+    }
+
+    /// Look up the style for a role.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let theme = TuiTheme::dark();
+    /// assert_eq!(theme.style(StyleRole::Border), theme.border);
+    /// ```
+    #[must_use]
+    pub fn style(&self, role: StyleRole) -> Style {
+        match role {
+            StyleRole::Primary => self.primary,
+            StyleRole::Border => self.border,
+            StyleRole::Focused => self.focused,
+            StyleRole::Error => self.error,
+            StyleRole::Selection => self.selection,
+        }
+    }
+
+    /// A bordered block using the theme's border type and style. This is intended for code drawing
+    /// by hand, blocks drawn as part of a [`TuiBlock`](crate::widgets::TuiBlock) have the theme
+    /// applied automatically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let block = TuiTheme::dark().block().title(" Pos ");
+    /// ```
+    #[must_use]
+    pub fn block(&self) -> Block<'static> {
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(self.border)
+            .border_type(self.border_type)
+    }
+}
+
+impl Default for TuiTheme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+/// The theme picked for the application when it didn't provide its own. While this is present the
+/// [`TuiTheme`] follows the [`TuiRenderSettings`], it is removed as soon as the application
+/// replaces or changes the theme.
+#[derive(Resource)]
+pub(crate) struct DefaultTheme(TuiTheme);
+
+impl DefaultTheme {
+    /// The built-in theme suited to the render settings. Colors would be stripped from the default
+    /// theme leaving nothing to distinguish the focus or selection, so a monochrome terminal gets
+    /// one using modifiers instead.
+    fn for_settings(settings: TuiRenderSettings) -> TuiTheme {
+        if settings.monochrome {
+            TuiTheme::monochrome()
+        } else {
+            TuiTheme::default()
+        }
+    }
+}
+
+/// Inserts a built-in [`TuiTheme`] matching the [`TuiRenderSettings`] if the application hasn't
+/// inserted a theme of its own before it started running.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn theme_setup(
+    mut commands: Commands,
+    settings: Res<TuiRenderSettings>,
+    theme: Option<Res<TuiTheme>>,
+) {
+    if theme.is_some() {
+        return;
+    }
+
+    let theme = DefaultTheme::for_settings(*settings);
+    commands.insert_resource(DefaultTheme(theme.clone()));
+    commands.insert_resource(theme);
+}
+
+/// Swaps the built-in theme when the [`TuiRenderSettings`] change, until the application sets a
+/// theme of its own.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn default_theme_system(
+    mut commands: Commands,
+    settings: Res<TuiRenderSettings>,
+    mut default: ResMut<DefaultTheme>,
+    mut theme: ResMut<TuiTheme>,
+) {
+    if *theme != default.0 {
+        commands.remove_resource::<DefaultTheme>();
+        return;
+    }
+
+    if !settings.is_changed() {
+        return;
+    }
+
+    let picked = DefaultTheme::for_settings(*settings);
+    if picked != default.0 {
+        default.0 = picked.clone();
+        *theme = picked;
+    }
+}
+
+/// Applies the style of a [`TuiTheme`] role to the entire area of a node. This is applied before
+/// any [`TuiStyle`](crate::render::TuiStyle) on the same entity.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::Paragraph;
+///
+/// fn spawn_error(mut commands: Commands) {
+///     commands.spawn((
+///         ParagraphBundle {
+///             paragraph: TuiParagraph(Paragraph::new("Connection lost")),
+///             ..default()
+///         },
+///         ThemeStyle(StyleRole::Error),
+///     ));
+/// }
+/// ```
+#[derive(Clone, Copy, Component, Debug, PartialEq, Eq)]
+pub struct ThemeStyle(pub StyleRole);
+
+/// Chooses which settings of the built-in widgets on an entity are replaced by the ones from the
+/// current [`TuiTheme`]. Every widget bundle includes this with everything themed, turn a setting
+/// off to keep the one the widget was given instead. Widgets on entities without this component
+/// are drawn as they were configured, apart from the theme's border and focus styles which are
+/// always applied beneath the style of a [`TuiBlock`](crate::widgets::TuiBlock).
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::{Block, BorderType};
+///
+/// fn spawn_plain_panel(mut commands: Commands) {
+///     commands.spawn(BlockBundle {
+///         block: TuiBlock(Block::bordered().border_type(BorderType::Plain)),
+///         themed: Themed {
+///             border_type: false,
+///             ..default()
+///         },
+///         ..default()
+///     });
+/// }
+/// ```
+#[derive(Clone, Copy, Component, Debug, PartialEq, Eq)]
+pub struct Themed {
+    /// Draw the borders of a [`TuiBlock`](crate::widgets::TuiBlock) with
+    /// [`TuiTheme::border_type`].
+    pub border_type: bool,
+
+    /// Draw gauges and sparklines with [`TuiTheme::primary`].
+    pub primary: bool,
+
+    /// Highlight the selected row of lists and tables, and the selected text of inputs, with
+    /// [`TuiTheme::selection`].
+    pub selection: bool,
+}
+
+impl Themed {
+    /// Every setting replaced by the theme, the default.
+    pub const ALL: Self = Self {
+        border_type: true,
+        primary: true,
+        selection: true,
+    };
+
+    /// Every setting kept as the widgets were configured, the same as leaving the component off.
+    pub const NONE: Self = Self {
+        border_type: false,
+        primary: false,
+        selection: false,
+    };
+}
+
+impl Default for Themed {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
@@ -0,0 +1,286 @@
+use std::fmt::{self, Display, Formatter};
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetEvent, AssetLoader, Assets, AsyncReadExt, Handle, LoadContext};
+use bevy::ecs::change_detection::DetectChanges;
+use bevy::ecs::event::EventReader;
+use bevy::ecs::system::{Res, ResMut, Resource};
+use bevy::reflect::TypePath;
+use bevy::utils::BoxedFuture;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::BorderType;
+use ron::extensions::Extensions;
+use serde::Deserialize;
+
+use crate::theme::TuiTheme;
+
+/// Selects the theme asset that should be used as the active [`TuiTheme`]. Whenever the asset
+/// finishes loading, is modified on disk, or this resource is pointed at a different handle the
+/// loaded theme is copied into the [`TuiTheme`] resource.
+///
+/// Themes are loaded from files ending in `.theme.ron` or `.theme.toml`. Every field is optional,
+/// anything left out is taken from [`TuiTheme::dark`]:
+///
+/// ```ron
+/// (
+///     primary: (fg: "blue"),
+///     focused: (fg: "magenta", add_modifier: "BOLD"),
+///     selection: (fg: "white", bg: "#268bd2"),
+///     border_type: Double,
+/// )
+/// ```
+///
+/// When the `file_watcher` feature is enabled the theme will be reloaded as soon as the file
+/// changes, without needing to restart the application.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn load_theme(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     let handle = asset_server.load("themes/solarized.theme.ron");
+///     commands.insert_resource(TuiThemeHandle(handle));
+/// }
+///
+/// App::new()
+///     .add_plugins(MinimalTuiPlugins)
+///     .add_systems(Startup, load_theme)
+///     .run();
+/// ```
+#[derive(Clone, Debug, Resource)]
+pub struct TuiThemeHandle(pub Handle<TuiTheme>);
+
+// The derived implementation unwraps internally which our lints don't allow.
+impl TypePath for TuiTheme {
+    fn type_path() -> &'static str {
+        "bevy_tui::theme::TuiTheme"
+    }
+
+    fn short_type_path() -> &'static str {
+        "TuiTheme"
+    }
+}
+
+/// Loads [`TuiTheme`] assets from RON or TOML files, chosen by the file's extension.
+#[derive(Default)]
+pub(crate) struct TuiThemeLoader;
+
+impl AssetLoader for TuiThemeLoader {
+    type Asset = TuiTheme;
+    type Settings = ();
+    type Error = TuiThemeLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<TuiTheme, TuiThemeLoaderError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            let is_toml = load_context
+                .path()
+                .extension()
+                .map_or(false, |ext| ext == "toml");
+
+            let file: ThemeFile = if is_toml {
+                toml::from_str(&String::from_utf8_lossy(&bytes))?
+            } else {
+                // Allows colors to be written without `Some(..)` and modifiers without the
+                // `Modifier(..)` wrapper the same way they're written in TOML
+                ron::Options::default()
+                    .with_default_extension(Extensions::IMPLICIT_SOME | Extensions::UNWRAP_NEWTYPES)
+                    .from_bytes(&bytes)?
+            };
+
+            Ok(file.into())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron", "theme.toml"]
+    }
+}
+
+/// The reasons a theme asset can fail to load.
+#[derive(Debug)]
+pub(crate) enum TuiThemeLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    Toml(toml::de::Error),
+}
+
+impl Display for TuiThemeLoaderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read theme: {err}"),
+            Self::Ron(err) => write!(f, "failed to parse RON theme: {err}"),
+            Self::Toml(err) => write!(f, "failed to parse TOML theme: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for TuiThemeLoaderError {}
+
+impl From<std::io::Error> for TuiThemeLoaderError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for TuiThemeLoaderError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        Self::Ron(err)
+    }
+}
+
+impl From<toml::de::Error> for TuiThemeLoaderError {
+    fn from(err: toml::de::Error) -> Self {
+        Self::Toml(err)
+    }
+}
+
+/// The on-disk representation of a [`TuiTheme`]. This is kept separate from the theme itself so
+/// every field can be left out of the file.
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeFile {
+    primary: StyleFile,
+    border: StyleFile,
+    focused: StyleFile,
+    error: StyleFile,
+    selection: StyleFile,
+    border_type: BorderTypeFile,
+}
+
+impl Default for ThemeFile {
+    fn default() -> Self {
+        let theme = TuiTheme::dark();
+
+        Self {
+            primary: theme.primary.into(),
+            border: theme.border.into(),
+            focused: theme.focused.into(),
+            error: theme.error.into(),
+            selection: theme.selection.into(),
+            border_type: theme.border_type.into(),
+        }
+    }
+}
+
+impl From<ThemeFile> for TuiTheme {
+    fn from(file: ThemeFile) -> Self {
+        Self {
+            primary: file.primary.into(),
+            border: file.border.into(),
+            focused: file.focused.into(),
+            error: file.error.into(),
+            selection: file.selection.into(),
+            border_type: file.border_type.into(),
+        }
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct StyleFile {
+    fg: Option<Color>,
+    bg: Option<Color>,
+    add_modifier: Modifier,
+    sub_modifier: Modifier,
+}
+
+impl From<Style> for StyleFile {
+    fn from(style: Style) -> Self {
+        Self {
+            fg: style.fg,
+            bg: style.bg,
+            add_modifier: style.add_modifier,
+            sub_modifier: style.sub_modifier,
+        }
+    }
+}
+
+impl From<StyleFile> for Style {
+    fn from(file: StyleFile) -> Self {
+        let mut style = Style::default()
+            .add_modifier(file.add_modifier)
+            .remove_modifier(file.sub_modifier);
+        style.fg = file.fg;
+        style.bg = file.bg;
+        style
+    }
+}
+
+/// Mirrors [`BorderType`] which doesn't implement `Deserialize` itself.
+#[derive(Clone, Copy, Deserialize)]
+enum BorderTypeFile {
+    Plain,
+    Rounded,
+    Double,
+    Thick,
+    QuadrantInside,
+    QuadrantOutside,
+}
+
+impl From<BorderType> for BorderTypeFile {
+    fn from(border_type: BorderType) -> Self {
+        match border_type {
+            BorderType::Plain => Self::Plain,
+            BorderType::Rounded => Self::Rounded,
+            BorderType::Double => Self::Double,
+            BorderType::Thick => Self::Thick,
+            BorderType::QuadrantInside => Self::QuadrantInside,
+            BorderType::QuadrantOutside => Self::QuadrantOutside,
+        }
+    }
+}
+
+impl From<BorderTypeFile> for BorderType {
+    fn from(file: BorderTypeFile) -> Self {
+        match file {
+            BorderTypeFile::Plain => Self::Plain,
+            BorderTypeFile::Rounded => Self::Rounded,
+            BorderTypeFile::Double => Self::Double,
+            BorderTypeFile::Thick => Self::Thick,
+            BorderTypeFile::QuadrantInside => Self::QuadrantInside,
+            BorderTypeFile::QuadrantOutside => Self::QuadrantOutside,
+        }
+    }
+}
+
+/// Copies the theme selected by [`TuiThemeHandle`] into the [`TuiTheme`] resource whenever it
+/// (re)loads or the handle is changed.
+#[allow(clippy::needless_pass_by_value)]
+pub(crate) fn theme_asset_system(
+    handle: Option<Res<TuiThemeHandle>>,
+    mut asset_events: EventReader<AssetEvent<TuiTheme>>,
+    themes: Res<Assets<TuiTheme>>,
+    mut theme: ResMut<TuiTheme>,
+) {
+    let Some(handle) = handle else {
+        asset_events.clear();
+        return;
+    };
+
+    let id = handle.0.id();
+    let reloaded = asset_events
+        .read()
+        .filter(|event| event.is_loaded_with_dependencies(id) || event.is_modified(id))
+        .count()
+        > 0;
+
+    if !reloaded && !handle.is_changed() {
+        return;
+    }
+
+    if let Some(loaded) = themes.get(id) {
+        if *theme != *loaded {
+            *theme = loaded.clone();
+        }
+    }
+}
@@ -0,0 +1,469 @@
+use bevy::ecs::bundle::Bundle;
+use bevy::ecs::component::Component;
+use ratatui::layout::Constraint;
+use ratatui::widgets::{
+    Block, Gauge, List, ListState, Paragraph, Row, Sparkline, Table, TableState,
+};
+
+use crate::focus::Focusable;
+use crate::layout::TuiNode;
+use crate::render::{TuiRenderContext, TuiStyle, TuiWidget};
+use crate::theme::Themed;
+
+mod scroll;
+mod text_input;
+
+pub(crate) use scroll::scroll_system;
+use scroll::Scrollable;
+
+pub(crate) use text_input::text_input_system;
+pub use text_input::{TextInput, TextInputBundle};
+
+/// A bordered and optionally titled region. When present on an entity the other widgets on that
+/// entity, as well as any children in the layout, are placed inside of the block's borders.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::{Block, Borders};
+///
+/// TuiBlock(Block::default().title(" Status ").borders(Borders::ALL));
+/// ```
+#[derive(Clone, Component, Debug, Default, PartialEq, Eq)]
+pub struct TuiBlock(pub Block<'static>);
+
+/// A component wrapping a ratatui [`Paragraph`] of text.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::Paragraph;
+///
+/// TuiParagraph(Paragraph::new("Hello Bevy!"));
+/// ```
+#[derive(Clone, Component, Debug, Default, PartialEq, Eq)]
+pub struct TuiParagraph(pub Paragraph<'static>);
+
+impl TuiWidget for TuiParagraph {
+    fn render(&mut self, ctx: &mut TuiRenderContext<'_, '_>) {
+        ctx.render_widget(self.0.clone());
+    }
+}
+
+/// A component wrapping a ratatui [`List`] of items along with its selection state. While
+/// focused the selection can be moved with the arrow keys, Page Up / Page Down, and Home / End,
+/// and with the scroll wheel while the mouse is over it. Every change to the selection sends a
+/// [`Selected`](crate::prelude::Selected) event.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::List;
+///
+/// let list = TuiList::new(List::new(["first", "second", "third"]));
+/// assert_eq!(list.state.selected(), None);
+/// ```
+#[derive(Clone, Component, Debug, Default)]
+pub struct TuiList {
+    /// The list being displayed.
+    pub list: List<'static>,
+
+    /// The selection and scroll offset of the list.
+    pub state: ListState,
+
+    visible_rows: usize,
+}
+
+impl TuiList {
+    /// Wrap a list with nothing selected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::widgets::List;
+    ///
+    /// TuiList::new(List::new(["alpha", "beta"]));
+    /// ```
+    #[must_use]
+    pub fn new(list: List<'static>) -> Self {
+        Self {
+            list,
+            ..Default::default()
+        }
+    }
+}
+
+impl Scrollable for TuiList {
+    fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    fn page_size(&self) -> usize {
+        self.visible_rows
+    }
+
+    fn selected(&self) -> Option<usize> {
+        self.state.selected()
+    }
+
+    fn select(&mut self, index: Option<usize>) {
+        self.state.select(index);
+    }
+}
+
+impl TuiWidget for TuiList {
+    fn render(&mut self, ctx: &mut TuiRenderContext<'_, '_>) {
+        self.visible_rows = usize::from(ctx.area().height);
+
+        let mut list = self.list.clone();
+        if ctx.themed().selection {
+            list = list.highlight_style(ctx.theme().selection);
+        }
+        ctx.render_stateful_widget(list, &mut self.state);
+    }
+}
+
+/// A component wrapping a ratatui [`Table`] along with its selection state. The table widget
+/// doesn't expose its rows so they are owned here and attached to the table when it gets drawn.
+/// The selection responds to the same keys and mouse wheel as [`TuiList`].
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+/// use ratatui::layout::Constraint;
+/// use ratatui::widgets::Row;
+///
+/// TuiTable::new(
+///     [Row::new(["alpha", "1"]), Row::new(["beta", "2"])],
+///     [Constraint::Min(10), Constraint::Length(3)],
+/// );
+/// ```
+#[derive(Clone, Component, Debug, Default)]
+pub struct TuiTable {
+    /// The rows to be displayed. Any rows already set on the table will be replaced by these.
+    pub rows: Vec<Row<'static>>,
+
+    /// The selection and scroll offset of the table.
+    pub state: TableState,
+
+    /// The table widget used to display the rows, this is where the widths, header, and styles
+    /// are configured.
+    pub table: Table<'static>,
+
+    visible_rows: usize,
+}
+
+impl TuiTable {
+    /// Create a new table from its rows and column widths with nothing selected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use ratatui::layout::Constraint;
+    /// use ratatui::widgets::Row;
+    ///
+    /// TuiTable::new([Row::new(["alpha"])], [Constraint::Min(5)]);
+    /// ```
+    pub fn new<R, C>(rows: R, widths: C) -> Self
+    where
+        R: IntoIterator,
+        R::Item: Into<Row<'static>>,
+        C: IntoIterator,
+        C::Item: Into<Constraint>,
+    {
+        Self {
+            rows: rows.into_iter().map(Into::into).collect(),
+            table: Table::default().widths(widths),
+            ..Default::default()
+        }
+    }
+}
+
+impl Scrollable for TuiTable {
+    fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn page_size(&self) -> usize {
+        self.visible_rows
+    }
+
+    fn selected(&self) -> Option<usize> {
+        self.state.selected()
+    }
+
+    fn select(&mut self, index: Option<usize>) {
+        self.state.select(index);
+    }
+}
+
+impl TuiWidget for TuiTable {
+    fn render(&mut self, ctx: &mut TuiRenderContext<'_, '_>) {
+        self.visible_rows = usize::from(ctx.area().height);
+
+        let mut table = self.table.clone();
+        if ctx.themed().selection {
+            table = table.highlight_style(ctx.theme().selection);
+        }
+        ctx.render_stateful_widget(table.rows(self.rows.clone()), &mut self.state);
+    }
+}
+
+/// A component wrapping a ratatui [`Gauge`] progress bar.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::Gauge;
+///
+/// TuiGauge(Gauge::default().percent(42));
+/// ```
+#[derive(Clone, Component, Debug, Default, PartialEq)]
+pub struct TuiGauge(pub Gauge<'static>);
+
+impl TuiWidget for TuiGauge {
+    fn render(&mut self, ctx: &mut TuiRenderContext<'_, '_>) {
+        let mut gauge = self.0.clone();
+        if ctx.themed().primary {
+            gauge = gauge.gauge_style(ctx.theme().primary);
+        }
+        ctx.render_widget(gauge);
+    }
+}
+
+/// A component wrapping a ratatui [`Sparkline`]. The sparkline widget borrows its data so the
+/// values are owned here and attached to the widget when it gets drawn.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+///
+/// let mut sparkline = TuiSparkline::default();
+/// sparkline.data.extend([1, 4, 2, 8]);
+/// ```
+#[derive(Clone, Component, Debug, Default, PartialEq, Eq)]
+pub struct TuiSparkline {
+    /// The values to be displayed, the oldest value first.
+    pub data: Vec<u64>,
+
+    /// The sparkline widget used to display the data. Any data already set on the widget will be
+    /// replaced by [`TuiSparkline::data`].
+    pub sparkline: Sparkline<'static>,
+}
+
+impl TuiWidget for TuiSparkline {
+    fn render(&mut self, ctx: &mut TuiRenderContext<'_, '_>) {
+        let mut sparkline = self.sparkline.clone();
+        if ctx.themed().primary {
+            sparkline = sparkline.style(ctx.theme().primary);
+        }
+        ctx.render_widget(sparkline.data(&self.data));
+    }
+}
+
+/// A bordered region that can contain other nodes.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::Block;
+///
+/// fn spawn_panel(mut commands: Commands) {
+///     commands.spawn(BlockBundle {
+///         block: TuiBlock(Block::bordered().title(" Panel ")),
+///         ..default()
+///     });
+/// }
+/// ```
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct BlockBundle {
+    /// The layout area of the block.
+    pub node: TuiNode,
+
+    /// The block being drawn.
+    pub block: TuiBlock,
+
+    /// The base style applied to the entire area of the block.
+    pub style: TuiStyle,
+
+    /// The settings of the block replaced by the theme.
+    pub themed: Themed,
+}
+
+/// A node displaying a paragraph of text.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::Paragraph;
+///
+/// fn spawn_text(mut commands: Commands) {
+///     commands.spawn(ParagraphBundle {
+///         paragraph: TuiParagraph(Paragraph::new("Press 'q' to quit.")),
+///         ..default()
+///     });
+/// }
+/// ```
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct ParagraphBundle {
+    /// The layout area of the paragraph.
+    pub node: TuiNode,
+
+    /// The paragraph being drawn.
+    pub paragraph: TuiParagraph,
+
+    /// The base style applied to the entire area of the paragraph.
+    pub style: TuiStyle,
+
+    /// The settings of the paragraph replaced by the theme.
+    pub themed: Themed,
+}
+
+/// A node displaying a scrollable list of items. Lists can be focused by default so they can be
+/// navigated with the keyboard.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::List;
+///
+/// fn spawn_list(mut commands: Commands) {
+///     commands.spawn(ListBundle {
+///         list: TuiList::new(List::new(["alpha", "beta"])),
+///         ..default()
+///     });
+/// }
+/// ```
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct ListBundle {
+    /// The layout area of the list.
+    pub node: TuiNode,
+
+    /// The list being drawn.
+    pub list: TuiList,
+
+    /// The base style applied to the entire area of the list.
+    pub style: TuiStyle,
+
+    /// The settings of the list replaced by the theme.
+    pub themed: Themed,
+
+    /// Allows the list to receive keyboard focus.
+    pub focusable: Focusable,
+}
+
+/// A node displaying a scrollable table of rows. Tables can be focused by default so they can be
+/// navigated with the keyboard.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::layout::Constraint;
+/// use ratatui::widgets::Row;
+///
+/// fn spawn_table(mut commands: Commands) {
+///     let rows = [Row::new(["alpha", "1"])];
+///
+///     commands.spawn(TableBundle {
+///         table: TuiTable::new(rows, [Constraint::Min(5); 2]),
+///         ..default()
+///     });
+/// }
+/// ```
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct TableBundle {
+    /// The layout area of the table.
+    pub node: TuiNode,
+
+    /// The table being drawn.
+    pub table: TuiTable,
+
+    /// The base style applied to the entire area of the table.
+    pub style: TuiStyle,
+
+    /// The settings of the table replaced by the theme.
+    pub themed: Themed,
+
+    /// Allows the table to receive keyboard focus.
+    pub focusable: Focusable,
+}
+
+/// A node displaying a progress gauge.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+/// use ratatui::widgets::Gauge;
+///
+/// fn spawn_gauge(mut commands: Commands) {
+///     commands.spawn(GaugeBundle {
+///         gauge: TuiGauge(Gauge::default().ratio(0.5)),
+///         ..default()
+///     });
+/// }
+/// ```
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct GaugeBundle {
+    /// The layout area of the gauge.
+    pub node: TuiNode,
+
+    /// The gauge being drawn.
+    pub gauge: TuiGauge,
+
+    /// The base style applied to the entire area of the gauge.
+    pub style: TuiStyle,
+
+    /// The settings of the gauge replaced by the theme.
+    pub themed: Themed,
+}
+
+/// A node displaying a sparkline of values.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn spawn_sparkline(mut commands: Commands) {
+///     commands.spawn(SparklineBundle {
+///         sparkline: TuiSparkline {
+///             data: vec![1, 3, 2, 5],
+///             ..default()
+///         },
+///         ..default()
+///     });
+/// }
+/// ```
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct SparklineBundle {
+    /// The layout area of the sparkline.
+    pub node: TuiNode,
+
+    /// The sparkline being drawn.
+    pub sparkline: TuiSparkline,
+
+    /// The base style applied to the entire area of the sparkline.
+    pub style: TuiStyle,
+
+    /// The settings of the sparkline replaced by the theme.
+    pub themed: Themed,
+}
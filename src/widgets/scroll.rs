@@ -0,0 +1,181 @@
+use bevy::ecs::component::Component;
+use bevy::ecs::entity::Entity;
+use bevy::ecs::event::{EventReader, EventWriter};
+use bevy::ecs::query::{With, Without};
+use bevy::ecs::system::{Query, Res};
+use bevy::hierarchy::{Children, Parent};
+use crossterm::event::{Event as CrossEvent, KeyCode, KeyEventKind};
+use ratatui::layout::Position;
+
+use crate::events::{FocusedInput, Selected};
+use crate::focus::descendants;
+use crate::input::MouseInput;
+use crate::layout::{LayoutAreas, TuiNode};
+use crate::session::{unclaimed_roots, SessionInput, TuiSession};
+
+/// The common operations needed to move the selection of a widget containing rows.
+pub(crate) trait Scrollable: Component {
+    fn len(&self) -> usize;
+
+    /// The number of rows that fit in the widget the last time it was drawn.
+    fn page_size(&self) -> usize;
+
+    fn selected(&self) -> Option<usize>;
+
+    fn select(&mut self, index: Option<usize>);
+}
+
+/// Works out where the selection should move to in response to a key, or `None` if the key
+/// doesn't move the selection.
+fn keyboard_target(key: KeyCode, current: Option<usize>, len: usize, page: usize) -> Option<usize> {
+    let last = len.checked_sub(1)?;
+    let page = page.max(1);
+
+    let target = match (key, current) {
+        (KeyCode::Home, _) | (KeyCode::Down | KeyCode::PageDown, None) => 0,
+        (KeyCode::End, _) | (KeyCode::Up | KeyCode::PageUp, None) => last,
+        (KeyCode::Up, Some(idx)) => idx.saturating_sub(1),
+        (KeyCode::Down, Some(idx)) => idx.saturating_add(1),
+        (KeyCode::PageUp, Some(idx)) => idx.saturating_sub(page),
+        (KeyCode::PageDown, Some(idx)) => idx.saturating_add(page),
+        _ => return None,
+    };
+
+    Some(target.min(last))
+}
+
+fn scroll_target(delta: i8, current: Option<usize>, len: usize) -> Option<usize> {
+    let last = len.checked_sub(1)?;
+
+    let target = match current {
+        None => 0,
+        Some(idx) if delta < 0 => idx.saturating_sub(usize::from(delta.unsigned_abs())),
+        Some(idx) => idx.saturating_add(usize::from(delta.unsigned_abs())),
+    };
+
+    Some(target.min(last))
+}
+
+fn apply<S: Scrollable>(
+    entity: Entity,
+    scrollable: &mut S,
+    target: Option<usize>,
+    selected: &mut EventWriter<Selected>,
+) {
+    let Some(target) = target else {
+        return;
+    };
+
+    if scrollable.selected() != Some(target) {
+        scrollable.select(Some(target));
+        selected.send(Selected(entity, target));
+    }
+}
+
+/// Moves the selection of every scrollable widget beneath the provided roots that is under the
+/// mouse cursor in response to a scroll wheel event, going by the areas they were laid out with on
+/// the terminal the event came from.
+fn scroll_hovered<S: Scrollable>(
+    scope: &[Entity],
+    areas: &LayoutAreas,
+    children: &Query<&Children>,
+    scrollables: &mut Query<(Entity, &mut S)>,
+    event: MouseInput,
+    selected: &mut EventWriter<Selected>,
+) {
+    let MouseInput::Scroll([_, delta], [x, y]) = event else {
+        return;
+    };
+
+    if delta == 0 {
+        return;
+    }
+
+    // Only the widgets shown on the terminal the event came from, other terminals may have
+    // something else drawn at the same position
+    for entity in descendants(scope, children) {
+        let Ok((entity, mut scrollable)) = scrollables.get_mut(entity) else {
+            continue;
+        };
+
+        let hovered = areas
+            .area(entity)
+            .map_or(false, |area| area.contains(Position { x, y }));
+        if !hovered {
+            continue;
+        }
+
+        let target = scroll_target(delta, scrollable.selected(), scrollable.len());
+        apply(entity, scrollable.as_mut(), target, selected);
+    }
+}
+
+/// Moves the selection of focused scrollable widgets in response to the keyboard, and of those
+/// under the mouse cursor in response to the scroll wheel. The mouse of the terminal the process
+/// is attached to, and that of each [`TuiSession`], only scrolls the widgets beneath the layout
+/// roots shown on that terminal.
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
+pub(crate) fn scroll_system<S: Scrollable>(
+    mut focused_input: EventReader<FocusedInput>,
+    mut mouse_events: EventReader<MouseInput>,
+    mut selected: EventWriter<Selected>,
+    mut scrollables: Query<(Entity, &mut S)>,
+    roots: Query<Entity, (With<TuiNode>, Without<Parent>)>,
+    children: Query<&Children>,
+    local_areas: Res<LayoutAreas>,
+    sessions: Query<(&TuiSession, &SessionInput, Option<&LayoutAreas>)>,
+) {
+    for FocusedInput { entity, event } in focused_input.read() {
+        let CrossEvent::Key(key) = event else {
+            continue;
+        };
+
+        if key.kind == KeyEventKind::Release {
+            continue;
+        }
+
+        let Ok((entity, mut scrollable)) = scrollables.get_mut(*entity) else {
+            continue;
+        };
+
+        let target = keyboard_target(
+            key.code,
+            scrollable.selected(),
+            scrollable.len(),
+            scrollable.page_size(),
+        );
+        apply(entity, scrollable.as_mut(), target, &mut selected);
+    }
+
+    let all_roots: Vec<Entity> = roots.iter().collect();
+    let unclaimed = unclaimed_roots(&all_roots, sessions.iter().map(|(session, ..)| session));
+
+    for event in mouse_events.read() {
+        scroll_hovered(
+            &unclaimed,
+            &local_areas,
+            &children,
+            &mut scrollables,
+            *event,
+            &mut selected,
+        );
+    }
+
+    // Sessions spawned without their areas have nothing laid out to scroll
+    let unlaid = LayoutAreas::default();
+    for (session, input, areas) in &sessions {
+        let scope = session.roots(&unclaimed);
+        let areas = areas.unwrap_or(&unlaid);
+
+        for event in input.mouse_events() {
+            scroll_hovered(
+                &scope,
+                areas,
+                &children,
+                &mut scrollables,
+                *event,
+                &mut selected,
+            );
+        }
+    }
+}
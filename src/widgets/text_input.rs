@@ -0,0 +1,533 @@
+use std::ops::Range;
+
+use bevy::ecs::bundle::Bundle;
+use bevy::ecs::component::Component;
+use bevy::ecs::event::{EventReader, EventWriter};
+use bevy::ecs::system::Query;
+use crossterm::event::{Event as CrossEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use unicode_width::UnicodeWidthChar;
+
+use crate::events::{FocusedInput, TextInputSubmitted};
+use crate::focus::Focusable;
+use crate::layout::TuiNode;
+use crate::render::{TuiRenderContext, TuiStyle, TuiWidget};
+use crate::theme::Themed;
+
+/// A single line text entry field. While focused it consumes the typed characters, pastes, and
+/// editing keys delivered through [`FocusedInput`] and positions the terminal's cursor within
+/// itself when drawn.
+///
+/// The following keys are supported:
+///
+/// * Left / Right move the cursor, Ctrl or Alt jump by words
+/// * Home / End jump to the start or end of the line
+/// * Shift combined with any of the movement keys extends the selection
+/// * Backspace / Delete remove the selection or the character next to the cursor, Ctrl removes a
+///   whole word
+/// * Enter sends a [`TextInputSubmitted`] event
+///
+/// # Examples
+///
+/// ```
+/// use bevy_tui::prelude::*;
+///
+/// let mut input = TextInput::new().with_value("hello");
+/// input.insert_str(" world");
+/// assert_eq!(input.value(), "hello world");
+/// ```
+#[derive(Clone, Component, Debug, PartialEq, Eq)]
+pub struct TextInput {
+    /// Text displayed in place of the value when it is empty.
+    pub placeholder: String,
+
+    /// The style used for the placeholder text.
+    pub placeholder_style: Style,
+
+    /// The style applied to the selected portion of the value. The theme's selection style is
+    /// used instead when the entity is [`Themed`].
+    pub selection_style: Style,
+
+    /// The style applied to the value.
+    pub style: Style,
+
+    anchor: Option<usize>,
+    cursor: usize,
+    scroll: usize,
+    value: String,
+}
+
+impl TextInput {
+    /// Create a new empty text input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// assert!(TextInput::new().value().is_empty());
+    /// ```
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            placeholder: String::new(),
+            placeholder_style: Style::default().add_modifier(Modifier::DIM),
+            selection_style: Style::default(),
+            style: Style::default(),
+
+            anchor: None,
+            cursor: 0,
+            scroll: 0,
+            value: String::new(),
+        }
+    }
+
+    /// Replace the value of the input, placing the cursor at the end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let input = TextInput::new().with_value("admin");
+    /// assert_eq!(input.cursor(), 5);
+    /// ```
+    #[must_use]
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Sets the text displayed when the input is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// TextInput::new().with_placeholder("username");
+    /// ```
+    #[must_use]
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// The current contents of the input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// assert_eq!(TextInput::new().with_value("abc").value(), "abc");
+    /// ```
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replace the contents of the input, clearing any selection and placing the cursor at the
+    /// end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let mut input = TextInput::new();
+    /// input.set_value("replaced");
+    /// assert_eq!(input.value(), "replaced");
+    /// ```
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.len();
+        self.anchor = None;
+        self.scroll = 0;
+    }
+
+    /// The position of the cursor, measured in characters from the start of the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// assert_eq!(TextInput::new().cursor(), 0);
+    /// ```
+    #[must_use]
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// The range of characters currently selected, if anything is selected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let mut input = TextInput::new().with_value("hello");
+    /// input.select_all();
+    /// assert_eq!(input.selection(), Some(0..5));
+    /// ```
+    #[must_use]
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+
+        match anchor.cmp(&self.cursor) {
+            std::cmp::Ordering::Less => Some(anchor..self.cursor),
+            std::cmp::Ordering::Equal => None,
+            std::cmp::Ordering::Greater => Some(self.cursor..anchor),
+        }
+    }
+
+    /// The currently selected text, if anything is selected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let mut input = TextInput::new().with_value("hello");
+    /// input.select_all();
+    /// assert_eq!(input.selected_text(), Some("hello"));
+    /// ```
+    #[must_use]
+    pub fn selected_text(&self) -> Option<&str> {
+        let range = self.selection()?;
+        Some(&self.value[self.byte_index(range.start)..self.byte_index(range.end)])
+    }
+
+    /// Select the entire value, leaving the cursor at the end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let mut input = TextInput::new().with_value("abc");
+    /// input.select_all();
+    /// assert_eq!(input.selected_text(), Some("abc"));
+    /// ```
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.len();
+    }
+
+    /// Insert text at the cursor, replacing the selection if there is one. Control characters
+    /// such as newlines are dropped as this is a single line input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    ///
+    /// let mut input = TextInput::new().with_value("ac");
+    /// input.insert_str("b");
+    /// assert_eq!(input.value(), "acb");
+    /// ```
+    pub fn insert_str(&mut self, text: &str) {
+        self.delete_selection();
+
+        let filtered: String = text.chars().filter(|c| !c.is_control()).collect();
+        let byte_idx = self.byte_index(self.cursor);
+
+        self.value.insert_str(byte_idx, &filtered);
+        self.cursor += filtered.chars().count();
+    }
+
+    /// Apply a key press to the input. Returns `true` when the key was used by the input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_tui::prelude::*;
+    /// use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    ///
+    /// let mut input = TextInput::new().with_value("hello");
+    /// input.handle_key(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+    /// assert_eq!(input.value(), "hell");
+    /// ```
+    pub fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if key.kind == KeyEventKind::Release {
+            return false;
+        }
+
+        let selecting = key.modifiers.contains(KeyModifiers::SHIFT);
+        let by_word = key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+
+        match key.code {
+            KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::ALT) => {
+                self.move_to(self.previous_word(), selecting);
+            }
+            KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::ALT) => {
+                self.move_to(self.next_word(), selecting);
+            }
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.delete_to(self.previous_word());
+            }
+            KeyCode::Char(_) if by_word => return false,
+            KeyCode::Char(chr) => self.insert_str(chr.encode_utf8(&mut [0; 4])),
+            KeyCode::Left if by_word => self.move_to(self.previous_word(), selecting),
+            KeyCode::Left => {
+                // Moving without extending the selection leaves the cursor at its start
+                let target = match self.selection() {
+                    Some(range) if !selecting => range.start,
+                    _ => self.cursor.saturating_sub(1),
+                };
+                self.move_to(target, selecting);
+            }
+            KeyCode::Right if by_word => self.move_to(self.next_word(), selecting),
+            KeyCode::Right => {
+                let target = match self.selection() {
+                    Some(range) if !selecting => range.end,
+                    _ => (self.cursor + 1).min(self.len()),
+                };
+                self.move_to(target, selecting);
+            }
+            KeyCode::Home => self.move_to(0, selecting),
+            KeyCode::End => self.move_to(self.len(), selecting),
+            KeyCode::Backspace if by_word => self.delete_to(self.previous_word()),
+            KeyCode::Backspace => self.delete_to(self.cursor.saturating_sub(1)),
+            KeyCode::Delete if by_word => self.delete_to(self.next_word()),
+            KeyCode::Delete => self.delete_to((self.cursor + 1).min(self.len())),
+            _ => return false,
+        }
+
+        true
+    }
+
+    fn byte_index(&self, char_idx: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(char_idx)
+            .map_or(self.value.len(), |(idx, _)| idx)
+    }
+
+    /// Removes the selected text if there is any, returning whether anything was removed.
+    fn delete_selection(&mut self) -> bool {
+        let selection = self.selection();
+        self.anchor = None;
+
+        let Some(range) = selection else {
+            return false;
+        };
+
+        let byte_range = self.byte_index(range.start)..self.byte_index(range.end);
+        self.value.replace_range(byte_range, "");
+        self.cursor = range.start;
+
+        true
+    }
+
+    /// Removes the text between the cursor and the provided position, or just the selection if
+    /// there is one.
+    fn delete_to(&mut self, target: usize) {
+        if self.delete_selection() {
+            return;
+        }
+
+        let range = self.cursor.min(target)..self.cursor.max(target);
+        let byte_range = self.byte_index(range.start)..self.byte_index(range.end);
+
+        self.value.replace_range(byte_range, "");
+        self.cursor = range.start;
+    }
+
+    fn len(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    fn move_to(&mut self, target: usize, selecting: bool) {
+        if selecting {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+
+        self.cursor = target;
+    }
+
+    fn next_word(&self) -> usize {
+        let chars: Vec<char> = self.value.chars().collect();
+        let mut idx = self.cursor;
+
+        while idx < chars.len() && !chars[idx].is_alphanumeric() {
+            idx += 1;
+        }
+
+        while idx < chars.len() && chars[idx].is_alphanumeric() {
+            idx += 1;
+        }
+
+        idx
+    }
+
+    fn previous_word(&self) -> usize {
+        let chars: Vec<char> = self.value.chars().collect();
+        let mut idx = self.cursor;
+
+        while idx > 0 && !chars[idx - 1].is_alphanumeric() {
+            idx -= 1;
+        }
+
+        while idx > 0 && chars[idx - 1].is_alphanumeric() {
+            idx -= 1;
+        }
+
+        idx
+    }
+
+    /// Adjusts the horizontal scroll offset so the cursor is visible in the available width.
+    fn scroll_to_cursor(&mut self, width: usize) {
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        }
+
+        // One cell is always left free at the end for the cursor to sit in
+        let available = width.saturating_sub(1);
+        let widths: Vec<usize> = self.value.chars().map(|c| c.width().unwrap_or(0)).collect();
+
+        while self.scroll < self.cursor
+            && widths[self.scroll..self.cursor].iter().sum::<usize>() > available
+        {
+            self.scroll += 1;
+        }
+    }
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TuiWidget for TextInput {
+    fn render(&mut self, ctx: &mut TuiRenderContext<'_, '_>) {
+        let area = ctx.area();
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        if self.value.is_empty() && !self.placeholder.is_empty() {
+            let placeholder = Span::styled(self.placeholder.clone(), self.placeholder_style);
+            ctx.render_widget(Paragraph::new(placeholder));
+        } else {
+            self.scroll_to_cursor(usize::from(area.width));
+
+            let selection = self.selection().unwrap_or_default();
+            let selection_style = if ctx.themed().selection {
+                ctx.theme().selection
+            } else {
+                self.selection_style
+            };
+
+            let mut spans: Vec<Span> = Vec::new();
+
+            for (idx, chr) in self.value.chars().enumerate().skip(self.scroll) {
+                let style = if selection.contains(&idx) {
+                    self.style.patch(selection_style)
+                } else {
+                    self.style
+                };
+
+                spans.push(Span::styled(chr.to_string(), style));
+            }
+
+            ctx.render_widget(Paragraph::new(Line::from(spans)));
+        }
+
+        if ctx.is_focused() {
+            let offset: usize = self
+                .value
+                .chars()
+                .skip(self.scroll)
+                .take(self.cursor.saturating_sub(self.scroll))
+                .map(|c| c.width().unwrap_or(0))
+                .sum();
+
+            let offset = u16::try_from(offset).unwrap_or(u16::MAX);
+            let x = area.x.saturating_add(offset).min(area.right() - 1);
+            ctx.frame().set_cursor(x, area.y);
+        }
+    }
+}
+
+/// A node containing an editable line of text.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_tui::prelude::*;
+///
+/// fn spawn_login(mut commands: Commands) {
+///     commands.spawn(TextInputBundle {
+///         text_input: TextInput::new().with_placeholder("username"),
+///         ..default()
+///     });
+/// }
+/// ```
+#[derive(Bundle, Clone, Debug)]
+pub struct TextInputBundle {
+    /// The layout area of the input.
+    pub node: TuiNode,
+
+    /// The input being edited.
+    pub text_input: TextInput,
+
+    /// The base style applied to the entire area of the input.
+    pub style: TuiStyle,
+
+    /// The settings of the input replaced by the theme.
+    pub themed: Themed,
+
+    /// Text inputs capture the keyboard while focused by default.
+    pub focusable: Focusable,
+}
+
+impl Default for TextInputBundle {
+    fn default() -> Self {
+        Self {
+            node: TuiNode::default(),
+            text_input: TextInput::new(),
+            style: TuiStyle::default(),
+            themed: Themed::default(),
+            focusable: Focusable::capturing(),
+        }
+    }
+}
+
+/// Applies the input delivered to focused text inputs.
+pub(crate) fn text_input_system(
+    mut focused_input: EventReader<FocusedInput>,
+    mut submitted: EventWriter<TextInputSubmitted>,
+    mut inputs: Query<&mut TextInput>,
+) {
+    for FocusedInput { entity, event } in focused_input.read() {
+        let Ok(mut input) = inputs.get_mut(*entity) else {
+            continue;
+        };
+
+        match event {
+            CrossEvent::Key(key) if key.code == KeyCode::Enter => {
+                if key.kind != KeyEventKind::Release {
+                    submitted.send(TextInputSubmitted {
+                        entity: *entity,
+                        value: input.value.clone(),
+                    });
+                }
+            }
+            CrossEvent::Key(key) => {
+                input.handle_key(*key);
+            }
+            CrossEvent::Paste(text) => input.insert_str(text),
+            _ => {}
+        }
+    }
+}
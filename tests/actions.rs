@@ -0,0 +1,224 @@
+//! Checks how key sequences, the counts typed before them and mouse bindings are matched.
+
+use std::time::Duration;
+
+use bevy::ecs::event::Events;
+use bevy::prelude::*;
+use bevy_tui::prelude::*;
+use bevy_tui::RawConsoleEvent;
+use crossterm::event::{Event as CrossEvent, KeyCode, KeyEvent, KeyModifiers};
+
+/// What is matched by keys that don't complete a binding.
+const NOTHING: [(&str, Option<u32>); 0] = [];
+
+/// An app matching the bindings of the map, with the time of each frame advanced by hand.
+fn action_app(map: ActionMap<&'static str>) -> App {
+    let mut app = App::new();
+    app.add_plugins(ActionPlugin::<&'static str>::default())
+        .insert_resource(map);
+
+    // The time only advances from the first update on
+    app.world.resource_mut::<Time<Real>>().update();
+
+    app
+}
+
+/// Type the keys in a single frame once the time has advanced, returning the actions matched
+/// along with their counts.
+fn type_keys(app: &mut App, after: Duration, keys: &str) -> Vec<(&'static str, Option<u32>)> {
+    let chords: Vec<KeyChord> = keys
+        .chars()
+        .map(|ch| KeyChord::new(KeyCode::Char(ch), KeyModifiers::NONE))
+        .collect();
+
+    type_chords(app, after, &chords)
+}
+
+/// Type the chords in a single frame once the time has advanced, returning the actions matched
+/// along with their counts.
+fn type_chords(
+    app: &mut App,
+    after: Duration,
+    chords: &[KeyChord],
+) -> Vec<(&'static str, Option<u32>)> {
+    app.world
+        .resource_mut::<Time<Real>>()
+        .update_with_duration(after);
+
+    app.world.send_event_batch(
+        chords.iter().map(|chord| {
+            RawConsoleEvent(CrossEvent::Key(KeyEvent::new(chord.code, chord.modifiers)))
+        }),
+    );
+    app.update();
+
+    app.world
+        .resource_mut::<Events<KeySequenceMatched<&'static str>>>()
+        .drain()
+        .map(|matched| (matched.action, matched.count))
+        .collect()
+}
+
+#[test]
+fn zero_matches_its_binding_unless_a_count_is_being_typed() {
+    let mut app = action_app(
+        ActionMap::new()
+            .with("start", KeyCode::Char('0'))
+            .with("down", KeyCode::Char('j')),
+    );
+
+    assert_eq!(type_keys(&mut app, Duration::ZERO, "0"), [("start", None)]);
+    assert_eq!(
+        type_keys(&mut app, Duration::ZERO, "10j"),
+        [("down", Some(10))]
+    );
+}
+
+#[test]
+fn zero_doesnt_start_a_count_without_a_binding() {
+    let mut app = action_app(ActionMap::new().with("down", KeyCode::Char('j')));
+
+    assert_eq!(type_keys(&mut app, Duration::ZERO, "0"), NOTHING);
+    assert_eq!(type_keys(&mut app, Duration::ZERO, "j"), [("down", None)]);
+}
+
+#[test]
+fn counts_are_forgotten_once_the_timeout_passes() {
+    let mut app = action_app(ActionMap::new().with("down", KeyCode::Char('j')));
+    let timeout = KeySequenceSettings::default()
+        .timeout
+        .expect("sequences to time out by default");
+
+    assert_eq!(type_keys(&mut app, Duration::ZERO, "5"), NOTHING);
+    assert_eq!(type_keys(&mut app, timeout / 2, "j"), [("down", Some(5))]);
+
+    assert_eq!(type_keys(&mut app, Duration::ZERO, "5"), NOTHING);
+    assert_eq!(type_keys(&mut app, timeout, ""), NOTHING);
+    assert_eq!(type_keys(&mut app, Duration::ZERO, "j"), [("down", None)]);
+}
+
+fn binding(text: &str) -> InputBinding {
+    text.parse().expect("a valid binding")
+}
+
+fn chord(text: &str) -> KeyChord {
+    text.parse().expect("a valid chord")
+}
+
+#[test]
+fn sequences_of_chords_match_once_complete() {
+    let mut app = action_app(ActionMap::new().with("save", binding("ctrl+x ctrl+s")));
+
+    assert_eq!(
+        type_chords(&mut app, Duration::ZERO, &[chord("ctrl+x")]),
+        NOTHING
+    );
+    let pending = app.world.resource::<ActionState<&str>>().pending().clone();
+    assert_eq!(pending.chords(), [chord("ctrl+x")]);
+
+    assert_eq!(
+        type_chords(&mut app, Duration::ZERO, &[chord("ctrl+s")]),
+        [("save", None)]
+    );
+    assert!(app
+        .world
+        .resource::<ActionState<&str>>()
+        .just_pressed("save"));
+
+    // Both chords typed in the same frame match the same way
+    assert_eq!(
+        type_chords(
+            &mut app,
+            Duration::ZERO,
+            &[chord("ctrl+x"), chord("ctrl+s")]
+        ),
+        [("save", None)]
+    );
+}
+
+#[test]
+fn a_binding_that_starts_a_longer_one_waits_for_the_timeout() {
+    let mut app = action_app(
+        ActionMap::new()
+            .with("next", KeyCode::Char('g'))
+            .with("top", binding("g g")),
+    );
+    let timeout = KeySequenceSettings::default()
+        .timeout
+        .expect("sequences to time out by default");
+
+    assert_eq!(type_keys(&mut app, Duration::ZERO, "g"), NOTHING);
+    assert_eq!(type_keys(&mut app, timeout / 2, ""), NOTHING);
+    assert_eq!(type_keys(&mut app, timeout / 2, ""), [("next", None)]);
+
+    assert_eq!(type_keys(&mut app, Duration::ZERO, "g"), NOTHING);
+    assert_eq!(type_keys(&mut app, timeout / 2, "g"), [("top", None)]);
+}
+
+#[test]
+fn a_binding_that_starts_a_longer_one_matches_when_another_key_follows() {
+    let mut app = action_app(
+        ActionMap::new()
+            .with("next", KeyCode::Char('g'))
+            .with("top", binding("g g"))
+            .with("down", KeyCode::Char('j')),
+    );
+
+    assert_eq!(
+        type_keys(&mut app, Duration::ZERO, "gj"),
+        [("next", None), ("down", None)]
+    );
+}
+
+#[test]
+fn an_unrelated_key_abandons_a_sequence() {
+    let mut app = action_app(
+        ActionMap::new()
+            .with("top", binding("g g"))
+            .with("down", KeyCode::Char('j')),
+    );
+
+    assert_eq!(type_keys(&mut app, Duration::ZERO, "gx"), NOTHING);
+    assert!(app
+        .world
+        .resource::<ActionState<&str>>()
+        .pending()
+        .is_empty());
+    assert_eq!(type_keys(&mut app, Duration::ZERO, "g"), NOTHING);
+    assert_eq!(type_keys(&mut app, Duration::ZERO, "g"), [("top", None)]);
+
+    // The key breaking the sequence is tried as the start of a new one
+    assert_eq!(type_keys(&mut app, Duration::ZERO, "gj"), [("down", None)]);
+    assert_eq!(type_keys(&mut app, Duration::ZERO, "3gj"), [("down", None)]);
+}
+
+#[test]
+fn mouse_bindings_are_held_as_long_as_the_button() {
+    let mut app = action_app(ActionMap::new().with("select", binding("mouse:left")));
+
+    app.world
+        .resource_mut::<ButtonInput<MouseButton>>()
+        .press(MouseButton::Left);
+    app.update();
+    let state = app.world.resource::<ActionState<&str>>();
+    assert!(state.just_pressed("select"));
+
+    app.update();
+    let state = app.world.resource::<ActionState<&str>>();
+    assert!(state.pressed("select"));
+    assert!(!state.just_pressed("select"));
+
+    app.world
+        .resource_mut::<ButtonInput<MouseButton>>()
+        .release(MouseButton::Left);
+    app.update();
+    let state = app.world.resource::<ActionState<&str>>();
+    assert!(state.just_released("select"));
+    assert!(!state.pressed("select"));
+
+    // Mouse bindings aren't sequences and don't send events
+    assert!(app
+        .world
+        .resource::<Events<KeySequenceMatched<&str>>>()
+        .is_empty());
+}
@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+use bevy_tui::prelude::*;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::Rect;
+use ratatui::{TerminalOptions, Viewport};
+
+/// A terminal of the provided size drawing to nowhere.
+pub fn headless_terminal(width: u16, height: u16) -> bevy_tui::BevyTerminal {
+    let output = TerminalOutput::writer(std::io::sink());
+    let backend = ColorFilterBackend::new(CrosstermBackend::new(output));
+    let options = TerminalOptions {
+        viewport: Viewport::Fixed(Rect::new(0, 0, width, height)),
+    };
+    let terminal = ratatui::Terminal::with_options(backend, options)
+        .expect("fixed viewports don't query the terminal");
+
+    bevy_tui::Terminal(terminal)
+}
+
+/// An app with the TUI plugins drawing to nowhere through a terminal of the provided size, so it
+/// can be updated without a terminal and leaves the one running the tests alone.
+pub fn headless_app(width: u16, height: u16) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalTuiPlugins)
+        .insert_resource(headless_terminal(width, height));
+
+    app
+}
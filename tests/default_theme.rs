@@ -0,0 +1,49 @@
+//! Checks the built-in theme follows the render settings until the application picks its own.
+
+mod common;
+
+use bevy_tui::prelude::*;
+
+#[test]
+fn render_settings_inserted_after_the_plugin_pick_the_theme() {
+    let mut app = common::headless_app(80, 24);
+    app.insert_resource(TuiRenderSettings {
+        downsample_colors: false,
+        monochrome: true,
+    });
+
+    app.update();
+    assert_eq!(*app.world.resource::<TuiTheme>(), TuiTheme::monochrome());
+
+    app.world.resource_mut::<TuiRenderSettings>().monochrome = false;
+    app.update();
+    assert_eq!(*app.world.resource::<TuiTheme>(), TuiTheme::dark());
+}
+
+#[test]
+fn themes_provided_by_the_app_are_kept() {
+    let mut app = common::headless_app(80, 24);
+    app.insert_resource(TuiTheme::light());
+    app.update();
+
+    app.world.resource_mut::<TuiRenderSettings>().monochrome = true;
+    app.update();
+    assert_eq!(*app.world.resource::<TuiTheme>(), TuiTheme::light());
+}
+
+#[test]
+fn themes_changed_while_running_are_kept() {
+    let mut app = common::headless_app(80, 24);
+    app.insert_resource(TuiRenderSettings {
+        downsample_colors: false,
+        monochrome: false,
+    });
+    app.update();
+
+    *app.world.resource_mut::<TuiTheme>() = TuiTheme::light();
+    app.update();
+
+    app.world.resource_mut::<TuiRenderSettings>().monochrome = true;
+    app.update();
+    assert_eq!(*app.world.resource::<TuiTheme>(), TuiTheme::light());
+}
@@ -0,0 +1,86 @@
+//! Checks clicks move focus to the entity under the cursor on the terminal they came from.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_tui::prelude::*;
+use crossterm::event::{
+    Event as CrossEvent, KeyModifiers, MouseButton as CrossMouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Constraint, Rect};
+
+/// The left mouse button pressed at a position on a session's terminal.
+fn session_click(session: Entity, column: u16, row: u16) -> SessionEvent {
+    SessionEvent {
+        session,
+        event: CrossEvent::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(CrossMouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }),
+    }
+}
+
+#[test]
+fn sessions_of_different_sizes_focus_the_entity_under_their_own_cursor() {
+    let mut app = common::headless_app(80, 24);
+
+    let mut fields = Vec::new();
+    app.world
+        .spawn((
+            TuiNode::default(),
+            TuiLayout::horizontal([Constraint::Percentage(50); 2]),
+        ))
+        .with_children(|root| {
+            fields.push(root.spawn((TuiNode::default(), Focusable::default())).id());
+            fields.push(root.spawn((TuiNode::default(), Focusable::default())).id());
+        });
+
+    let wide = app
+        .world
+        .spawn(SessionBundle::new(common::headless_terminal(80, 24)))
+        .id();
+    let narrow = app
+        .world
+        .spawn(SessionBundle::new(common::headless_terminal(20, 10)))
+        .id();
+    app.update();
+
+    let areas = |app: &App, session| {
+        let areas = app
+            .world
+            .get::<LayoutAreas>(session)
+            .expect("session areas");
+        fields
+            .iter()
+            .map(|field| areas.area(*field))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        areas(&app, wide),
+        [
+            Some(Rect::new(0, 0, 40, 24)),
+            Some(Rect::new(40, 0, 40, 24))
+        ]
+    );
+    assert_eq!(
+        areas(&app, narrow),
+        [
+            Some(Rect::new(0, 0, 10, 10)),
+            Some(Rect::new(10, 0, 10, 10))
+        ]
+    );
+
+    app.world.send_event(session_click(wide, 15, 1));
+    app.world.send_event(session_click(narrow, 15, 1));
+    app.update();
+
+    let focused = |app: &App, session| {
+        app.world
+            .get::<FocusedEntity>(session)
+            .and_then(FocusedEntity::get)
+    };
+    assert_eq!(focused(&app, wide), Some(fields[0]));
+    assert_eq!(focused(&app, narrow), Some(fields[1]));
+}
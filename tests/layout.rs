@@ -0,0 +1,66 @@
+//! Checks the areas computed for the nodes of an entity hierarchy.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_tui::prelude::*;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::widgets::Block;
+
+#[test]
+fn children_split_the_area_of_their_root() {
+    let mut app = common::headless_app(80, 24);
+
+    let mut children = Vec::new();
+    let root = app
+        .world
+        .spawn((
+            TuiNode::default(),
+            TuiLayout::vertical([Constraint::Length(3), Constraint::Min(0)]),
+        ))
+        .with_children(|root| {
+            children.push(root.spawn(TuiNode::default()).id());
+            children.push(root.spawn(TuiNode::default()).id());
+        })
+        .id();
+
+    app.update();
+
+    let area = |entity| app.world.get::<TuiNode>(entity).map(TuiNode::area);
+    assert_eq!(area(root), Some(Rect::new(0, 0, 80, 24)));
+    assert_eq!(area(children[0]), Some(Rect::new(0, 0, 80, 3)));
+    assert_eq!(area(children[1]), Some(Rect::new(0, 3, 80, 21)));
+}
+
+#[test]
+fn children_are_placed_inside_blocks_and_inherit_without_a_layout() {
+    let mut app = common::headless_app(80, 24);
+
+    let mut panel = None;
+    let mut content = None;
+    app.world
+        .spawn((
+            TuiNode::default(),
+            TuiLayout::horizontal([Constraint::Length(20), Constraint::Min(0)]).spacing(1),
+        ))
+        .with_children(|root| {
+            root.spawn(TuiNode::default());
+
+            let id = root
+                .spawn((TuiNode::default(), TuiBlock(Block::bordered())))
+                .with_children(|panel| {
+                    content = Some(panel.spawn(TuiNode::default()).id());
+                })
+                .id();
+            panel = Some(id);
+        });
+
+    app.update();
+
+    let area = |entity: Option<Entity>| {
+        let entity = entity.expect("entity to be spawned");
+        app.world.get::<TuiNode>(entity).map(TuiNode::area)
+    };
+    assert_eq!(area(panel), Some(Rect::new(21, 0, 59, 24)));
+    assert_eq!(area(content), Some(Rect::new(22, 1, 57, 22)));
+}
@@ -0,0 +1,133 @@
+//! Checks the scroll wheel only moves the selection of lists shown on the terminal it came from.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_tui::prelude::*;
+use crossterm::event::{Event as CrossEvent, KeyModifiers, MouseEvent, MouseEventKind};
+use ratatui::layout::Constraint;
+use ratatui::widgets::List;
+
+fn spawn_list(app: &mut App) -> Entity {
+    app.world
+        .spawn(ListBundle {
+            list: TuiList::new(List::new(["alpha", "beta", "gamma"])),
+            ..default()
+        })
+        .id()
+}
+
+/// The scroll wheel turned down once over a position on a session's terminal.
+fn session_scroll(session: Entity, column: u16, row: u16) -> SessionEvent {
+    SessionEvent {
+        session,
+        event: CrossEvent::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }),
+    }
+}
+
+fn selected(app: &App, list: Entity) -> Option<usize> {
+    app.world
+        .get::<TuiList>(list)
+        .and_then(|list| list.state.selected())
+}
+
+#[test]
+fn scrolling_over_a_list_moves_its_selection() {
+    let mut app = common::headless_app(80, 24);
+    let list = spawn_list(&mut app);
+    app.update();
+
+    app.world.send_event(MouseInput::Scroll([0, 1], [2, 1]));
+    app.update();
+    assert_eq!(selected(&app, list), Some(0));
+
+    app.world.send_event(MouseInput::Scroll([0, 1], [2, 1]));
+    app.update();
+    assert_eq!(selected(&app, list), Some(1));
+}
+
+#[test]
+fn local_scrolling_leaves_lists_shown_by_sessions_alone() {
+    let mut app = common::headless_app(80, 24);
+    let list = spawn_list(&mut app);
+    app.world
+        .spawn(SessionBundle::new(common::headless_terminal(80, 24)))
+        .insert(TuiSession { root: Some(list) });
+    app.update();
+
+    app.world.send_event(MouseInput::Scroll([0, 1], [2, 1]));
+    app.update();
+    assert_eq!(selected(&app, list), None);
+}
+
+#[test]
+fn scrolling_in_a_session_moves_the_selection_of_its_lists() {
+    let mut app = common::headless_app(80, 24);
+    let local_list = spawn_list(&mut app);
+    let session_list = spawn_list(&mut app);
+    let session = app
+        .world
+        .spawn(SessionBundle::new(common::headless_terminal(40, 10)))
+        .insert(TuiSession {
+            root: Some(session_list),
+        })
+        .id();
+    app.update();
+
+    app.world.send_event(session_scroll(session, 2, 1));
+    app.update();
+    assert_eq!(selected(&app, session_list), Some(0));
+
+    app.world.send_event(session_scroll(session, 2, 1));
+    app.update();
+    assert_eq!(selected(&app, session_list), Some(1));
+    assert_eq!(selected(&app, local_list), None);
+}
+
+#[test]
+fn sessions_of_different_sizes_scroll_the_lists_under_their_own_cursor() {
+    let mut app = common::headless_app(80, 24);
+
+    let mut lists = Vec::new();
+    app.world
+        .spawn((
+            TuiNode::default(),
+            TuiLayout::horizontal([Constraint::Percentage(50); 2]),
+        ))
+        .with_children(|root| {
+            lists.push(root.spawn(ListBundle::default()).id());
+            lists.push(root.spawn(ListBundle::default()).id());
+        });
+    for list in &lists {
+        app.world
+            .entity_mut(*list)
+            .insert(TuiList::new(List::new(["alpha", "beta", "gamma"])));
+    }
+
+    // Both sessions show the shared root, so column 15 is over the left list on the wide one
+    // and over the right list on the narrow one
+    let wide = app
+        .world
+        .spawn(SessionBundle::new(common::headless_terminal(80, 24)))
+        .id();
+    let narrow = app
+        .world
+        .spawn(SessionBundle::new(common::headless_terminal(20, 10)))
+        .id();
+    app.update();
+
+    app.world.send_event(session_scroll(wide, 15, 1));
+    app.update();
+    assert_eq!(selected(&app, lists[0]), Some(0));
+    assert_eq!(selected(&app, lists[1]), None);
+
+    app.world.send_event(session_scroll(narrow, 15, 1));
+    app.update();
+    assert_eq!(selected(&app, lists[0]), Some(0));
+    assert_eq!(selected(&app, lists[1]), Some(0));
+}
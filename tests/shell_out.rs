@@ -0,0 +1,179 @@
+//! Checks how the application handles keys and signals around a [`ShellOut`] on a
+//! pseudo-terminal. Each check runs this test binary again as the application, so it has a
+//! terminal of its own.
+#![cfg(unix)]
+
+use std::process::Command;
+use std::time::Duration;
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy_tui::prelude::*;
+use ratatui::widgets::Paragraph;
+use signal_hook::consts::SIGINT;
+
+/// Set for the process running as the application to the name of the check.
+const APP_VAR: &str = "BEVY_TUI_TEST_APP";
+
+/// The arguments running only the named test in this test binary.
+fn test_args(test: &str) -> [&str; 4] {
+    ["--exact", test, "--nocapture", "--test-threads=1"]
+}
+
+/// Run the named test again as the application on a pseudo-terminal, returning its exit code.
+fn run_app(test: &str) -> Option<u32> {
+    let mut command = Command::new(std::env::current_exe().expect("test binary to be known"));
+    command.args(test_args(test));
+    command.env(APP_VAR, test);
+
+    let mut pty = PtyTerminal::spawn(&command, 80, 24).expect("application to start");
+    pty.wait_exit(Duration::from_secs(10))
+        .expect("application to be waited on")
+}
+
+/// Hand the terminal to a program as soon as the application starts, and raise `SIGINT` once it
+/// has exited.
+fn shell_out_then_interrupt(quit: TuiQuitSettings) -> Result<(), Box<dyn std::error::Error>> {
+    fn shell_out(mut commands: Commands, mut started: Local<bool>) {
+        if !*started {
+            *started = true;
+            commands.add(ShellOut::new(Command::new("true")));
+        }
+    }
+
+    fn interrupt(mut finished: EventReader<ShellOutFinished>) {
+        if finished.read().count() > 0 {
+            signal_hook::low_level::raise(SIGINT).expect("signal to be raised");
+        }
+    }
+
+    initialize_terminal()?;
+
+    App::new()
+        .add_plugins(MinimalTuiPlugins)
+        .insert_resource(quit)
+        .add_systems(Update, (shell_out, interrupt))
+        .run();
+
+    teardown_terminal()?;
+
+    Ok(())
+}
+
+#[test]
+fn signals_are_followed_again_after_shell_out() {
+    const TEST: &str = "signals_are_followed_again_after_shell_out";
+
+    if std::env::var(APP_VAR).as_deref() == Ok(TEST) {
+        shell_out_then_interrupt(TuiQuitSettings::default()).expect("application to run");
+        return;
+    }
+
+    // The interrupt is handled by exiting the application cleanly rather than being ignored
+    assert_eq!(run_app(TEST), Some(0));
+}
+
+#[test]
+fn unhandled_signals_stay_unhandled_after_shell_out() {
+    const TEST: &str = "unhandled_signals_stay_unhandled_after_shell_out";
+
+    if std::env::var(APP_VAR).as_deref() == Ok(TEST) {
+        let quit = TuiQuitSettings {
+            handle_signals: false,
+            ..default()
+        };
+        shell_out_then_interrupt(quit).expect("application to run");
+        return;
+    }
+
+    // The interrupt ends the process the default way instead of being handled or ignored
+    let code = run_app(TEST);
+    assert!(code.is_some_and(|code| code != 0), "exited with {code:?}");
+}
+
+/// Show that the application is ready, run a program once `r` is pressed and exit once it has.
+fn run_program_on_key() -> Result<(), Box<dyn std::error::Error>> {
+    #[allow(clippy::needless_pass_by_value)]
+    fn run_program(mut commands: Commands, keys: Res<ButtonInput<KeyCode>>) {
+        if keys.just_pressed(KeyCode::KeyR) {
+            // The program ignores Ctrl+C, so only the application could be ended by it
+            let mut program = Command::new("sh");
+            program.args(["-c", "trap '' INT; echo running; sleep 2"]);
+
+            commands.add(ShellOut::new(program));
+        }
+    }
+
+    fn exit_when_finished(
+        mut finished: EventReader<ShellOutFinished>,
+        mut exit: EventWriter<AppExit>,
+    ) {
+        if finished.read().count() > 0 {
+            exit.send(AppExit);
+        }
+    }
+
+    initialize_terminal()?;
+
+    App::new()
+        .add_plugins(MinimalTuiPlugins)
+        .add_systems(Startup, |mut commands: Commands| {
+            commands.spawn(ParagraphBundle {
+                paragraph: TuiParagraph(Paragraph::new("Ready")),
+                ..default()
+            });
+        })
+        .add_systems(Update, (run_program, exit_when_finished))
+        .run();
+
+    teardown_terminal()?;
+
+    Ok(())
+}
+
+#[test]
+fn keys_are_left_to_the_program() {
+    const TEST: &str = "keys_are_left_to_the_program";
+
+    if std::env::var(APP_VAR).as_deref() == Ok(TEST) {
+        run_program_on_key().expect("application to run");
+        return;
+    }
+
+    // Ctrl+Z only stops the application along with the program under a shell with job control
+    if Command::new("bash").arg("-c").arg("true").status().is_err() {
+        eprintln!("skipping, bash is needed for job control");
+        return;
+    }
+
+    for keys in [&["\x1a"][..], &["\x03", "\x03"]] {
+        // The shell continues the application if it was stopped, and exits with its status
+        let mut command = Command::new("bash");
+        command.args(["-mc", "\"$0\" \"$@\" || fg"]);
+        command.arg(std::env::current_exe().expect("test binary to be known"));
+        command.args(test_args(TEST));
+        command.env(APP_VAR, TEST);
+
+        let mut pty = PtyTerminal::spawn(&command, 80, 24).expect("shell to start");
+        assert!(pty.wait_for(Duration::from_secs(10), |screen| {
+            screen.contents().contains("Ready")
+        }));
+
+        pty.send("r").expect("key to be sent");
+        assert!(pty.wait_for(Duration::from_secs(10), |screen| {
+            screen.contents().contains("running")
+        }));
+
+        for key in keys {
+            pty.send(key).expect("key to be sent");
+            std::thread::sleep(Duration::from_millis(100));
+        }
+
+        let code = pty.wait_exit(Duration::from_secs(10));
+        assert_eq!(
+            code.expect("shell to be waited on"),
+            Some(0),
+            "after {keys:?}"
+        );
+    }
+}
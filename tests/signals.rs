@@ -0,0 +1,104 @@
+//! Checks that the signals the application takes over behave the default way again once it has
+//! been dropped. Signals affect the whole process, so each check runs this test binary again in a
+//! process of its own and looks at how that process ended.
+#![cfg(unix)]
+
+mod common;
+
+use std::os::raw::c_int;
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM, SIGTSTP};
+
+/// Set for the process running a check to the signal it should raise once the application is
+/// gone.
+const SIGNAL_VAR: &str = "BEVY_TUI_TEST_SIGNAL";
+
+/// How long a process stopped by a check is left before being continued.
+const STOPPED_FOR: Duration = Duration::from_secs(1);
+
+/// Run the application for a single frame and drop it.
+fn run_and_drop_app() {
+    common::headless_app(80, 24).update();
+}
+
+/// When running as the process of a check, raise its signal after the application is dropped.
+/// Returning from the test means the signal was ignored, unless it stopped the process until the
+/// check continued it.
+fn raise_after_drop() -> bool {
+    let Ok(signal) = std::env::var(SIGNAL_VAR) else {
+        return false;
+    };
+    let signal = signal.parse().expect("signal to be a number");
+
+    run_and_drop_app();
+
+    let raised = Instant::now();
+    signal_hook::low_level::raise(signal).expect("signal to be raised");
+
+    if signal == SIGTSTP {
+        assert!(
+            raised.elapsed() >= STOPPED_FOR / 2,
+            "the process wasn't stopped"
+        );
+    }
+
+    true
+}
+
+/// Run the test again in a process of its own that raises the signal.
+fn spawn_raising(test: &str, signal: c_int) -> Child {
+    Command::new(std::env::current_exe().expect("test binary to be known"))
+        .args(["--exact", test, "--nocapture", "--test-threads=1"])
+        .env(SIGNAL_VAR, signal.to_string())
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("test binary to run")
+}
+
+/// Run the test again in a process of its own that raises the signal, returning the signal that
+/// ended it if any.
+fn raise_in_process(test: &str, signal: c_int) -> Option<c_int> {
+    let status = spawn_raising(test, signal)
+        .wait()
+        .expect("test binary to exit");
+
+    status.signal()
+}
+
+#[test]
+fn quit_signals_end_the_process_after_drop() {
+    if raise_after_drop() {
+        return;
+    }
+
+    for signal in [SIGINT, SIGTERM, SIGHUP] {
+        assert_eq!(
+            raise_in_process("quit_signals_end_the_process_after_drop", signal),
+            Some(signal),
+            "signal {signal} didn't end the process"
+        );
+    }
+}
+
+#[test]
+fn suspend_signal_stops_the_process_after_drop() {
+    if raise_after_drop() {
+        return;
+    }
+
+    let mut process = spawn_raising("suspend_signal_stops_the_process_after_drop", SIGTSTP);
+    std::thread::sleep(STOPPED_FOR);
+
+    let pid = process.id().to_string();
+    let continued = Command::new("kill")
+        .args(["-CONT", &pid])
+        .status()
+        .expect("kill to run");
+    assert!(continued.success());
+
+    let status = process.wait().expect("test binary to exit");
+    assert!(status.success(), "{status}");
+}
@@ -0,0 +1,147 @@
+//! Checks a telnet client connecting over loopback is sized to its window and shown the UI.
+
+mod common;
+
+use std::io::{ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy_tui::prelude::*;
+use ratatui::widgets::Paragraph;
+
+const IAC: u8 = 255;
+const DO: u8 = 253;
+const SB: u8 = 250;
+const SE: u8 = 240;
+const OPT_NAWS: u8 = 31;
+
+/// Where the test is told the address the server is listening on.
+#[derive(Resource)]
+struct AddressReport(Mutex<Option<Sender<SocketAddr>>>);
+
+#[allow(clippy::needless_pass_by_value)]
+fn report_address(server: Res<TelnetServer>, report: Res<AddressReport>) {
+    let Some(address) = server.local_addr() else {
+        return;
+    };
+
+    if let Some(sender) = report.0.lock().expect("not poisoned").take() {
+        sender.send(address).expect("the test to be waiting");
+    }
+}
+
+/// Set by the test once it has seen what it was waiting for, asking the app to exit.
+#[derive(Resource)]
+struct ExitRequest(Arc<AtomicBool>);
+
+#[allow(clippy::needless_pass_by_value)]
+fn exit_when_requested(request: Res<ExitRequest>, mut exit: EventWriter<AppExit>) {
+    if request.0.load(Ordering::Relaxed) {
+        exit.send(AppExit);
+    }
+}
+
+/// Read what the server sent until the check passes, or until it closes the connection when
+/// there is no check.
+fn read_until(client: &mut TcpStream, received: &mut Vec<u8>, check: impl Fn(&[u8]) -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    let mut buffer = [0; 4096];
+
+    while !check(received) {
+        assert!(
+            Instant::now() < deadline,
+            "timed out, received {received:?}"
+        );
+
+        match client.read(&mut buffer) {
+            Ok(0) => return,
+            Ok(len) => received.extend_from_slice(&buffer[..len]),
+            Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(err) => panic!("failed to read from the server: {err}"),
+        }
+    }
+}
+
+/// What was sent after the last row of a 40 by 10 window was cleared.
+fn redrawn(received: &[u8]) -> Option<&[u8]> {
+    let cleared = b"\x1b[10;1H\x1b[J";
+    let start = received
+        .windows(cleared.len())
+        .position(|window| window == cleared)?;
+
+    Some(&received[start + cleared.len()..])
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+#[test]
+fn clients_negotiate_their_size_and_receive_frames() {
+    let (address_sender, address) = channel();
+    let exit = Arc::new(AtomicBool::new(false));
+    let exit_request = ExitRequest(Arc::clone(&exit));
+
+    let app = std::thread::spawn(move || {
+        let mut app = common::headless_app(80, 24);
+        app.add_plugins(TelnetPlugin)
+            .insert_resource(TelnetSettings {
+                address: ([127, 0, 0, 1], 0).into(),
+                max_clients: 1,
+            })
+            .insert_resource(AddressReport(Mutex::new(Some(address_sender))))
+            .insert_resource(exit_request)
+            .add_systems(Update, (report_address, exit_when_requested));
+        app.world.spawn(ParagraphBundle {
+            paragraph: TuiParagraph(Paragraph::new("hello telnet")),
+            ..default()
+        });
+
+        app.run();
+    });
+
+    let address = address
+        .recv_timeout(Duration::from_secs(10))
+        .expect("the server to start listening");
+    let mut client = TcpStream::connect(address).expect("to connect over loopback");
+    client
+        .set_read_timeout(Some(Duration::from_millis(10)))
+        .expect("a read timeout");
+
+    // The server asks for the window size as soon as the client connects
+    let mut received = Vec::new();
+    read_until(&mut client, &mut received, |received| {
+        contains(received, &[IAC, DO, OPT_NAWS])
+    });
+
+    client
+        .write_all(&[IAC, SB, OPT_NAWS, 0, 40, 0, 10, IAC, SE])
+        .expect("to send the window size");
+    let negotiated = received.len();
+
+    // Resizing clears the rows of the new size one by one and draws the whole screen again
+    read_until(&mut client, &mut received, |received| {
+        redrawn(&received[negotiated..]).map_or(false, |redrawn| {
+            contains(redrawn, b"\x1b[1;1Hhello\x1b[1;7Htelnet")
+        })
+    });
+
+    let resized = &received[negotiated..];
+    let text = String::from_utf8_lossy(resized);
+    assert!(!contains(resized, b"\x1b[11;1H"), "received {text:?}");
+
+    exit.store(true, Ordering::Relaxed);
+    read_until(&mut client, &mut received, |_| false);
+    app.join().expect("the app to exit cleanly");
+
+    // The client's terminal is restored before it is disconnected
+    let text = String::from_utf8_lossy(&received);
+    assert!(received.ends_with(b"\x1b[?1049l"), "received {text:?}");
+}
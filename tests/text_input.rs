@@ -0,0 +1,168 @@
+//! Checks editing, selecting and scrolling the value of a text input.
+
+use bevy::prelude::*;
+use bevy_tui::prelude::*;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+fn press(input: &mut TextInput, code: KeyCode, modifiers: KeyModifiers) {
+    input.handle_key(KeyEvent::new(code, modifiers));
+}
+
+/// An app showing a single input holding the value.
+fn input_app(value: &str) -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins(MinimalTuiPlugins);
+    let input = app
+        .world
+        .spawn(TextInputBundle {
+            text_input: TextInput::new().with_value(value),
+            ..default()
+        })
+        .id();
+
+    (app, input)
+}
+
+/// Press the keys on the input shown by the app, then draw it into a single row of the provided
+/// width.
+fn draw_after(app: &mut App, input: Entity, keys: &[KeyCode], width: u16) -> String {
+    let mut text_input = app
+        .world
+        .get_mut::<TextInput>(input)
+        .expect("the input to exist");
+    for &key in keys {
+        press(&mut text_input, key, KeyModifiers::NONE);
+    }
+
+    let buffer = render_buffer(&mut app.world, width, 1);
+    buffer_snapshot(&buffer)
+}
+
+#[test]
+fn word_jumps_stop_at_the_edges_of_words() {
+    let mut input = TextInput::new().with_value("hello, big world");
+
+    press(&mut input, KeyCode::Left, KeyModifiers::CONTROL);
+    assert_eq!(input.cursor(), 11);
+    press(&mut input, KeyCode::Left, KeyModifiers::CONTROL);
+    assert_eq!(input.cursor(), 7);
+    press(&mut input, KeyCode::Left, KeyModifiers::CONTROL);
+    assert_eq!(input.cursor(), 0);
+    press(&mut input, KeyCode::Left, KeyModifiers::CONTROL);
+    assert_eq!(input.cursor(), 0);
+
+    press(&mut input, KeyCode::Right, KeyModifiers::CONTROL);
+    assert_eq!(input.cursor(), 5);
+    press(&mut input, KeyCode::Right, KeyModifiers::CONTROL);
+    assert_eq!(input.cursor(), 10);
+
+    // The readline bindings move the same way
+    press(&mut input, KeyCode::Char('f'), KeyModifiers::ALT);
+    assert_eq!(input.cursor(), 16);
+    press(&mut input, KeyCode::Char('b'), KeyModifiers::ALT);
+    assert_eq!(input.cursor(), 11);
+}
+
+#[test]
+fn shift_selects_and_typing_replaces_the_selection() {
+    let mut input = TextInput::new().with_value("hello world");
+
+    press(
+        &mut input,
+        KeyCode::Left,
+        KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+    );
+    assert_eq!(input.selection(), Some(6..11));
+    assert_eq!(input.selected_text(), Some("world"));
+
+    press(&mut input, KeyCode::Left, KeyModifiers::SHIFT);
+    assert_eq!(input.selected_text(), Some(" world"));
+
+    press(&mut input, KeyCode::Char('!'), KeyModifiers::NONE);
+    assert_eq!(input.value(), "hello!");
+    assert_eq!(input.selection(), None);
+    assert_eq!(input.cursor(), 6);
+
+    press(&mut input, KeyCode::Home, KeyModifiers::SHIFT);
+    input.insert_str("bye");
+    assert_eq!(input.value(), "bye");
+}
+
+#[test]
+fn moving_without_shift_collapses_the_selection() {
+    let mut input = TextInput::new().with_value("hello world");
+
+    input.select_all();
+    press(&mut input, KeyCode::Left, KeyModifiers::NONE);
+    assert_eq!(input.selection(), None);
+    assert_eq!(input.cursor(), 0);
+
+    input.select_all();
+    press(&mut input, KeyCode::Right, KeyModifiers::NONE);
+    assert_eq!(input.selection(), None);
+    assert_eq!(input.cursor(), 11);
+
+    // The edge is used whichever end of the selection the cursor is at
+    press(&mut input, KeyCode::Home, KeyModifiers::NONE);
+    press(&mut input, KeyCode::Right, KeyModifiers::SHIFT);
+    press(&mut input, KeyCode::Right, KeyModifiers::SHIFT);
+    press(&mut input, KeyCode::Right, KeyModifiers::NONE);
+    assert_eq!(input.cursor(), 2);
+
+    press(&mut input, KeyCode::Left, KeyModifiers::SHIFT);
+    press(&mut input, KeyCode::Left, KeyModifiers::SHIFT);
+    press(&mut input, KeyCode::Left, KeyModifiers::NONE);
+    assert_eq!(input.cursor(), 0);
+
+    // Without a selection the cursor moves by a character as usual
+    press(&mut input, KeyCode::Right, KeyModifiers::NONE);
+    assert_eq!(input.cursor(), 1);
+}
+
+#[test]
+fn scrolls_to_keep_the_cursor_visible() {
+    let (mut app, input) = input_app("abcdefgh");
+
+    // A cell is left free at the end for the cursor
+    assert_eq!(draw_after(&mut app, input, &[], 5), "efgh\n");
+    assert_eq!(draw_after(&mut app, input, &[KeyCode::Home], 5), "abcde\n");
+    assert_eq!(
+        draw_after(&mut app, input, &[KeyCode::Right; 4], 5),
+        "abcde\n"
+    );
+    assert_eq!(draw_after(&mut app, input, &[KeyCode::Right], 5), "bcdef\n");
+    assert_eq!(draw_after(&mut app, input, &[KeyCode::End], 5), "efgh\n");
+
+    // The view only scrolls back once the cursor moves past its start
+    assert_eq!(
+        draw_after(&mut app, input, &[KeyCode::Left; 4], 5),
+        "efgh\n"
+    );
+    assert_eq!(draw_after(&mut app, input, &[KeyCode::Left], 5), "defgh\n");
+
+    // A wider terminal shows the rest of the value again once the cursor moves
+    assert_eq!(
+        draw_after(&mut app, input, &[KeyCode::Home], 20),
+        "abcdefgh\n"
+    );
+}
+
+#[test]
+fn wide_characters_take_two_cells() {
+    let mut input = TextInput::new().with_value("日本語");
+    press(&mut input, KeyCode::Left, KeyModifiers::NONE);
+    press(&mut input, KeyCode::Backspace, KeyModifiers::NONE);
+    assert_eq!(input.value(), "日語");
+    assert_eq!(input.cursor(), 1);
+
+    let (mut app, input) = input_app("日本語");
+    assert_eq!(draw_after(&mut app, input, &[], 5), "本語\n");
+    assert_eq!(draw_after(&mut app, input, &[KeyCode::Home], 5), "日本\n");
+    assert_eq!(
+        draw_after(&mut app, input, &[KeyCode::Right; 3], 5),
+        "本語\n"
+    );
+
+    let (mut app, input) = input_app("a日本");
+    assert_eq!(draw_after(&mut app, input, &[], 4), "本\n");
+}
@@ -0,0 +1,55 @@
+//! Checks the theme only replaces the settings of widgets on entities that opted in to it.
+
+use bevy::prelude::*;
+use bevy_tui::prelude::*;
+use ratatui::widgets::{Block, BorderType};
+
+fn app_with_theme() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalTuiPlugins)
+        .insert_resource(TuiTheme {
+            border_type: BorderType::Double,
+            ..TuiTheme::dark()
+        });
+    app
+}
+
+#[test]
+fn bundles_use_the_theme_border_type() {
+    let mut app = app_with_theme();
+    app.world.spawn(BlockBundle {
+        block: TuiBlock(Block::bordered()),
+        ..default()
+    });
+
+    let buffer = render_buffer(&mut app.world, 4, 3);
+    assert_eq!(buffer_snapshot(&buffer), "╔══╗\n║  ║\n╚══╝\n");
+}
+
+#[test]
+fn explicit_plain_borders_are_kept_when_not_themed() {
+    let mut app = app_with_theme();
+    app.world.spawn(BlockBundle {
+        block: TuiBlock(Block::bordered().border_type(BorderType::Plain)),
+        themed: Themed {
+            border_type: false,
+            ..default()
+        },
+        ..default()
+    });
+
+    let buffer = render_buffer(&mut app.world, 4, 3);
+    assert_eq!(buffer_snapshot(&buffer), "┌──┐\n│  │\n└──┘\n");
+}
+
+#[test]
+fn entities_without_themed_are_drawn_as_configured() {
+    let mut app = app_with_theme();
+    app.world.spawn((
+        TuiNode::default(),
+        TuiBlock(Block::bordered().border_type(BorderType::Thick)),
+    ));
+
+    let buffer = render_buffer(&mut app.world, 4, 3);
+    assert_eq!(buffer_snapshot(&buffer), "┏━━┓\n┃  ┃\n┗━━┛\n");
+}
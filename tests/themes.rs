@@ -0,0 +1,92 @@
+//! Loads the theme files shipped in `assets/themes` and checks they're applied as they reload.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_tui::prelude::*;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::BorderType;
+
+/// Update the app until the theme selected by the handle has been applied, or give up after a
+/// generous number of frames.
+fn update_until_loaded(app: &mut App, handle: &Handle<TuiTheme>) {
+    for _ in 0..1_000 {
+        app.update();
+
+        let themes = app.world.resource::<Assets<TuiTheme>>();
+        if let Some(theme) = themes.get(handle) {
+            if app.world.resource::<TuiTheme>() == theme {
+                return;
+            }
+        }
+    }
+
+    panic!("theme was never loaded");
+}
+
+fn load(app: &mut App, path: &'static str) -> Handle<TuiTheme> {
+    let handle = app.world.resource::<AssetServer>().load(path);
+    app.insert_resource(TuiThemeHandle(handle.clone()));
+    update_until_loaded(app, &handle);
+    handle
+}
+
+#[test]
+fn shipped_ron_theme_loads() {
+    let mut app = common::headless_app(80, 24);
+    load(&mut app, "themes/solarized.theme.ron");
+
+    let theme = app.world.resource::<TuiTheme>();
+    assert_eq!(
+        theme.primary,
+        Style::default().fg(Color::Rgb(0x26, 0x8b, 0xd2))
+    );
+    assert_eq!(
+        theme.focused,
+        Style::default()
+            .fg(Color::Rgb(0xb5, 0x89, 0x00))
+            .add_modifier(Modifier::BOLD)
+    );
+    assert_eq!(theme.border_type, BorderType::Rounded);
+}
+
+#[test]
+fn shipped_toml_theme_loads() {
+    let mut app = common::headless_app(80, 24);
+    load(&mut app, "themes/gruvbox.theme.toml");
+
+    let theme = app.world.resource::<TuiTheme>();
+    assert_eq!(
+        theme.selection,
+        Style::default()
+            .fg(Color::Rgb(0x28, 0x28, 0x28))
+            .bg(Color::Rgb(0x83, 0xa5, 0x98))
+    );
+    assert_eq!(theme.border_type, BorderType::Thick);
+}
+
+#[test]
+fn reloading_a_theme_replaces_the_resource() {
+    let mut app = common::headless_app(80, 24);
+    let handle = load(&mut app, "themes/solarized.theme.ron");
+
+    // Modifying the loaded asset is reported the same way as the file changing on disk
+    let mut themes = app.world.resource_mut::<Assets<TuiTheme>>();
+    let theme = themes.get_mut(&handle).expect("theme to be loaded");
+    theme.border_type = BorderType::Double;
+
+    // Asset events are sent after `PostUpdate`, so the change is picked up on the following frame
+    app.update();
+    app.update();
+
+    assert_eq!(
+        app.world.resource::<TuiTheme>().border_type,
+        BorderType::Double
+    );
+
+    load(&mut app, "themes/gruvbox.theme.toml");
+    assert_eq!(
+        app.world.resource::<TuiTheme>().border_type,
+        BorderType::Thick
+    );
+}